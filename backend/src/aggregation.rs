@@ -0,0 +1,340 @@
+use anyhow::{Result, anyhow};
+use rust_decimal::Decimal;
+use rust_decimal::prelude::{FromPrimitive, ToPrimitive};
+use serde::{Deserialize, Serialize};
+use crate::error::OracleError;
+use crate::oracle_client::AggregatedPrice;
+
+// Floor for relative confidence so a source reporting zero doesn't take all the weight (0.01 bps)
+const MIN_RELATIVE_CONFIDENCE: Decimal = Decimal::from_parts(1, 0, 0, false, 6);
+
+// Scales the median absolute deviation to a standard deviation for normally distributed prices
+const MAD_TO_STDDEV: f64 = 1.4826;
+
+/// One source's price as seen by an aggregation strategy
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SourcePrice {
+    pub price: Decimal,
+    pub confidence: Decimal, // Absolute, in quote currency
+    pub weight: f64,         // Configured source weight
+}
+
+/// Combines the valid source prices for a symbol into one mark price
+pub trait AggregationStrategy: Send + Sync {
+    fn aggregate(&self, prices: &[SourcePrice]) -> Result<Decimal>;
+    fn name(&self) -> &'static str;
+}
+
+/// Strategy selectable per symbol in config
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AggregationMethod {
+    Median,
+    #[default]
+    WeightedMedian,
+    TrimmedMean,
+    InverseVariance,
+}
+
+impl AggregationMethod {
+    pub fn strategy(self, trim_fraction: f64) -> Box<dyn AggregationStrategy> {
+        match self {
+            AggregationMethod::Median => Box::new(Median),
+            AggregationMethod::WeightedMedian => Box::new(WeightedMedian),
+            AggregationMethod::TrimmedMean => Box::new(TrimmedMean { trim_fraction }),
+            AggregationMethod::InverseVariance => Box::new(InverseVariance),
+        }
+    }
+}
+
+/// Plain median, ignoring weights and confidence; the mean of the middle two for an even count
+pub struct Median;
+
+impl AggregationStrategy for Median {
+    fn aggregate(&self, prices: &[SourcePrice]) -> Result<Decimal> {
+        let prices: Vec<Decimal> = valid_prices(prices)?.iter().map(|p| p.price).collect();
+        Ok(median_of(prices))
+    }
+
+    fn name(&self) -> &'static str {
+        "median"
+    }
+}
+
+/// Median by configured source weight; when the cumulative weight lands exactly on
+/// half, the two neighbouring prices are averaged
+pub struct WeightedMedian;
+
+impl AggregationStrategy for WeightedMedian {
+    fn aggregate(&self, prices: &[SourcePrice]) -> Result<Decimal> {
+        let sorted: Vec<_> = sorted_prices(prices)?.into_iter().filter(|p| p.weight > 0.0).collect();
+        let total: f64 = sorted.iter().map(|p| p.weight).sum();
+        if total <= 0.0 {
+            return Err(anyhow!("All sources have zero configured weight"));
+        }
+
+        let half = total / 2.0;
+        let mut cumulative = 0.0;
+        for (i, price) in sorted.iter().enumerate() {
+            cumulative += price.weight;
+            if (cumulative - half).abs() <= f64::EPSILON * total && i + 1 < sorted.len() {
+                return Ok((price.price + sorted[i + 1].price) / Decimal::TWO);
+            }
+            if cumulative > half {
+                return Ok(price.price);
+            }
+        }
+        Ok(sorted[sorted.len() - 1].price)
+    }
+
+    fn name(&self) -> &'static str {
+        "weighted_median"
+    }
+}
+
+/// Mean after dropping `floor(n * trim_fraction)` prices from each end
+pub struct TrimmedMean {
+    pub trim_fraction: f64,
+}
+
+impl AggregationStrategy for TrimmedMean {
+    fn aggregate(&self, prices: &[SourcePrice]) -> Result<Decimal> {
+        let sorted = sorted_prices(prices)?;
+        let trim = (sorted.len() as f64 * self.trim_fraction).floor() as usize;
+        let kept = &sorted[trim..sorted.len() - trim];
+        if kept.is_empty() {
+            return Err(anyhow!("Trimming {} of {} prices leaves nothing to average", 2 * trim, sorted.len()));
+        }
+        Ok(kept.iter().map(|p| p.price).sum::<Decimal>() / Decimal::from(kept.len()))
+    }
+
+    fn name(&self) -> &'static str {
+        "trimmed_mean"
+    }
+}
+
+/// Weighted mean with weights `source_weight / (confidence / price)^2`, so sources are
+/// compared on relative rather than dollar confidence
+pub struct InverseVariance;
+
+impl AggregationStrategy for InverseVariance {
+    fn aggregate(&self, prices: &[SourcePrice]) -> Result<Decimal> {
+        let mut weighted_sum = Decimal::ZERO;
+        let mut total_weight = Decimal::ZERO;
+        for p in valid_prices(prices)? {
+            let relative = (p.confidence.abs() / p.price).max(MIN_RELATIVE_CONFIDENCE);
+            let source_weight = Decimal::from_f64(p.weight)
+                .ok_or_else(|| anyhow!("Invalid source weight {}", p.weight))?;
+            let weight = source_weight / (relative * relative);
+            weighted_sum += p.price * weight;
+            total_weight += weight;
+        }
+        if total_weight <= Decimal::ZERO {
+            return Err(anyhow!("All sources have zero configured weight"));
+        }
+        Ok(weighted_sum / total_weight)
+    }
+
+    fn name(&self) -> &'static str {
+        "inverse_variance"
+    }
+}
+
+/// What to serve when fewer than `min_sources` sources survive aggregation
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DegradedMode {
+    /// Fail the request
+    Reject,
+    /// Serve the last price that met quorum, flagged as such
+    LastGood,
+    /// Serve what the remaining sources (usually one) give, with widened confidence
+    #[default]
+    SingleSource,
+}
+
+/// How a published price was produced, reported alongside it
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PriceMode {
+    #[default]
+    Normal,
+    LastGood,
+    SingleSource,
+}
+
+impl PriceMode {
+    /// Name stored in `price_feeds.mode`, matching the serialized form
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PriceMode::Normal => "normal",
+            PriceMode::LastGood => "last_good",
+            PriceMode::SingleSource => "single_source",
+        }
+    }
+
+    pub fn parse(mode: &str) -> Result<Self> {
+        match mode {
+            "normal" => Ok(PriceMode::Normal),
+            "last_good" => Ok(PriceMode::LastGood),
+            "single_source" => Ok(PriceMode::SingleSource),
+            other => Err(anyhow!("Unknown price mode: {}", other)),
+        }
+    }
+}
+
+/// Per-symbol quorum and the degraded mode applied below it
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(default)]
+pub struct QuorumPolicy {
+    pub min_sources: usize,
+    pub degraded_mode: DegradedMode,
+    /// Applied to the confidence of prices served in `single_source` mode
+    pub confidence_multiplier: f64,
+    /// Oldest last good price `last_good` mode will serve
+    pub last_good_max_age_seconds: u64,
+}
+
+impl Default for QuorumPolicy {
+    fn default() -> Self {
+        Self {
+            min_sources: 2,
+            degraded_mode: DegradedMode::SingleSource,
+            confidence_multiplier: 2.0,
+            last_good_max_age_seconds: 300,
+        }
+    }
+}
+
+impl QuorumPolicy {
+    /// Passes through an aggregate that met quorum; otherwise applies the degraded mode
+    /// to the short aggregate (or the error that prevented one)
+    pub fn apply(
+        &self,
+        symbol: &str,
+        aggregated: Result<AggregatedPrice>,
+        last_good: Option<&AggregatedPrice>,
+        now: i64,
+    ) -> Result<AggregatedPrice> {
+        let (below_quorum, shortfall) = match aggregated {
+            Ok(price) if price.sources.len() >= self.min_sources => return Ok(price),
+            Ok(price) => {
+                let shortfall = OracleError::QuorumNotMet {
+                    symbol: symbol.to_string(),
+                    available: price.sources.len(),
+                    required: self.min_sources,
+                }.into();
+                (Some(price), shortfall)
+            }
+            Err(e) => (None, e),
+        };
+
+        match self.degraded_mode {
+            DegradedMode::Reject => Err(shortfall),
+            DegradedMode::SingleSource => match below_quorum {
+                Some(price) => Ok(AggregatedPrice {
+                    confidence: price.confidence * Decimal::try_from(self.confidence_multiplier)?,
+                    mode: PriceMode::SingleSource,
+                    ..price
+                }),
+                None => Err(shortfall),
+            },
+            DegradedMode::LastGood => match last_good {
+                Some(last) if now - last.timestamp <= self.last_good_max_age_seconds as i64 => {
+                    Ok(AggregatedPrice { mode: PriceMode::LastGood, ..last.clone() })
+                }
+                _ => Err(shortfall.context(format!(
+                    "No last good {} price within {}s", symbol, self.last_good_max_age_seconds
+                ))),
+            },
+        }
+    }
+}
+
+/// Drops sources that stray from the cross-source median before aggregation
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OutlierFilter {
+    /// Scaled MADs from the median beyond which a source is an outlier
+    pub mad_multiplier: f64,
+    /// Relative deviation that is never treated as an outlier, however tight the others agree
+    pub min_deviation: f64,
+    /// Relative deviation that is always an outlier
+    pub max_deviation: f64,
+}
+
+impl Default for OutlierFilter {
+    fn default() -> Self {
+        Self { mad_multiplier: 3.0, min_deviation: 0.005, max_deviation: 0.05 }
+    }
+}
+
+impl OutlierFilter {
+    /// Indices of the sources to exclude, with the reason for each. Fails when the
+    /// outliers aren't a minority, since there's then no consensus to fall back on.
+    pub fn outliers(&self, prices: &[SourcePrice]) -> Result<Vec<(usize, String)>> {
+        if prices.len() < 2 {
+            return Ok(Vec::new());
+        }
+
+        // Prices stay exact; only the relative deviations compared to the thresholds are floats
+        let median = Median.aggregate(prices)?;
+        let deviations: Vec<Decimal> = prices.iter().map(|p| (p.price - median).abs()).collect();
+        let relative_mad = MAD_TO_STDDEV * ratio(median_of(deviations.clone()), median);
+
+        let mut excluded = Vec::new();
+        for (i, &deviation) in deviations.iter().enumerate() {
+            let relative = ratio(deviation, median);
+            if relative <= self.min_deviation {
+                continue;
+            }
+            if relative > self.max_deviation {
+                excluded.push((i, format!("{:.2}% from median {}", relative * 100.0, median)));
+            } else if prices.len() >= 3 && relative > self.mad_multiplier * relative_mad {
+                excluded.push((i, format!(
+                    "{:.2}% from median {}, beyond {} scaled MADs ({:.2}%)",
+                    relative * 100.0, median, self.mad_multiplier, relative_mad * 100.0
+                )));
+            }
+        }
+
+        if excluded.len() * 2 >= prices.len() {
+            return Err(anyhow!(
+                "{} of {} sources deviate from median {}, no majority left to aggregate",
+                excluded.len(), prices.len(), median
+            ));
+        }
+        Ok(excluded)
+    }
+}
+
+// Mean of the middle two for an even count; `values` must not be empty
+fn median_of(mut values: Vec<Decimal>) -> Decimal {
+    values.sort();
+    let mid = values.len() / 2;
+    if values.len() % 2 == 0 {
+        (values[mid - 1] + values[mid]) / Decimal::TWO
+    } else {
+        values[mid]
+    }
+}
+
+// `value / base` as a float, for comparing against configured ratios; `base` is positive
+fn ratio(value: Decimal, base: Decimal) -> f64 {
+    (value / base).to_f64().unwrap_or(f64::INFINITY)
+}
+
+fn valid_prices(prices: &[SourcePrice]) -> Result<Vec<SourcePrice>> {
+    if prices.is_empty() {
+        return Err(anyhow!("No price data to aggregate"));
+    }
+    if let Some(bad) = prices.iter().find(|p| p.price <= Decimal::ZERO) {
+        return Err(anyhow!("Cannot aggregate non-positive price {}", bad.price));
+    }
+    Ok(prices.to_vec())
+}
+
+fn sorted_prices(prices: &[SourcePrice]) -> Result<Vec<SourcePrice>> {
+    let mut sorted = valid_prices(prices)?;
+    sorted.sort_by_key(|p| p.price);
+    Ok(sorted)
+}
@@ -0,0 +1,51 @@
+use rust_decimal::Decimal;
+
+/// Sanity limits a symbol's prices must meet to be served
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PriceBounds {
+    /// Lowest acceptable price; prices must also be positive
+    pub min_price: Decimal,
+    pub max_price: Option<Decimal>,
+    /// Oldest aggregate that may be served outside `last_good` mode
+    pub max_age_seconds: u64,
+    /// Oldest source price that is aggregated
+    pub max_source_age_seconds: u64,
+    /// Relative deviation from the cross-source median that always excludes a source
+    pub max_deviation: f64,
+}
+
+impl Default for PriceBounds {
+    fn default() -> Self {
+        Self {
+            min_price: Decimal::ZERO,
+            max_price: None,
+            max_age_seconds: 30,
+            max_source_age_seconds: 30,
+            max_deviation: 0.05,
+        }
+    }
+}
+
+impl PriceBounds {
+    /// Why `price` falls outside the band, if it does
+    pub fn violation(&self, price: Decimal) -> Option<String> {
+        if price <= Decimal::ZERO {
+            return Some(format!("price {} is not positive", price));
+        }
+        if price < self.min_price {
+            return Some(format!("price {} below minimum {}", price, self.min_price));
+        }
+        match self.max_price {
+            Some(max_price) if price > max_price => Some(format!("price {} above maximum {}", price, max_price)),
+            _ => None,
+        }
+    }
+
+    pub fn is_fresh(&self, age_seconds: i64) -> bool {
+        age_seconds <= self.max_age_seconds as i64
+    }
+
+    pub fn is_source_fresh(&self, age_seconds: i64) -> bool {
+        age_seconds <= self.max_source_age_seconds as i64
+    }
+}
@@ -0,0 +1,220 @@
+use std::collections::HashMap;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use anyhow::{Result, anyhow};
+use async_trait::async_trait;
+use log::warn;
+use reqwest::Client;
+use rust_decimal::Decimal;
+use serde::Deserialize;
+use crate::config::ExchangeConfig;
+use crate::oracle_client::{OracleClient, PriceData};
+use crate::quote::QuoteAsset;
+
+/// Centralized exchanges with a supported public ticker API
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Exchange {
+    Binance,
+    Coinbase,
+    Kraken,
+}
+
+impl Exchange {
+    /// Source name reported on prices and used for weights
+    pub fn name(&self) -> &'static str {
+        match self {
+            Exchange::Binance => "Binance",
+            Exchange::Coinbase => "Coinbase",
+            Exchange::Kraken => "Kraken",
+        }
+    }
+
+    pub fn default_base_url(&self) -> &'static str {
+        match self {
+            Exchange::Binance => "https://api.binance.com",
+            Exchange::Coinbase => "https://api.exchange.coinbase.com",
+            Exchange::Kraken => "https://api.kraken.com",
+        }
+    }
+
+    /// Exchange market for an oracle symbol, e.g. BTC/USD -> BTCUSDT on Binance
+    pub fn market_symbol(&self, symbol: &str) -> Option<String> {
+        let (base, quote) = symbol.split_once('/')?;
+        Some(match self {
+            // Would be USDTUSDT, which doesn't exist
+            Exchange::Binance if base == "USDT" && quote == "USD" => return None,
+            // Binance has no USD spot books; USDT is the deepest dollar quote
+            Exchange::Binance => format!("{}{}", base, if quote == "USD" { "USDT" } else { quote }),
+            Exchange::Coinbase => format!("{}-{}", base, quote),
+            Exchange::Kraken => format!("{}{}", if base == "BTC" { "XBT" } else { base }, quote),
+        })
+    }
+
+    /// Quote of the default market for a symbol, where it differs from the symbol's own
+    pub fn market_quote(&self, symbol: &str) -> Option<QuoteAsset> {
+        match self {
+            Exchange::Binance if QuoteAsset::of_symbol(symbol) == Some(QuoteAsset::Usd) => Some(QuoteAsset::Usdt),
+            _ => None,
+        }
+    }
+
+    fn ticker_url(&self, base_url: &str, market: &str) -> String {
+        match self {
+            Exchange::Binance => format!("{}/api/v3/ticker/bookTicker?symbol={}", base_url, market),
+            Exchange::Coinbase => format!("{}/products/{}/ticker", base_url, market),
+            Exchange::Kraken => format!("{}/0/public/Ticker?pair={}", base_url, market),
+        }
+    }
+
+    /// Best bid, best ask and exchange timestamp (if the ticker has one)
+    fn parse_ticker(&self, body: &serde_json::Value) -> Result<(Decimal, Decimal, Option<i64>)> {
+        match self {
+            // {"symbol":"BTCUSDT","bidPrice":"67425.00","bidQty":"1.2","askPrice":"67425.10","askQty":"0.8"}
+            Exchange::Binance => Ok((
+                parse_decimal(&body["bidPrice"], "bidPrice")?,
+                parse_decimal(&body["askPrice"], "askPrice")?,
+                None,
+            )),
+            // {"ask":"67425.10","bid":"67425.00","price":"67425.05","time":"2026-10-18T09:30:12.123456Z",...}
+            Exchange::Coinbase => {
+                let time = body["time"]
+                    .as_str()
+                    .ok_or_else(|| anyhow!("time not found in ticker"))?;
+                let timestamp = chrono::DateTime::parse_from_rfc3339(time)?.timestamp();
+                Ok((
+                    parse_decimal(&body["bid"], "bid")?,
+                    parse_decimal(&body["ask"], "ask")?,
+                    Some(timestamp),
+                ))
+            }
+            // {"error":[],"result":{"XXBTZUSD":{"a":["67425.10","1","1.000"],"b":["67425.00","2","2.000"],...}}}
+            Exchange::Kraken => {
+                if let Some(error) = body["error"].as_array().and_then(|errors| errors.first()) {
+                    return Err(anyhow!("Kraken error: {}", error));
+                }
+                // Result is keyed by Kraken's internal pair name, not the one requested
+                let ticker = body["result"]
+                    .as_object()
+                    .and_then(|result| result.values().next())
+                    .ok_or_else(|| anyhow!("No ticker in Kraken response"))?;
+                Ok((
+                    parse_decimal(&ticker["b"][0], "b")?,
+                    parse_decimal(&ticker["a"][0], "a")?,
+                    None,
+                ))
+            }
+        }
+    }
+}
+
+fn parse_decimal(value: &serde_json::Value, field: &str) -> Result<Decimal> {
+    value
+        .as_str()
+        .ok_or_else(|| anyhow!("{} not found in ticker", field))?
+        .parse::<Decimal>()
+        .map_err(|e| anyhow!("Invalid {}: {}", field, e))
+}
+
+/// Public ticker client for one exchange, priced at the top-of-book mid
+pub struct CexClient {
+    client: Client,
+    exchange: Exchange,
+    base_url: String,
+    market_overrides: HashMap<String, String>,
+    quote_overrides: HashMap<String, QuoteAsset>,
+}
+
+impl CexClient {
+    pub fn new(exchange: Exchange, base_url: Option<String>, market_overrides: HashMap<String, String>) -> Self {
+        Self {
+            client: Client::new(),
+            exchange,
+            base_url: base_url.unwrap_or_else(|| exchange.default_base_url().to_string()),
+            market_overrides,
+            quote_overrides: HashMap::new(),
+        }
+    }
+
+    pub fn from_config(config: &ExchangeConfig) -> Self {
+        Self {
+            quote_overrides: config.quotes.clone(),
+            ..Self::new(config.name, config.base_url.clone(), config.symbols.clone())
+        }
+    }
+
+    fn market(&self, symbol: &str) -> Result<String> {
+        self.market_overrides
+            .get(symbol)
+            .cloned()
+            .or_else(|| self.exchange.market_symbol(symbol))
+            .ok_or_else(|| anyhow!("No {} market for symbol: {}", self.exchange.name(), symbol))
+    }
+}
+
+#[async_trait]
+impl OracleClient for CexClient {
+    async fn get_price(&self, symbol: &str) -> Result<PriceData> {
+        let market = self.market(symbol)?;
+        let url = self.exchange.ticker_url(&self.base_url, &market);
+
+        let response = self.client
+            .get(&url)
+            .timeout(Duration::from_secs(5))
+            .header("User-Agent", "GoQuant-Oracle/1.0")
+            .send()
+            .await?;
+
+        let status = response.status();
+        let response_text = response.text().await?;
+        if !status.is_success() {
+            return Err(anyhow!("{} returned {} for {}: {}", self.exchange.name(), status, market, response_text));
+        }
+        let body: serde_json::Value = serde_json::from_str(&response_text)
+            .map_err(|e| anyhow!("Failed to parse {} response '{}': {}", self.exchange.name(), response_text, e))?;
+
+        let (bid, ask, exchange_time) = self.exchange.parse_ticker(&body)?;
+        if bid <= Decimal::ZERO || ask < bid {
+            return Err(anyhow!("Invalid {} book for {}: bid {} ask {}", self.exchange.name(), market, bid, ask));
+        }
+
+        let timestamp = match exchange_time {
+            Some(timestamp) => timestamp,
+            None => SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs() as i64,
+        };
+
+        Ok(PriceData {
+            symbol: symbol.to_string(),
+            price: (bid + ask) / Decimal::TWO,
+            confidence: (ask - bid) / Decimal::TWO,
+            timestamp,
+            source: self.exchange.name().to_string(),
+        })
+    }
+
+    async fn get_multiple_prices(&self, symbols: &[String]) -> Result<Vec<PriceData>> {
+        let futures = symbols.iter().map(|symbol| self.get_price(symbol));
+        let price_results = futures::future::join_all(futures).await;
+
+        let mut results = Vec::new();
+        for (symbol, result) in symbols.iter().zip(price_results) {
+            match result {
+                Ok(price_data) => results.push(price_data),
+                Err(e) => warn!("Failed to fetch {} price from {}: {}", symbol, self.exchange.name(), e),
+            }
+        }
+
+        Ok(results)
+    }
+
+    fn get_name(&self) -> &str {
+        self.exchange.name()
+    }
+
+    async fn supports(&self, symbol: &str) -> bool {
+        self.market(symbol).is_ok()
+    }
+
+    fn quote_asset(&self, symbol: &str) -> Option<QuoteAsset> {
+        self.quote_overrides.get(symbol).copied().or_else(|| self.exchange.market_quote(symbol))
+    }
+}
@@ -0,0 +1,239 @@
+use std::collections::HashMap;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use anyhow::{Result, anyhow};
+use async_trait::async_trait;
+use log::warn;
+use reqwest::Client;
+use rust_decimal::Decimal;
+use thiserror::Error;
+use tokio::sync::RwLock;
+use crate::feed_registry::SharedFeedRegistry;
+use crate::oracle_client::{OracleClient, PriceData};
+
+// Function selectors: keccak256(signature)[..4]
+const LATEST_ROUND_DATA_SELECTOR: &str = "0xfeaf968c";
+const DECIMALS_SELECTOR: &str = "0x313ce567";
+const WORD_SIZE: usize = 32;
+
+#[derive(Debug, Error, PartialEq)]
+pub enum ChainlinkError {
+    #[error("invalid eth_call result: {0}")]
+    InvalidHex(String),
+    #[error("eth_call returned {0} bytes, expected {1}")]
+    UnexpectedLength(usize, usize),
+    #[error("{0} does not fit in its expected width")]
+    Overflow(&'static str),
+    #[error("round {0} has not been answered")]
+    IncompleteRound(u128),
+    #[error("answer from round {answered_in_round} carried over into round {round_id}")]
+    StaleRound { round_id: u128, answered_in_round: u128 },
+    #[error("answer {0} is not a positive price")]
+    NonPositiveAnswer(i128),
+    #[error("round updated {age}s ago, heartbeat is {heartbeat}s")]
+    HeartbeatExceeded { age: i64, heartbeat: u64 },
+}
+
+/// Decoded `latestRoundData()` return tuple
+#[derive(Debug, Clone, PartialEq)]
+pub struct RoundData {
+    pub round_id: u128,
+    pub answer: i128,
+    pub started_at: u64,
+    pub updated_at: u64,
+    pub answered_in_round: u128,
+}
+
+impl RoundData {
+    /// Rejects unanswered, carried-over, non-positive or out-of-heartbeat rounds
+    pub fn validate(&self, now: i64, heartbeat_seconds: u64) -> Result<(), ChainlinkError> {
+        if self.updated_at == 0 {
+            return Err(ChainlinkError::IncompleteRound(self.round_id));
+        }
+        if self.answered_in_round < self.round_id {
+            return Err(ChainlinkError::StaleRound {
+                round_id: self.round_id,
+                answered_in_round: self.answered_in_round,
+            });
+        }
+        if self.answer <= 0 {
+            return Err(ChainlinkError::NonPositiveAnswer(self.answer));
+        }
+
+        let age = now - self.updated_at as i64;
+        if age > heartbeat_seconds as i64 {
+            return Err(ChainlinkError::HeartbeatExceeded { age, heartbeat: heartbeat_seconds });
+        }
+        Ok(())
+    }
+
+    /// The answer scaled by the feed's `decimals()`
+    pub fn price(&self, decimals: u8) -> Result<Decimal, ChainlinkError> {
+        Decimal::try_from_i128_with_scale(self.answer, decimals as u32)
+            .map_err(|_| ChainlinkError::Overflow("answer"))
+    }
+}
+
+/// (uint80 roundId, int256 answer, uint256 startedAt, uint256 updatedAt, uint80 answeredInRound)
+pub fn decode_latest_round_data(result: &str) -> Result<RoundData, ChainlinkError> {
+    let data = decode_hex(result)?;
+    if data.len() != 5 * WORD_SIZE {
+        return Err(ChainlinkError::UnexpectedLength(data.len(), 5 * WORD_SIZE));
+    }
+    let word = |i: usize| &data[i * WORD_SIZE..(i + 1) * WORD_SIZE];
+
+    Ok(RoundData {
+        round_id: read_uint(word(0), "roundId")?,
+        answer: read_int(word(1))?,
+        started_at: read_uint(word(2), "startedAt")? as u64,
+        updated_at: read_uint(word(3), "updatedAt")? as u64,
+        answered_in_round: read_uint(word(4), "answeredInRound")?,
+    })
+}
+
+pub fn decode_decimals(result: &str) -> Result<u8, ChainlinkError> {
+    let data = decode_hex(result)?;
+    if data.len() != WORD_SIZE {
+        return Err(ChainlinkError::UnexpectedLength(data.len(), WORD_SIZE));
+    }
+    u8::try_from(read_uint(&data, "decimals")?).map_err(|_| ChainlinkError::Overflow("decimals"))
+}
+
+fn decode_hex(result: &str) -> Result<Vec<u8>, ChainlinkError> {
+    let hex = result.strip_prefix("0x").unwrap_or(result);
+    if hex.len() % 2 != 0 {
+        return Err(ChainlinkError::InvalidHex(result.to_string()));
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&hex[i..i + 2], 16))
+        .collect::<Result<_, _>>()
+        .map_err(|_| ChainlinkError::InvalidHex(result.to_string()))
+}
+
+// Unsigned word whose value fits in the low 16 bytes
+fn read_uint(word: &[u8], field: &'static str) -> Result<u128, ChainlinkError> {
+    if word[..16].iter().any(|&b| b != 0) {
+        return Err(ChainlinkError::Overflow(field));
+    }
+    Ok(u128::from_be_bytes(word[16..].try_into().expect("32-byte word")))
+}
+
+// Two's complement int256; the high 16 bytes must only be sign extension
+fn read_int(word: &[u8]) -> Result<i128, ChainlinkError> {
+    let value = i128::from_be_bytes(word[16..].try_into().expect("32-byte word"));
+    let sign = if value < 0 { 0xff } else { 0x00 };
+    if word[..16].iter().any(|&b| b != sign) {
+        return Err(ChainlinkError::Overflow("answer"));
+    }
+    Ok(value)
+}
+
+/// Chainlink aggregator reader over raw JSON-RPC `eth_call`
+pub struct ChainlinkClient {
+    client: Client,
+    rpc_url: String,
+    registry: SharedFeedRegistry,
+    default_heartbeat: Duration,
+    decimals: RwLock<HashMap<String, u8>>, // Per aggregator, immutable on-chain
+}
+
+impl ChainlinkClient {
+    pub fn new(rpc_url: String, registry: SharedFeedRegistry, default_heartbeat: Duration) -> Self {
+        Self {
+            client: Client::new(),
+            rpc_url,
+            registry,
+            default_heartbeat,
+            decimals: RwLock::new(HashMap::new()),
+        }
+    }
+
+    async fn eth_call(&self, to: &str, data: &str) -> Result<String> {
+        let payload = serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "eth_call",
+            "params": [{ "to": to, "data": data }, "latest"]
+        });
+
+        let response = self.client
+            .post(&self.rpc_url)
+            .json(&payload)
+            .timeout(Duration::from_secs(10))
+            .header("User-Agent", "GoQuant-Oracle/1.0")
+            .send()
+            .await?;
+
+        let response_text = response.text().await?;
+        let response_json: serde_json::Value = serde_json::from_str(&response_text)
+            .map_err(|e| anyhow!("Failed to parse EVM RPC response '{}': {}", response_text, e))?;
+
+        if let Some(error) = response_json.get("error") {
+            return Err(anyhow!("eth_call to {} failed: {}", to, error));
+        }
+        response_json["result"]
+            .as_str()
+            .map(str::to_string)
+            .ok_or_else(|| anyhow!("No result in eth_call response"))
+    }
+
+    async fn get_decimals(&self, address: &str) -> Result<u8> {
+        if let Some(decimals) = self.decimals.read().await.get(address) {
+            return Ok(*decimals);
+        }
+
+        let decimals = decode_decimals(&self.eth_call(address, DECIMALS_SELECTOR).await?)?;
+        self.decimals.write().await.insert(address.to_string(), decimals);
+        Ok(decimals)
+    }
+}
+
+#[async_trait]
+impl OracleClient for ChainlinkClient {
+    async fn get_price(&self, symbol: &str) -> Result<PriceData> {
+        let feed = self.registry.read().await
+            .chainlink_feed(symbol)
+            .cloned()
+            .ok_or_else(|| anyhow!("Chainlink aggregator not found for symbol: {}", symbol))?;
+
+        let decimals = self.get_decimals(&feed.address).await?;
+        let round = decode_latest_round_data(&self.eth_call(&feed.address, LATEST_ROUND_DATA_SELECTOR).await?)?;
+
+        let now = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs() as i64;
+        let heartbeat = feed.heartbeat_seconds.unwrap_or(self.default_heartbeat.as_secs());
+        round.validate(now, heartbeat)?;
+
+        // No confidence is published; the answer may drift up to the deviation threshold unreported
+        let price = round.price(decimals)?;
+        Ok(PriceData {
+            symbol: symbol.to_string(),
+            price,
+            confidence: price * Decimal::from(feed.deviation_bps) / Decimal::from(10_000),
+            timestamp: round.updated_at as i64,
+            source: "Chainlink".to_string(),
+        })
+    }
+
+    async fn get_multiple_prices(&self, symbols: &[String]) -> Result<Vec<PriceData>> {
+        let futures = symbols.iter().map(|symbol| self.get_price(symbol));
+        let price_results = futures::future::join_all(futures).await;
+
+        let mut results = Vec::new();
+        for (symbol, result) in symbols.iter().zip(price_results) {
+            match result {
+                Ok(price_data) => results.push(price_data),
+                Err(e) => warn!("Failed to fetch {} price from Chainlink: {}", symbol, e),
+            }
+        }
+
+        Ok(results)
+    }
+
+    fn get_name(&self) -> &str {
+        "Chainlink"
+    }
+
+    async fn supports(&self, symbol: &str) -> bool {
+        self.registry.read().await.chainlink_feed(symbol).is_some()
+    }
+}
@@ -0,0 +1,313 @@
+use std::collections::HashMap;
+use std::path::Path;
+use anyhow::{Result, Context};
+use rust_decimal::Decimal;
+use serde::Deserialize;
+use crate::aggregation::{AggregationMethod, DegradedMode, QuorumPolicy};
+use crate::cex_client::Exchange;
+use crate::quote::{DepegAction, QuoteAsset};
+
+pub const DEFAULT_CONFIG_PATH: &str = "config/dev.toml";
+
+/// Typed view of the oracle-related sections of `config/dev.toml`
+#[derive(Debug, Clone, Deserialize)]
+pub struct OracleFileConfig {
+    pub solana: SolanaConfig,
+    pub oracle: OracleSettings,
+    pub trading_pairs: TradingPairs,
+    #[serde(default)]
+    pub oracle_feeds: OracleFeeds,
+    #[serde(default)]
+    pub exchanges: Vec<ExchangeConfig>,
+    pub evm: Option<EvmConfig>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct SolanaConfig {
+    pub rpc_url: String,
+    pub ws_url: String,
+    pub commitment: String,
+    pub timeout_seconds: u64,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct EvmConfig {
+    pub rpc_url: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct OracleSettings {
+    pub max_price_deviation_bps: u32,
+    pub max_price_age_seconds: u64,
+    pub update_interval_ms: u64,
+    pub confidence_threshold: f64,
+    pub pyth_weight: f64,
+    pub switchboard_weight: f64,
+    #[serde(default)]
+    pub pyth_onchain: PythOnChainMode,
+    /// Subscribe to Hermes over SSE instead of polling on every cache miss
+    #[serde(default)]
+    pub pyth_stream: bool,
+    /// Receive Switchboard rounds through `accountSubscribe` on `solana.ws_url`
+    #[serde(default)]
+    pub switchboard_stream: bool,
+    #[serde(default)]
+    pub resilience: ResilienceConfig,
+    /// Default strategy for combining source prices; pairs may override it
+    #[serde(default)]
+    pub aggregation: AggregationMethod,
+    /// Fraction dropped from each end by `trimmed_mean`, in [0, 0.5)
+    #[serde(default = "default_trim_fraction")]
+    pub trim_fraction: f64,
+    #[serde(default)]
+    pub outliers: OutlierConfig,
+    /// Default quorum policy; trading pairs may override `min_sources` and `degraded_mode`
+    #[serde(default)]
+    pub quorum: QuorumPolicy,
+    #[serde(default)]
+    pub quotes: QuoteConfig,
+    #[serde(default)]
+    pub cache: CacheConfig,
+    /// Default refresh cadence; trading pairs may override the intervals with `refresh_interval_ms`,
+    /// `min_refresh_interval_ms` and `max_refresh_interval_ms`
+    #[serde(default)]
+    pub schedule: ScheduleConfig,
+    /// Default price bands and ages; trading pairs may override any of them
+    #[serde(default)]
+    pub bounds: BoundsConfig,
+}
+
+/// Aggregated price cache. Within `stale_while_revalidate_ms` past the TTL the cached
+/// price is still served while one background fetch refreshes it; 0 disables that.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct CacheConfig {
+    pub ttl_ms: u64,
+    pub stale_while_revalidate_ms: u64,
+}
+
+impl Default for CacheConfig {
+    fn default() -> Self {
+        Self { ttl_ms: 500, stale_while_revalidate_ms: 0 }
+    }
+}
+
+/// How often the monitoring loop refreshes each symbol. Starting from `interval_ms`, the
+/// interval moves between `max_interval_ms` in calm markets and `min_interval_ms` once
+/// realized volatility or the spread between sources reaches its threshold; both bounds
+/// default to `interval_ms`, which keeps it fixed.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct ScheduleConfig {
+    pub interval_ms: u64,
+    pub min_interval_ms: Option<u64>,
+    pub max_interval_ms: Option<u64>,
+    /// Fraction of the interval randomly added or removed each time, in [0, 1)
+    pub jitter: f64,
+    /// Realized volatility per minute at which `min_interval_ms` is reached
+    pub volatility_bps: u32,
+    /// Spread between sources at which `min_interval_ms` is reached
+    pub dispersion_bps: u32,
+}
+
+impl Default for ScheduleConfig {
+    fn default() -> Self {
+        Self {
+            interval_ms: 250,
+            min_interval_ms: None,
+            max_interval_ms: None,
+            jitter: 0.1,
+            volatility_bps: 50,
+            dispersion_bps: 25,
+        }
+    }
+}
+
+/// Sanity limits on a symbol's prices. Unset fields fall back to the `[oracle.bounds]`
+/// value, then to built-in defaults; `max_deviation_bps` falls back to `max_price_deviation_bps`.
+#[derive(Debug, Clone, Copy, Default, Deserialize)]
+pub struct BoundsConfig {
+    pub min_price: Option<Decimal>,
+    pub max_price: Option<Decimal>,
+    pub max_age_seconds: Option<u64>,
+    pub max_source_age_seconds: Option<u64>,
+    pub max_deviation_bps: Option<u32>,
+}
+
+/// Median-based outlier exclusion; `max_price_deviation_bps` is the hard cap
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct OutlierConfig {
+    pub mad_multiplier: f64,
+    /// Deviation from the median that is always tolerated
+    pub min_deviation_bps: u32,
+}
+
+impl Default for OutlierConfig {
+    fn default() -> Self {
+        Self { mad_multiplier: 3.0, min_deviation_bps: 50 }
+    }
+}
+
+/// Conversion of stablecoin-quoted sources into USD
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct QuoteConfig {
+    /// Trading pair pricing each stablecoin in USD, e.g. USDT = "USDT/USD"
+    pub feeds: HashMap<QuoteAsset, String>,
+    pub max_depeg_bps: u32,
+    pub depeg_action: DepegAction,
+}
+
+impl Default for QuoteConfig {
+    fn default() -> Self {
+        Self { feeds: HashMap::new(), max_depeg_bps: 100, depeg_action: DepegAction::Exclude }
+    }
+}
+
+fn default_trim_fraction() -> f64 {
+    0.2
+}
+
+/// Retry and circuit breaker settings applied to every oracle source
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct ResilienceConfig {
+    pub max_attempts: u32,
+    pub initial_backoff_ms: u64,
+    pub max_backoff_ms: u64,
+    /// Fraction of each backoff that may be randomly shaved off, in [0, 1]
+    pub jitter: f64,
+    /// Budget for a whole call including retries
+    pub call_deadline_ms: u64,
+    /// Number of recent calls the error rate is computed over
+    pub breaker_window: usize,
+    /// Calls needed in the window before the breaker may open, at most `breaker_window`
+    pub breaker_min_calls: usize,
+    /// Error rate that opens the breaker, in (0, 1]
+    pub breaker_error_rate: f64,
+    /// How long an open breaker rejects calls before letting a probe through
+    pub breaker_open_ms: u64,
+}
+
+impl Default for ResilienceConfig {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            initial_backoff_ms: 50,
+            max_backoff_ms: 1000,
+            jitter: 0.5,
+            call_deadline_ms: 2000,
+            breaker_window: 20,
+            breaker_min_calls: 5,
+            breaker_error_rate: 0.5,
+            breaker_open_ms: 30_000,
+        }
+    }
+}
+
+/// How the on-chain Pyth price accounts are used alongside Hermes
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PythOnChainMode {
+    /// Read the account only when Hermes fails
+    #[default]
+    Fallback,
+    /// Aggregate the account as its own source
+    Source,
+    Disabled,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct TradingPairs {
+    pub pairs: Vec<TradingPairConfig>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct TradingPairConfig {
+    pub symbol: String,
+    pub tick_size: String,
+    pub min_order_size: String,
+    pub max_leverage: u32,
+    pub funding_interval_hours: u32,
+    pub aggregation: Option<AggregationMethod>,
+    pub min_sources: Option<usize>,
+    pub degraded_mode: Option<DegradedMode>,
+    /// Priced from other pairs instead of its own feeds
+    pub synthetic: Option<SyntheticConfig>,
+    pub bounds: Option<BoundsConfig>,
+    /// Faster for hot symbols, slower for the long tail
+    pub refresh_interval_ms: Option<u64>,
+    pub min_refresh_interval_ms: Option<u64>,
+    pub max_refresh_interval_ms: Option<u64>,
+}
+
+/// Product of the `multiply` pairs over the product of the `divide` pairs
+#[derive(Debug, Clone, Deserialize)]
+pub struct SyntheticConfig {
+    pub multiply: Vec<String>,
+    #[serde(default)]
+    pub divide: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct OracleFeeds {
+    #[serde(default)]
+    pub pyth: Vec<PythFeedConfig>,
+    #[serde(default)]
+    pub switchboard: Vec<SwitchboardFeedConfig>,
+    #[serde(default)]
+    pub chainlink: Vec<ChainlinkFeedConfig>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct PythFeedConfig {
+    pub symbol: String,
+    pub feed_id: String,        // Hermes price feed ID (32-byte hex)
+    pub pubkey: Option<String>, // On-chain price account
+    pub weight: Option<f64>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct SwitchboardFeedConfig {
+    pub symbol: String,
+    pub pubkey: String,         // Aggregator account
+    pub weight: Option<f64>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ChainlinkFeedConfig {
+    pub symbol: String,
+    pub address: String,                // Aggregator proxy on the `evm` chain
+    pub weight: Option<f64>,
+    pub heartbeat_seconds: Option<u64>, // Max round age, defaults to oracle.max_price_age_seconds
+    pub deviation_bps: Option<u32>,     // Update threshold, reported as confidence
+}
+
+/// Centralized exchange ticker source; prices every trading pair it lists
+#[derive(Debug, Clone, Deserialize)]
+pub struct ExchangeConfig {
+    pub name: Exchange,
+    pub base_url: Option<String>,
+    pub weight: Option<f64>,
+    /// Market overrides for pairs the default mapping gets wrong, e.g. "BTC/USD" = "BTCUSDC"
+    #[serde(default)]
+    pub symbols: HashMap<String, String>,
+    /// Quote of the markets in `symbols` where the default guess is wrong, e.g. "BTC/USD" = "USDC"
+    #[serde(default)]
+    pub quotes: HashMap<String, QuoteAsset>,
+}
+
+impl OracleFileConfig {
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let contents = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read oracle config {}", path.display()))?;
+        Self::parse(&contents)
+            .with_context(|| format!("Invalid oracle config {}", path.display()))
+    }
+
+    pub fn parse(contents: &str) -> Result<Self> {
+        Ok(toml::from_str(contents)?)
+    }
+}
@@ -0,0 +1,132 @@
+use std::sync::Arc;
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::Json;
+use log::error;
+use serde::Serialize;
+use thiserror::Error;
+
+/// Why a price couldn't be served or a request couldn't be handled. Raised where the
+/// failure happens and carried through `anyhow` to the HTTP layer, which recovers it
+/// with `OracleError::from`. Cheap to clone, so one failed fetch can answer every
+/// caller waiting on it.
+#[derive(Debug, Clone, Error)]
+pub enum OracleError {
+    #[error("Unknown symbol {0}")]
+    UnknownSymbol(String),
+    #[error("{0}")]
+    InvalidRequest(String),
+    #[error("Admin API is disabled")]
+    AdminDisabled,
+    #[error("Missing or invalid admin token")]
+    Unauthorized,
+    #[error("Symbol {symbol} is still used by {dependents}")]
+    SymbolInUse { symbol: String, dependents: String },
+    #[error("No price data for {symbol}: {reason}")]
+    SourcesUnavailable { symbol: String, reason: String },
+    #[error("{symbol} price is stale: {age_seconds}s old")]
+    Stale { symbol: String, age_seconds: i64 },
+    #[error("{symbol} out of bounds: {reason}")]
+    OutOfBounds { symbol: String, reason: String },
+    #[error("Sources for {symbol} disagree: {reason}")]
+    DeviationTooHigh { symbol: String, reason: String },
+    #[error("Only {available} of {required} required sources for {symbol}")]
+    QuorumNotMet { symbol: String, available: usize, required: usize },
+    #[error("Storage error: {0}")]
+    Storage(Arc<sqlx::Error>),
+    #[error("{0:#}")]
+    Internal(Arc<anyhow::Error>),
+}
+
+/// JSON body of every error response
+#[derive(Debug, Serialize)]
+pub struct ErrorBody {
+    pub error: ErrorDetail,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ErrorDetail {
+    pub code: &'static str,
+    pub message: String,
+}
+
+impl OracleError {
+    /// Machine-readable code returned in the error body
+    pub fn code(&self) -> &'static str {
+        match self {
+            OracleError::UnknownSymbol(_) => "unknown_symbol",
+            OracleError::InvalidRequest(_) => "invalid_request",
+            OracleError::AdminDisabled => "admin_disabled",
+            OracleError::Unauthorized => "unauthorized",
+            OracleError::SymbolInUse { .. } => "symbol_in_use",
+            OracleError::SourcesUnavailable { .. } => "sources_unavailable",
+            OracleError::Stale { .. } => "stale_price",
+            OracleError::OutOfBounds { .. } => "out_of_bounds",
+            OracleError::DeviationTooHigh { .. } => "deviation_too_high",
+            OracleError::QuorumNotMet { .. } => "quorum_not_met",
+            OracleError::Storage(_) => "storage_error",
+            OracleError::Internal(_) => "internal_error",
+        }
+    }
+
+    /// Client mistakes are 4xx; upstream sources giving no usable data are 502, and
+    /// data that is there but can't be trusted right now is 503
+    pub fn status(&self) -> StatusCode {
+        match self {
+            OracleError::UnknownSymbol(_) => StatusCode::NOT_FOUND,
+            OracleError::InvalidRequest(_) => StatusCode::BAD_REQUEST,
+            OracleError::AdminDisabled => StatusCode::FORBIDDEN,
+            OracleError::Unauthorized => StatusCode::UNAUTHORIZED,
+            OracleError::SymbolInUse { .. } => StatusCode::CONFLICT,
+            OracleError::SourcesUnavailable { .. }
+            | OracleError::OutOfBounds { .. }
+            | OracleError::DeviationTooHigh { .. } => StatusCode::BAD_GATEWAY,
+            OracleError::Stale { .. } | OracleError::QuorumNotMet { .. } => StatusCode::SERVICE_UNAVAILABLE,
+            OracleError::Storage(_) | OracleError::Internal(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+
+    /// Storage and internal failures may carry upstream responses or SQL, so clients only
+    /// get the code; the detail is logged here instead
+    pub fn body(&self) -> ErrorBody {
+        let generic = match self {
+            OracleError::Storage(_) => Some("Storage error"),
+            OracleError::Internal(_) => Some("Internal error"),
+            _ => None,
+        };
+        let message = match generic {
+            Some(generic) => {
+                error!("{}: {}", self.code(), self);
+                generic.to_string()
+            }
+            None => self.to_string(),
+        };
+        ErrorBody { error: ErrorDetail { code: self.code(), message } }
+    }
+}
+
+// Recovers the typed error from wherever it was raised, even under added context
+impl From<anyhow::Error> for OracleError {
+    fn from(error: anyhow::Error) -> Self {
+        let error = match error.downcast::<OracleError>() {
+            Ok(error) => return error,
+            Err(error) => error,
+        };
+        match error.downcast::<sqlx::Error>() {
+            Ok(error) => OracleError::Storage(Arc::new(error)),
+            Err(error) => OracleError::Internal(Arc::new(error)),
+        }
+    }
+}
+
+impl From<sqlx::Error> for OracleError {
+    fn from(error: sqlx::Error) -> Self {
+        OracleError::Storage(Arc::new(error))
+    }
+}
+
+impl IntoResponse for OracleError {
+    fn into_response(self) -> Response {
+        (self.status(), Json(self.body())).into_response()
+    }
+}
@@ -0,0 +1,652 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+use anyhow::{Result, anyhow};
+use log::warn;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use tokio::sync::RwLock;
+use crate::aggregation::{AggregationMethod, AggregationStrategy, OutlierFilter, QuorumPolicy};
+use crate::bounds::PriceBounds;
+use crate::config::{BoundsConfig, OracleFileConfig, OracleSettings, ResilienceConfig, TradingPairConfig};
+use crate::quote::{QuoteAsset, QuoteConversion};
+use crate::scheduler::RefreshSchedule;
+use crate::synthetic::SyntheticPair;
+
+/// Registry handle the oracle clients read from; symbols can be added at runtime
+pub type SharedFeedRegistry = Arc<RwLock<FeedRegistry>>;
+
+// Most Chainlink crypto/USD feeds update on a 0.5% deviation
+const DEFAULT_CHAINLINK_DEVIATION_BPS: u32 = 50;
+
+/// Feeds backing one symbol; at least one must be set
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SymbolFeeds {
+    pub pyth_feed_id: Option<String>,
+    pub pyth_price_account: Option<String>,
+    pub switchboard_address: Option<String>,
+}
+
+/// Chainlink aggregator on the configured EVM chain
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChainlinkFeed {
+    pub address: String,
+    pub heartbeat_seconds: Option<u64>,
+    pub deviation_bps: u32,
+}
+
+/// Symbol -> feed mapping shared by the oracle clients, built from config
+#[derive(Debug, Clone, Default)]
+pub struct FeedRegistry {
+    symbols: Vec<String>,
+    pyth_feed_ids: HashMap<String, String>,
+    pyth_price_accounts: HashMap<String, String>,
+    switchboard_addresses: HashMap<String, String>,
+    chainlink_feeds: HashMap<String, ChainlinkFeed>,
+    source_weights: HashMap<String, HashMap<String, f64>>,
+    exchange_weights: HashMap<String, f64>,
+    aggregation: HashMap<String, AggregationMethod>,
+    quorum: HashMap<String, QuorumPolicy>,
+    synthetic: HashMap<String, SyntheticPair>,
+    bounds: HashMap<String, PriceBounds>,
+    schedules: HashMap<String, RefreshSchedule>,
+    default_pyth_weight: f64,
+    default_switchboard_weight: f64,
+    default_aggregation: AggregationMethod,
+    trim_fraction: f64,
+    outlier_filter: OutlierFilter,
+    default_quorum: QuorumPolicy,
+    default_bounds: PriceBounds,
+    default_schedule: RefreshSchedule,
+    default_chainlink_heartbeat: u64,
+    quote_conversion: QuoteConversion,
+}
+
+impl FeedRegistry {
+    pub fn from_config(config: &OracleFileConfig) -> Result<Self> {
+        validate_resilience(&config.oracle.resilience).map_err(|e| anyhow!("oracle.resilience: {}", e))?;
+        let mut registry = Self {
+            default_pyth_weight: validate_weight(config.oracle.pyth_weight)
+                .map_err(|e| anyhow!("oracle.pyth_weight: {}", e))?,
+            default_switchboard_weight: validate_weight(config.oracle.switchboard_weight)
+                .map_err(|e| anyhow!("oracle.switchboard_weight: {}", e))?,
+            default_aggregation: config.oracle.aggregation,
+            trim_fraction: validate_trim_fraction(config.oracle.trim_fraction)
+                .map_err(|e| anyhow!("oracle.trim_fraction: {}", e))?,
+            outlier_filter: outlier_filter(&config.oracle)?,
+            default_quorum: quorum_policy(&config.oracle)?,
+            default_bounds: default_bounds(&config.oracle)?,
+            default_schedule: default_schedule(&config.oracle)?,
+            default_chainlink_heartbeat: config.oracle.max_price_age_seconds,
+            ..Self::default()
+        };
+
+        for pair in &config.trading_pairs.pairs {
+            validate_symbol(&pair.symbol)?;
+            if registry.symbols.contains(&pair.symbol) {
+                return Err(anyhow!("Duplicate trading pair: {}", pair.symbol));
+            }
+            registry.symbols.push(pair.symbol.clone());
+            if let Some(method) = pair.aggregation {
+                registry.aggregation.insert(pair.symbol.clone(), method);
+            }
+            if pair.min_sources.is_some() || pair.degraded_mode.is_some() {
+                let policy = QuorumPolicy {
+                    min_sources: validate_min_sources(pair.min_sources.unwrap_or(registry.default_quorum.min_sources))
+                        .map_err(|e| anyhow!("min_sources for {}: {}", pair.symbol, e))?,
+                    degraded_mode: pair.degraded_mode.unwrap_or(registry.default_quorum.degraded_mode),
+                    ..registry.default_quorum
+                };
+                registry.quorum.insert(pair.symbol.clone(), policy);
+            }
+            if let Some(bounds) = &pair.bounds {
+                let bounds = price_bounds(registry.default_bounds, bounds, config.oracle.outliers.min_deviation_bps)
+                    .map_err(|e| anyhow!("bounds for {}: {}", pair.symbol, e))?;
+                registry.bounds.insert(pair.symbol.clone(), bounds);
+            }
+            if pair.refresh_interval_ms.is_some() || pair.min_refresh_interval_ms.is_some() || pair.max_refresh_interval_ms.is_some() {
+                let defaults = registry.default_schedule;
+                let interval = pair.refresh_interval_ms.map(Duration::from_millis).unwrap_or(defaults.interval);
+                // An interval outside the default range widens it rather than failing
+                let schedule = refresh_schedule(
+                    defaults,
+                    interval,
+                    pair.min_refresh_interval_ms.map(Duration::from_millis).unwrap_or(defaults.min_interval.min(interval)),
+                    pair.max_refresh_interval_ms.map(Duration::from_millis).unwrap_or(defaults.max_interval.max(interval)),
+                ).map_err(|e| anyhow!("refresh intervals for {}: {}", pair.symbol, e))?;
+                registry.schedules.insert(pair.symbol.clone(), schedule);
+            }
+        }
+
+        for pair in &config.trading_pairs.pairs {
+            if let Some(synthetic) = synthetic_pair(pair, &config.trading_pairs.pairs)? {
+                registry.synthetic.insert(pair.symbol.clone(), synthetic);
+            }
+        }
+
+        for feed in &config.oracle_feeds.pyth {
+            registry.ensure_known_symbol(&feed.symbol, "Pyth")?;
+            let feed_id = normalize_pyth_feed_id(&feed.feed_id)
+                .map_err(|e| anyhow!("Pyth feed for {}: {}", feed.symbol, e))?;
+            if let Some(pubkey) = &feed.pubkey {
+                validate_solana_address(pubkey)
+                    .map_err(|e| anyhow!("Pyth price account for {}: {}", feed.symbol, e))?;
+            }
+            let weight = validate_weight(feed.weight.unwrap_or(registry.default_pyth_weight))
+                .map_err(|e| anyhow!("Pyth weight for {}: {}", feed.symbol, e))?;
+
+            if registry.pyth_feed_ids.insert(feed.symbol.clone(), feed_id).is_some() {
+                return Err(anyhow!("Duplicate Pyth feed for {}", feed.symbol));
+            }
+            registry.set_weight(&feed.symbol, "Pyth", weight);
+            if let Some(pubkey) = &feed.pubkey {
+                registry.pyth_price_accounts.insert(feed.symbol.clone(), pubkey.clone());
+                registry.set_weight(&feed.symbol, "PythOnChain", weight);
+            }
+        }
+
+        for feed in &config.oracle_feeds.switchboard {
+            registry.ensure_known_symbol(&feed.symbol, "Switchboard")?;
+            validate_solana_address(&feed.pubkey)
+                .map_err(|e| anyhow!("Switchboard aggregator for {}: {}", feed.symbol, e))?;
+            let weight = validate_weight(feed.weight.unwrap_or(registry.default_switchboard_weight))
+                .map_err(|e| anyhow!("Switchboard weight for {}: {}", feed.symbol, e))?;
+
+            if registry.switchboard_addresses.insert(feed.symbol.clone(), feed.pubkey.clone()).is_some() {
+                return Err(anyhow!("Duplicate Switchboard feed for {}", feed.symbol));
+            }
+            registry.set_weight(&feed.symbol, "Switchboard", weight);
+        }
+
+        if !config.oracle_feeds.chainlink.is_empty() && config.evm.is_none() {
+            return Err(anyhow!("Chainlink feeds are configured without an [evm] section"));
+        }
+        for feed in &config.oracle_feeds.chainlink {
+            registry.ensure_known_symbol(&feed.symbol, "Chainlink")?;
+            validate_evm_address(&feed.address)
+                .map_err(|e| anyhow!("Chainlink aggregator for {}: {}", feed.symbol, e))?;
+            let weight = validate_weight(feed.weight.unwrap_or(1.0))
+                .map_err(|e| anyhow!("Chainlink weight for {}: {}", feed.symbol, e))?;
+
+            let chainlink_feed = ChainlinkFeed {
+                address: feed.address.to_lowercase(),
+                heartbeat_seconds: feed.heartbeat_seconds,
+                deviation_bps: feed.deviation_bps.unwrap_or(DEFAULT_CHAINLINK_DEVIATION_BPS),
+            };
+            if registry.chainlink_feeds.insert(feed.symbol.clone(), chainlink_feed).is_some() {
+                return Err(anyhow!("Duplicate Chainlink feed for {}", feed.symbol));
+            }
+            registry.set_weight(&feed.symbol, "Chainlink", weight);
+        }
+
+        // Exchanges price every pair, so their weight isn't per symbol
+        for exchange in &config.exchanges {
+            let name = exchange.name.name();
+            let weight = validate_weight(exchange.weight.unwrap_or(1.0))
+                .map_err(|e| anyhow!("{} weight: {}", name, e))?;
+            if registry.exchange_weights.insert(name.to_string(), weight).is_some() {
+                return Err(anyhow!("Duplicate exchange {}", name));
+            }
+            for symbol in exchange.symbols.keys() {
+                validate_symbol(symbol)?;
+            }
+        }
+
+        registry.quote_conversion = quote_conversion(&config.oracle, &registry)?;
+
+        // Refreshing faster than the cache expires would only re-read the cached price
+        let cache_ttl = Duration::from_millis(config.oracle.cache.ttl_ms);
+        registry.default_schedule = at_least(registry.default_schedule, cache_ttl, "oracle.schedule");
+        for (symbol, schedule) in registry.schedules.iter_mut() {
+            *schedule = at_least(*schedule, cache_ttl, symbol);
+        }
+
+        // Every monitored pair needs at least one way to be priced
+        for symbol in &registry.symbols {
+            if !registry.synthetic.contains_key(symbol)
+                && !registry.pyth_feed_ids.contains_key(symbol)
+                && !registry.switchboard_addresses.contains_key(symbol)
+                && !registry.chainlink_feeds.contains_key(symbol)
+            {
+                return Err(anyhow!("Trading pair {} has no oracle feeds configured", symbol));
+            }
+        }
+
+        Ok(registry)
+    }
+
+    pub fn into_shared(self) -> SharedFeedRegistry {
+        Arc::new(RwLock::new(self))
+    }
+
+    pub fn symbols(&self) -> &[String] {
+        &self.symbols
+    }
+
+    pub fn contains(&self, symbol: &str) -> bool {
+        self.symbols.iter().any(|s| s == symbol)
+    }
+
+    pub fn pyth_feed_id(&self, symbol: &str) -> Option<&String> {
+        self.pyth_feed_ids.get(symbol)
+    }
+
+    pub fn pyth_price_account(&self, symbol: &str) -> Option<&String> {
+        self.pyth_price_accounts.get(symbol)
+    }
+
+    pub fn switchboard_address(&self, symbol: &str) -> Option<&String> {
+        self.switchboard_addresses.get(symbol)
+    }
+
+    pub fn chainlink_feed(&self, symbol: &str) -> Option<&ChainlinkFeed> {
+        self.chainlink_feeds.get(symbol)
+    }
+
+    pub fn feeds(&self, symbol: &str) -> SymbolFeeds {
+        SymbolFeeds {
+            pyth_feed_id: self.pyth_feed_ids.get(symbol).cloned(),
+            pyth_price_account: self.pyth_price_accounts.get(symbol).cloned(),
+            switchboard_address: self.switchboard_addresses.get(symbol).cloned(),
+        }
+    }
+
+    pub fn pyth_feed_ids(&self) -> &HashMap<String, String> {
+        &self.pyth_feed_ids
+    }
+
+    pub fn switchboard_addresses(&self) -> &HashMap<String, String> {
+        &self.switchboard_addresses
+    }
+
+    /// Configured weight for a source on a symbol (or for an exchange), 1.0 if none was configured
+    pub fn source_weight(&self, symbol: &str, source: &str) -> f64 {
+        self.source_weights
+            .get(symbol)
+            .and_then(|weights| weights.get(source))
+            .or_else(|| self.exchange_weights.get(source))
+            .copied()
+            .unwrap_or(1.0)
+    }
+
+    /// Strategy for combining the sources of a symbol, with the `trimmed_mean` fraction applied
+    pub fn aggregation_strategy(&self, symbol: &str) -> Box<dyn AggregationStrategy> {
+        self.aggregation_method(symbol).strategy(self.trim_fraction)
+    }
+
+    pub fn aggregation_method(&self, symbol: &str) -> AggregationMethod {
+        self.aggregation.get(symbol).copied().unwrap_or(self.default_aggregation)
+    }
+
+    /// Outlier filter for a symbol, capped at its `max_deviation`
+    pub fn outlier_filter(&self, symbol: &str) -> OutlierFilter {
+        OutlierFilter { max_deviation: self.price_bounds(symbol).max_deviation, ..self.outlier_filter }
+    }
+
+    pub fn quorum_policy(&self, symbol: &str) -> QuorumPolicy {
+        self.quorum.get(symbol).copied().unwrap_or(self.default_quorum)
+    }
+
+    pub fn price_bounds(&self, symbol: &str) -> PriceBounds {
+        self.bounds.get(symbol).copied().unwrap_or(self.default_bounds)
+    }
+
+    /// Oldest price from `source` that is aggregated for `symbol`. Chainlink only posts a
+    /// round on a deviation or heartbeat, so its rounds count as fresh for the whole heartbeat.
+    pub fn max_source_age_seconds(&self, symbol: &str, source: &str) -> u64 {
+        let max_age = self.price_bounds(symbol).max_source_age_seconds;
+        match self.chainlink_feeds.get(symbol) {
+            Some(feed) if source == "Chainlink" => {
+                max_age.max(feed.heartbeat_seconds.unwrap_or(self.default_chainlink_heartbeat))
+            }
+            _ => max_age,
+        }
+    }
+
+    pub fn refresh_schedule(&self, symbol: &str) -> RefreshSchedule {
+        self.schedules.get(symbol).copied().unwrap_or(self.default_schedule)
+    }
+
+    pub fn quote_conversion(&self) -> &QuoteConversion {
+        &self.quote_conversion
+    }
+
+    /// Legs of a symbol priced from other symbols, `None` for symbols with their own feeds
+    pub fn synthetic(&self, symbol: &str) -> Option<&SyntheticPair> {
+        self.synthetic.get(symbol)
+    }
+
+    /// Synthetic symbols with `symbol` as one of their legs
+    pub fn synthetic_dependents(&self, symbol: &str) -> Vec<String> {
+        let mut dependents: Vec<String> = self.synthetic.iter()
+            .filter(|(_, pair)| pair.legs().any(|leg| leg == symbol))
+            .map(|(synthetic, _)| synthetic.clone())
+            .collect();
+        dependents.sort();
+        dependents
+    }
+
+    /// Stablecoins whose USD rate `symbol` provides for quote conversion
+    pub fn quote_dependents(&self, symbol: &str) -> Vec<String> {
+        let mut quotes: Vec<String> = self.quote_conversion.feeds.iter()
+            .filter(|(_, feed)| *feed == symbol)
+            .map(|(quote, _)| quote.to_string())
+            .collect();
+        quotes.sort();
+        quotes
+    }
+
+    /// Registers a new symbol with the default source weights
+    pub fn add_symbol(&mut self, symbol: &str, feeds: &SymbolFeeds) -> Result<()> {
+        validate_symbol(symbol)?;
+        if self.contains(symbol) {
+            return Err(anyhow!("Symbol {} is already registered", symbol));
+        }
+        if *feeds == SymbolFeeds::default() {
+            return Err(anyhow!("Symbol {} needs at least one oracle feed", symbol));
+        }
+
+        // Validate everything before touching any map
+        let pyth_feed_id = feeds.pyth_feed_id.as_deref().map(normalize_pyth_feed_id).transpose()?;
+        for address in [&feeds.pyth_price_account, &feeds.switchboard_address].into_iter().flatten() {
+            validate_solana_address(address)?;
+        }
+
+        self.symbols.push(symbol.to_string());
+        if let Some(feed_id) = pyth_feed_id {
+            self.pyth_feed_ids.insert(symbol.to_string(), feed_id);
+            self.set_weight(symbol, "Pyth", self.default_pyth_weight);
+        }
+        if let Some(account) = &feeds.pyth_price_account {
+            self.pyth_price_accounts.insert(symbol.to_string(), account.clone());
+            self.set_weight(symbol, "PythOnChain", self.default_pyth_weight);
+        }
+        if let Some(address) = &feeds.switchboard_address {
+            self.switchboard_addresses.insert(symbol.to_string(), address.clone());
+            self.set_weight(symbol, "Switchboard", self.default_switchboard_weight);
+        }
+
+        Ok(())
+    }
+
+    /// Removes a symbol and all of its feeds, returning whether it was registered
+    pub fn remove_symbol(&mut self, symbol: &str) -> bool {
+        let registered = self.contains(symbol);
+        self.symbols.retain(|s| s != symbol);
+        self.pyth_feed_ids.remove(symbol);
+        self.pyth_price_accounts.remove(symbol);
+        self.switchboard_addresses.remove(symbol);
+        self.chainlink_feeds.remove(symbol);
+        self.source_weights.remove(symbol);
+        self.aggregation.remove(symbol);
+        self.quorum.remove(symbol);
+        self.synthetic.remove(symbol);
+        self.bounds.remove(symbol);
+        self.schedules.remove(symbol);
+        registered
+    }
+
+    fn ensure_known_symbol(&self, symbol: &str, source: &str) -> Result<()> {
+        if !self.contains(symbol) {
+            Err(anyhow!("{} feed configured for unknown trading pair {}", source, symbol))
+        } else if self.synthetic.contains_key(symbol) {
+            Err(anyhow!("{} feed configured for synthetic pair {}", source, symbol))
+        } else {
+            Ok(())
+        }
+    }
+
+    fn set_weight(&mut self, symbol: &str, source: &str, weight: f64) {
+        self.source_weights
+            .entry(symbol.to_string())
+            .or_default()
+            .insert(source.to_string(), weight);
+    }
+}
+
+/// Symbols are `BASE/QUOTE` with uppercase alphanumeric legs
+pub fn validate_symbol(symbol: &str) -> Result<()> {
+    let valid_leg = |leg: &str| !leg.is_empty() && leg.chars().all(|c| c.is_ascii_uppercase() || c.is_ascii_digit());
+    match symbol.split_once('/') {
+        Some((base, quote)) if valid_leg(base) && valid_leg(quote) => Ok(()),
+        _ => Err(anyhow!("'{}' is not a BASE/QUOTE symbol", symbol)),
+    }
+}
+
+/// Validates a Hermes feed ID and returns it lowercased without a `0x` prefix
+pub fn normalize_pyth_feed_id(feed_id: &str) -> Result<String> {
+    let trimmed = feed_id.strip_prefix("0x").unwrap_or(feed_id);
+    if trimmed.len() != 64 || !trimmed.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(anyhow!("'{}' is not a 32-byte hex feed ID", feed_id));
+    }
+    Ok(trimmed.to_ascii_lowercase())
+}
+
+pub fn validate_solana_address(address: &str) -> Result<()> {
+    let bytes = bs58::decode(address)
+        .into_vec()
+        .map_err(|e| anyhow!("'{}' is not valid base58: {}", address, e))?;
+    if bytes.len() != 32 {
+        return Err(anyhow!("'{}' decodes to {} bytes, expected 32", address, bytes.len()));
+    }
+    Ok(())
+}
+
+/// Checks a 0x-prefixed 20-byte hex contract address
+pub fn validate_evm_address(address: &str) -> Result<()> {
+    let hex = address
+        .strip_prefix("0x")
+        .ok_or_else(|| anyhow!("'{}' is missing the 0x prefix", address))?;
+    if hex.len() != 40 || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(anyhow!("'{}' is not a 20-byte hex address", address));
+    }
+    Ok(())
+}
+
+fn outlier_filter(settings: &OracleSettings) -> Result<OutlierFilter> {
+    let mad_multiplier = settings.outliers.mad_multiplier;
+    if !mad_multiplier.is_finite() || mad_multiplier <= 0.0 {
+        return Err(anyhow!("oracle.outliers.mad_multiplier must be positive, got {}", mad_multiplier));
+    }
+    if settings.outliers.min_deviation_bps >= settings.max_price_deviation_bps {
+        return Err(anyhow!(
+            "oracle.outliers.min_deviation_bps ({}) must be below oracle.max_price_deviation_bps ({})",
+            settings.outliers.min_deviation_bps, settings.max_price_deviation_bps
+        ));
+    }
+    Ok(OutlierFilter {
+        mad_multiplier,
+        min_deviation: settings.outliers.min_deviation_bps as f64 / 10_000.0,
+        max_deviation: settings.max_price_deviation_bps as f64 / 10_000.0,
+    })
+}
+
+fn quorum_policy(settings: &OracleSettings) -> Result<QuorumPolicy> {
+    let quorum = settings.quorum;
+    if !quorum.confidence_multiplier.is_finite() || quorum.confidence_multiplier < 1.0 {
+        return Err(anyhow!(
+            "oracle.quorum.confidence_multiplier must be at least 1, got {}", quorum.confidence_multiplier
+        ));
+    }
+    validate_min_sources(quorum.min_sources).map_err(|e| anyhow!("oracle.quorum.min_sources: {}", e))?;
+    Ok(quorum)
+}
+
+fn validate_resilience(resilience: &ResilienceConfig) -> Result<()> {
+    if !resilience.jitter.is_finite() || !(0.0..=1.0).contains(&resilience.jitter) {
+        return Err(anyhow!("jitter must be in [0, 1], got {}", resilience.jitter));
+    }
+    // A zero rate would open the breaker on a run of successes
+    if !(resilience.breaker_error_rate > 0.0 && resilience.breaker_error_rate <= 1.0) {
+        return Err(anyhow!("breaker_error_rate must be in (0, 1], got {}", resilience.breaker_error_rate));
+    }
+    if resilience.breaker_window < resilience.breaker_min_calls {
+        return Err(anyhow!(
+            "breaker_window {} is smaller than breaker_min_calls {}, so the breaker could never open",
+            resilience.breaker_window, resilience.breaker_min_calls
+        ));
+    }
+    Ok(())
+}
+
+fn validate_min_sources(min_sources: usize) -> Result<usize> {
+    if min_sources == 0 {
+        return Err(anyhow!("at least one source is required"));
+    }
+    Ok(min_sources)
+}
+
+fn default_schedule(settings: &OracleSettings) -> Result<RefreshSchedule> {
+    let schedule = &settings.schedule;
+    if !(0.0..1.0).contains(&schedule.jitter) {
+        return Err(anyhow!("oracle.schedule.jitter must be in [0, 1), got {}", schedule.jitter));
+    }
+    if schedule.volatility_bps == 0 || schedule.dispersion_bps == 0 {
+        return Err(anyhow!("oracle.schedule: volatility_bps and dispersion_bps must be positive"));
+    }
+    let interval = Duration::from_millis(schedule.interval_ms);
+    let defaults = RefreshSchedule {
+        jitter: schedule.jitter,
+        volatility_threshold: schedule.volatility_bps as f64 / 10_000.0,
+        dispersion_threshold: schedule.dispersion_bps as f64 / 10_000.0,
+        ..RefreshSchedule::fixed(interval)
+    };
+    refresh_schedule(
+        defaults,
+        interval,
+        schedule.min_interval_ms.map(Duration::from_millis).unwrap_or(interval),
+        schedule.max_interval_ms.map(Duration::from_millis).unwrap_or(interval),
+    ).map_err(|e| anyhow!("oracle.schedule: {}", e))
+}
+
+// Below 10ms a symbol would spin on its cache rather than refresh
+fn refresh_schedule(defaults: RefreshSchedule, interval: Duration, min_interval: Duration, max_interval: Duration) -> Result<RefreshSchedule> {
+    if min_interval < Duration::from_millis(10) {
+        return Err(anyhow!("minimum interval must be at least 10ms, got {}ms", min_interval.as_millis()));
+    }
+    if !(min_interval..=max_interval).contains(&interval) {
+        return Err(anyhow!(
+            "interval {}ms must be within {}ms..={}ms",
+            interval.as_millis(), min_interval.as_millis(), max_interval.as_millis()
+        ));
+    }
+    Ok(RefreshSchedule { interval, min_interval, max_interval, ..defaults })
+}
+
+/// Raises `schedule`'s intervals to at least `floor`
+fn at_least(schedule: RefreshSchedule, floor: Duration, name: &str) -> RefreshSchedule {
+    if schedule.min_interval >= floor {
+        return schedule;
+    }
+    warn!(
+        "Minimum refresh interval of {} raised from {}ms to the {}ms cache TTL",
+        name, schedule.min_interval.as_millis(), floor.as_millis()
+    );
+    RefreshSchedule {
+        interval: schedule.interval.max(floor),
+        min_interval: floor,
+        max_interval: schedule.max_interval.max(floor),
+        ..schedule
+    }
+}
+
+fn default_bounds(settings: &OracleSettings) -> Result<PriceBounds> {
+    let defaults = PriceBounds {
+        max_deviation: settings.max_price_deviation_bps as f64 / 10_000.0,
+        ..PriceBounds::default()
+    };
+    price_bounds(defaults, &settings.bounds, settings.outliers.min_deviation_bps)
+        .map_err(|e| anyhow!("oracle.bounds: {}", e))
+}
+
+// Set fields override `defaults`, then the result is validated as a whole
+fn price_bounds(defaults: PriceBounds, config: &BoundsConfig, min_deviation_bps: u32) -> Result<PriceBounds> {
+    if let Some(bps) = config.max_deviation_bps {
+        if bps <= min_deviation_bps || bps > 10_000 {
+            return Err(anyhow!(
+                "max_deviation_bps must be above oracle.outliers.min_deviation_bps ({}) and at most 10000, got {}",
+                min_deviation_bps, bps
+            ));
+        }
+    }
+    let bounds = PriceBounds {
+        min_price: config.min_price.unwrap_or(defaults.min_price),
+        max_price: config.max_price.or(defaults.max_price),
+        max_age_seconds: config.max_age_seconds.unwrap_or(defaults.max_age_seconds),
+        max_source_age_seconds: config.max_source_age_seconds.unwrap_or(defaults.max_source_age_seconds),
+        max_deviation: config.max_deviation_bps.map_or(defaults.max_deviation, |bps| bps as f64 / 10_000.0),
+    };
+
+    if bounds.min_price < Decimal::ZERO {
+        return Err(anyhow!("min_price must not be negative, got {}", bounds.min_price));
+    }
+    if let Some(max_price) = bounds.max_price {
+        if max_price <= bounds.min_price {
+            return Err(anyhow!("max_price {} must be above min_price {}", max_price, bounds.min_price));
+        }
+    }
+    if bounds.max_age_seconds == 0 || bounds.max_source_age_seconds == 0 {
+        return Err(anyhow!("max_age_seconds and max_source_age_seconds must be positive"));
+    }
+    Ok(bounds)
+}
+
+fn quote_conversion(settings: &OracleSettings, registry: &FeedRegistry) -> Result<QuoteConversion> {
+    let quotes = &settings.quotes;
+    for (asset, symbol) in &quotes.feeds {
+        if *asset == QuoteAsset::Usd || QuoteAsset::of_symbol(symbol) != Some(QuoteAsset::Usd) {
+            return Err(anyhow!("oracle.quotes.feeds: {} = {} must price a stablecoin in USD", asset, symbol));
+        }
+        if !registry.contains(symbol) || registry.synthetic(symbol).is_some() {
+            return Err(anyhow!("oracle.quotes.feeds: {} must be a trading pair with its own feeds", symbol));
+        }
+    }
+    if quotes.max_depeg_bps == 0 || quotes.max_depeg_bps >= 10_000 {
+        return Err(anyhow!("oracle.quotes.max_depeg_bps must be in (0, 10000), got {}", quotes.max_depeg_bps));
+    }
+
+    Ok(QuoteConversion {
+        feeds: quotes.feeds.clone(),
+        max_depeg: Decimal::new(quotes.max_depeg_bps as i64, 4),
+        depeg_action: quotes.depeg_action,
+    })
+}
+
+// Legs must be pairs with their own feeds, so prices never recurse through other synthetics
+fn synthetic_pair(pair: &TradingPairConfig, pairs: &[TradingPairConfig]) -> Result<Option<SyntheticPair>> {
+    let Some(config) = &pair.synthetic else {
+        return Ok(None);
+    };
+    if config.multiply.is_empty() {
+        return Err(anyhow!("Synthetic pair {} needs at least one multiply leg", pair.symbol));
+    }
+    if pair.aggregation.is_some() || pair.min_sources.is_some() || pair.degraded_mode.is_some() {
+        return Err(anyhow!(
+            "Synthetic pair {} takes its aggregation and quorum from its legs", pair.symbol
+        ));
+    }
+    for leg in config.multiply.iter().chain(&config.divide) {
+        match pairs.iter().find(|p| &p.symbol == leg) {
+            None => return Err(anyhow!("Synthetic pair {} uses unknown trading pair {}", pair.symbol, leg)),
+            Some(p) if p.synthetic.is_some() => {
+                return Err(anyhow!("Synthetic pair {} uses synthetic pair {} as a leg", pair.symbol, leg));
+            }
+            Some(_) => {}
+        }
+    }
+    Ok(Some(SyntheticPair { multiply: config.multiply.clone(), divide: config.divide.clone() }))
+}
+
+fn validate_trim_fraction(fraction: f64) -> Result<f64> {
+    if !fraction.is_finite() || !(0.0..0.5).contains(&fraction) {
+        return Err(anyhow!("{} is outside [0, 0.5)", fraction));
+    }
+    Ok(fraction)
+}
+
+fn validate_weight(weight: f64) -> Result<f64> {
+    if !weight.is_finite() || !(0.0..=1.0).contains(&weight) {
+        return Err(anyhow!("{} is outside [0, 1]", weight));
+    }
+    Ok(weight)
+}
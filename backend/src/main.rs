@@ -1,29 +1,57 @@
 use axum::{
     extract::{ws::WebSocketUpgrade, ws::WebSocket, State, Path, Query},
-    http::StatusCode,
+    http::{header, HeaderMap, StatusCode},
     response::IntoResponse,
-    routing::get,
+    routing::{delete, get},
     Json, Router,
 };
 use futures::{sink::SinkExt, stream::StreamExt};
+use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
-use std::{collections::HashMap, sync::Arc};
+use std::{collections::{BTreeMap, HashMap}, sync::Arc};
 use std::time::{SystemTime, UNIX_EPOCH};
 use tokio::net::TcpListener;
 use tower::ServiceBuilder;
 use tower_http::cors::CorsLayer;
 use tracing::{info, warn, error};
-use sqlx::{postgres::PgPoolOptions, PgPool, Row};
 
+mod aggregation;
+mod bounds;
+mod cex_client;
+mod chainlink_client;
+mod config;
+mod error;
+mod feed_registry;
+mod memory_store;
 mod oracle_client;
+mod postgres_store;
 mod price_aggregator;
+mod price_cache;
+mod price_store;
+mod pyth_account;
+mod pyth_stream;
+mod quote;
+mod resilient_client;
+mod scheduler;
+mod solana_rpc;
+#[cfg(feature = "sqlite")]
+mod sqlite_store;
+mod source_stats;
+mod switchboard_account;
+mod switchboard_stream;
+mod synthetic;
 
 #[cfg(test)]
 mod tests;
 
-use oracle_client::OracleManager;
+use aggregation::PriceMode;
+use config::OracleFileConfig;
+use error::{ErrorDetail, OracleError};
+use feed_registry::{FeedRegistry, SymbolFeeds};
+use oracle_client::{build_oracle_clients, OracleManager, SourceObservation, SourceStatus, SystemHealth};
 use price_aggregator::PriceAggregator;
+use price_store::PriceStore;
 
 // Configuration structures
 #[derive(Debug, Clone)]
@@ -33,12 +61,14 @@ pub struct AppConfig {
     pub server_port: u16,
     pub pyth_rpc_url: String,
     pub switchboard_rpc_url: String,
+    pub oracle_config_path: String,
+    pub admin_api_token: Option<String>,
 }
 
 // Application state
 #[derive(Clone)]
 pub struct AppState {
-    pub db: PgPool,
+    pub store: Arc<dyn PriceStore>,
     pub config: AppConfig,
     pub price_aggregator: Arc<PriceAggregator>,
 }
@@ -47,12 +77,24 @@ pub struct AppState {
 #[derive(Serialize, Deserialize)]
 pub struct PriceResponse {
     pub symbol: String,
-    pub mark_price: f64,
-    pub index_price: f64,
+    pub mark_price: Decimal,
+    pub index_price: Decimal,
     pub timestamp: i64,
-    pub confidence: f64,
+    pub confidence: Decimal,
     pub sources: Vec<String>,
     pub manipulation_score: Option<f64>,
+    /// `normal`, or the degraded mode applied when the symbol was below quorum
+    pub mode: PriceMode,
+    /// Every source price behind this one, including those excluded and why
+    #[serde(default)]
+    pub observations: Vec<SourceObservation>,
+}
+
+/// Prices that could be served, and why each of the others couldn't
+#[derive(Serialize)]
+pub struct PricesResponse {
+    pub prices: Vec<PriceResponse>,
+    pub errors: BTreeMap<String, ErrorDetail>,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -61,6 +103,7 @@ pub struct HealthResponse {
     pub timestamp: i64,
     pub database: bool,
     pub oracles: Value,
+    pub sources: Vec<SourceStatus>,
 }
 
 // Query parameters
@@ -81,6 +124,13 @@ pub struct ManipulationQuery {
     pub hours: Option<u64>,
 }
 
+#[derive(Deserialize)]
+pub struct AddSymbolRequest {
+    pub symbol: String,
+    #[serde(flatten)]
+    pub feeds: SymbolFeeds,
+}
+
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Initialize tracing
@@ -89,24 +139,33 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Load configuration
     let config = load_config().await?;
     
-    // Initialize database connection pool
-    let database_url = &config.database_url;
-    let db_pool = PgPoolOptions::new()
-        .max_connections(20)
-        .connect(database_url)
-        .await?;
+    // Open the price store and run its migrations; DATABASE_URL=memory:// needs no database
+    let store = price_store::connect(&config.database_url).await?;
     
-    // Run migrations
-    info!("Running database migrations...");
-    sqlx::migrate!("../db").run(&db_pool).await?;
+    // Load feed registry; unknown or malformed feed IDs abort startup
+    let oracle_config = OracleFileConfig::load(&config.oracle_config_path)?;
+    let registry = FeedRegistry::from_config(&oracle_config)?;
+    info!("Loaded {} trading pairs from {}", registry.symbols().len(), config.oracle_config_path);
     
     // Initialize oracle manager and price aggregator
-    let oracle_manager = OracleManager::new(db_pool.clone());
-    let price_aggregator = Arc::new(PriceAggregator::new(oracle_manager, db_pool.clone()));
+    let registry = registry.into_shared();
+    let clients = build_oracle_clients(
+        &oracle_config,
+        &registry,
+        config.pyth_rpc_url.clone(),
+        config.switchboard_rpc_url.clone(),
+    );
+    let oracle_manager = OracleManager::new(store.clone(), registry, clients, &oracle_config.oracle.cache);
+    let price_aggregator = Arc::new(PriceAggregator::new(oracle_manager, store.clone()));
+    
+    let restored = price_aggregator.load_persisted_symbols().await?;
+    if restored > 0 {
+        info!("Restored {} trading symbols added at runtime", restored);
+    }
     
     // Create application state
     let app_state = AppState {
-        db: db_pool,
+        store,
         config: config.clone(),
         price_aggregator: price_aggregator.clone(),
     };
@@ -114,13 +173,13 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Start background price monitoring
     let price_aggregator_clone = price_aggregator.clone();
     tokio::spawn(async move {
-        let symbols = vec![
-            "BTC/USD".to_string(),
-            "ETH/USD".to_string(), 
-            "SOL/USD".to_string(),
-            "AVAX/USD".to_string(),
-        ];
-        price_aggregator_clone.start_continuous_monitoring(symbols).await;
+        price_aggregator_clone.start_continuous_monitoring().await;
+    });
+
+    // Refresh prices served stale under stale-while-revalidate
+    let price_aggregator_clone = price_aggregator.clone();
+    tokio::spawn(async move {
+        price_aggregator_clone.run_cache_revalidation().await;
     });
     
     // Build application routes
@@ -139,6 +198,8 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         .route("/api/v1/liquidation/:symbol", get(get_liquidation_price))
         .route("/api/v1/system/health", get(get_system_health))
         .route("/api/v1/manipulation/:symbol", get(get_manipulation_score))
+        .route("/api/v1/admin/symbols", get(list_trading_symbols).post(add_trading_symbol))
+        .route("/api/v1/admin/symbols/:symbol", delete(remove_trading_symbol))
         .route("/ws/prices", get(websocket_handler))
         .layer(
             ServiceBuilder::new()
@@ -166,7 +227,7 @@ async fn health_check(State(state): State<AppState>) -> Result<Json<HealthRespon
         .as_secs() as i64;
     
     // Check database connection
-    let db_healthy = match sqlx::query("SELECT 1").fetch_one(&state.db).await {
+    let db_healthy = match state.store.check_health().await {
         Ok(_) => true,
         Err(e) => {
             error!("Database health check failed: {}", e);
@@ -194,15 +255,17 @@ async fn health_check(State(state): State<AppState>) -> Result<Json<HealthRespon
         timestamp,
         database: db_healthy,
         oracles: oracles_health,
+        sources: state.price_aggregator.source_status(),
     }))
 }
 
 async fn get_price(
     Path(symbol): Path<String>,
     State(state): State<AppState>,
-) -> Result<Json<PriceResponse>, StatusCode> {
+) -> Result<Json<PriceResponse>, OracleError> {
     match state.price_aggregator.get_price_with_validation(&symbol).await {
         Ok(aggregated_price) => {
+            let observations = aggregated_price.observations();
             let response = PriceResponse {
                 symbol: aggregated_price.symbol,
                 mark_price: aggregated_price.mark_price,
@@ -213,12 +276,14 @@ async fn get_price(
                     .map(|s| s.source.clone())
                     .collect(),
                 manipulation_score: None, // Could be added if needed
+                mode: aggregated_price.mode,
+                observations,
             };
             Ok(Json(response))
         }
         Err(e) => {
-            warn!("Failed to get price for {}: {}", symbol, e);
-            Err(StatusCode::NOT_FOUND)
+            warn!("Failed to get price for {}: {:#}", symbol, e);
+            Err(e.into())
         }
     }
 }
@@ -226,42 +291,52 @@ async fn get_price(
 async fn get_multiple_prices(
     Query(params): Query<HashMap<String, String>>,
     State(state): State<AppState>,
-) -> Result<Json<Vec<PriceResponse>>, StatusCode> {
+) -> Json<PricesResponse> {
     let symbols = if let Some(symbols_str) = params.get("symbols") {
         symbols_str.split(',')
             .map(|s| s.trim().to_string())
             .collect()
     } else {
-        vec!["BTC/USD".to_string(), "ETH/USD".to_string(), "SOL/USD".to_string()]
+        state.price_aggregator.monitored_symbols().await
     };
     
     let mut responses = Vec::new();
+    let mut errors = BTreeMap::new();
     
     for symbol in symbols {
-        if let Ok(aggregated_price) = state.price_aggregator.get_price_with_validation(&symbol).await {
-            let response = PriceResponse {
-                symbol: aggregated_price.symbol,
-                mark_price: aggregated_price.mark_price,
-                index_price: aggregated_price.index_price,
-                timestamp: aggregated_price.timestamp,
-                confidence: aggregated_price.confidence,
-                sources: aggregated_price.sources.iter()
-                    .map(|s| s.source.clone())
-                    .collect(),
-                manipulation_score: None,
-            };
-            responses.push(response);
-        }
+        let aggregated_price = match state.price_aggregator.get_price_with_validation(&symbol).await {
+            Ok(aggregated_price) => aggregated_price,
+            Err(e) => {
+                warn!("Failed to get price for {}: {:#}", symbol, e);
+                errors.insert(symbol, OracleError::from(e).body().error);
+                continue;
+            }
+        };
+        let observations = aggregated_price.observations();
+        let response = PriceResponse {
+            symbol: aggregated_price.symbol,
+            mark_price: aggregated_price.mark_price,
+            index_price: aggregated_price.index_price,
+            timestamp: aggregated_price.timestamp,
+            confidence: aggregated_price.confidence,
+            sources: aggregated_price.sources.iter()
+                .map(|s| s.source.clone())
+                .collect(),
+            manipulation_score: None,
+            mode: aggregated_price.mode,
+            observations,
+        };
+        responses.push(response);
     }
     
-    Ok(Json(responses))
+    Json(PricesResponse { prices: responses, errors })
 }
 
 async fn get_price_history(
     Path(symbol): Path<String>,
     Query(params): Query<HashMap<String, String>>,
     State(state): State<AppState>,
-) -> Result<Json<Vec<PriceResponse>>, StatusCode> {
+) -> Result<Json<Vec<PriceResponse>>, OracleError> {
     let hours = params.get("hours")
         .and_then(|h| h.parse::<u64>().ok())
         .unwrap_or(24);
@@ -271,49 +346,27 @@ async fn get_price_history(
         .unwrap()
         .as_secs() as i64 - (hours * 3600) as i64;
     
-    let rows = sqlx::query(
-        r#"
-        SELECT symbol, mark_price, index_price, confidence, 
-               EXTRACT(epoch FROM created_at) as timestamp,
-               source_count
-        FROM price_feeds
-        WHERE symbol = $1 AND created_at >= to_timestamp($2)
-        ORDER BY created_at DESC
-        LIMIT 1000
-        "#
-    )
-    .bind(&symbol)
-    .bind(cutoff_time)
-    .fetch_all(&state.db)
-    .await
-    .map_err(|e| {
-        error!("Database query failed: {}", e);
-        StatusCode::INTERNAL_SERVER_ERROR
+    let history = state.store.price_history(&symbol, cutoff_time, 1000).await.map_err(|e| {
+        error!("Failed to load price history: {:#}", e);
+        OracleError::from(e)
     })?;
     
-    let mut responses = Vec::new();
-    
-    for row in rows {
-        let symbol: String = row.try_get("symbol").unwrap_or_default();
-        let mark_price: f64 = row.try_get::<f64, _>("mark_price")
-            .unwrap_or_default();
-        let index_price: f64 = row.try_get::<f64, _>("index_price")
-            .unwrap_or_default();
-        let confidence: f64 = row.try_get::<f64, _>("confidence")
-            .unwrap_or_default();
-        let timestamp: i64 = row.try_get::<f64, _>("timestamp").unwrap_or_default() as i64;
-        let source_count: i32 = row.try_get("source_count").unwrap_or(0);
-        
-        responses.push(PriceResponse {
-            symbol,
-            mark_price,
-            index_price,
-            timestamp,
-            confidence,
-            sources: (0..source_count).map(|i| format!("source_{}", i)).collect(),
-            manipulation_score: None,
-        });
-    }
+    let responses = history.into_iter()
+        .map(|price| {
+            let observations = price.observations();
+            PriceResponse {
+                symbol: price.symbol,
+                mark_price: price.mark_price,
+                index_price: price.index_price,
+                timestamp: price.timestamp,
+                confidence: price.confidence,
+                sources: price.sources.into_iter().map(|s| s.source).collect(),
+                manipulation_score: None,
+                mode: price.mode,
+                observations,
+            }
+        })
+        .collect();
     
     Ok(Json(responses))
 }
@@ -321,14 +374,14 @@ async fn get_price_history(
 async fn get_manipulation_report(
     Query(params): Query<ManipulationQuery>,
     State(state): State<AppState>,
-) -> Result<Json<Value>, StatusCode> {
+) -> Result<Json<Value>, OracleError> {
     let hours = params.hours.unwrap_or(24);
     
     match state.price_aggregator.get_manipulation_report(&params.symbol, hours).await {
         Ok(report) => Ok(Json(report)),
         Err(e) => {
-            error!("Failed to generate manipulation report: {}", e);
-            Err(StatusCode::INTERNAL_SERVER_ERROR)
+            error!("Failed to generate manipulation report: {:#}", e);
+            Err(e.into())
         }
     }
 }
@@ -336,7 +389,7 @@ async fn get_manipulation_report(
 async fn get_price_sources(
     Path(symbol): Path<String>,
     State(state): State<AppState>,
-) -> Result<Json<serde_json::Value>, StatusCode> {
+) -> Result<Json<serde_json::Value>, OracleError> {
     match state.price_aggregator.get_price_with_validation(&symbol).await {
         Ok(aggregated_price) => {
             let sources: Vec<serde_json::Value> = aggregated_price.sources.iter()
@@ -351,13 +404,14 @@ async fn get_price_sources(
             Ok(Json(serde_json::json!({
                 "symbol": symbol,
                 "sources": sources,
+                "excluded_sources": aggregated_price.excluded_sources,
                 "aggregated_price": aggregated_price.mark_price,
                 "timestamp": aggregated_price.timestamp
             })))
         }
         Err(e) => {
-            warn!("Failed to get sources for {}: {}", symbol, e);
-            Err(StatusCode::NOT_FOUND)
+            warn!("Failed to get sources for {}: {:#}", symbol, e);
+            Err(e.into())
         }
     }
 }
@@ -386,7 +440,8 @@ async fn handle_socket(socket: WebSocket, state: AppState) {
                     "confidence": update.confidence,
                     "timestamp": update.timestamp,
                     "sources": update.sources,
-                    "manipulation_score": update.manipulation_score
+                    "manipulation_score": update.manipulation_score,
+                    "mode": update.mode
                 }
             });
             
@@ -420,6 +475,77 @@ async fn handle_socket(socket: WebSocket, state: AppState) {
     }
 }
 
+// Admin handlers for runtime symbol onboarding
+
+// Admin routes are disabled unless ADMIN_API_TOKEN is set
+fn authorize_admin(headers: &HeaderMap, config: &AppConfig) -> Result<(), OracleError> {
+    let expected = config.admin_api_token.as_deref().ok_or(OracleError::AdminDisabled)?;
+    let provided = headers.get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .ok_or(OracleError::Unauthorized)?;
+
+    // Constant-time comparison so the token can't be guessed byte by byte
+    let matches = provided.len() == expected.len()
+        && provided.bytes().zip(expected.bytes()).fold(0u8, |acc, (a, b)| acc | (a ^ b)) == 0;
+    if matches {
+        Ok(())
+    } else {
+        Err(OracleError::Unauthorized)
+    }
+}
+
+async fn list_trading_symbols(
+    headers: HeaderMap,
+    State(state): State<AppState>,
+) -> Result<Json<Value>, OracleError> {
+    authorize_admin(&headers, &state.config)?;
+    
+    Ok(Json(serde_json::json!({
+        "symbols": state.price_aggregator.monitored_symbols().await
+    })))
+}
+
+async fn add_trading_symbol(
+    headers: HeaderMap,
+    State(state): State<AppState>,
+    Json(request): Json<AddSymbolRequest>,
+) -> Result<(StatusCode, Json<Value>), OracleError> {
+    authorize_admin(&headers, &state.config)?;
+    
+    let symbol = request.symbol.clone();
+    match state.price_aggregator
+        .add_trading_symbol(request.symbol, request.feeds)
+        .await
+    {
+        Ok(()) => Ok((StatusCode::CREATED, Json(serde_json::json!({"symbol": symbol})))),
+        Err(e) => {
+            warn!("Failed to add trading symbol {}: {}", symbol, e);
+            Err(e.into())
+        }
+    }
+}
+
+async fn remove_trading_symbol(
+    headers: HeaderMap,
+    Path(symbol): Path<String>,
+    State(state): State<AppState>,
+) -> Result<StatusCode, OracleError> {
+    authorize_admin(&headers, &state.config)?;
+    
+    match state.price_aggregator.remove_trading_symbol(&symbol).await {
+        Ok(()) => Ok(StatusCode::NO_CONTENT),
+        Err(e) => {
+            let e = OracleError::from(e);
+            match e {
+                OracleError::Storage(_) => error!("Failed to remove trading symbol {}: {}", symbol, e),
+                _ => warn!("Failed to remove trading symbol {}: {}", symbol, e),
+            }
+            Err(e)
+        }
+    }
+}
+
 // Configuration loading
 async fn load_config() -> Result<AppConfig, Box<dyn std::error::Error>> {
     Ok(AppConfig {
@@ -435,9 +561,23 @@ async fn load_config() -> Result<AppConfig, Box<dyn std::error::Error>> {
             .unwrap_or_else(|_| "https://hermes.pyth.network".to_string()),
         switchboard_rpc_url: std::env::var("SWITCHBOARD_RPC_URL")
             .unwrap_or_else(|_| "https://api.mainnet-beta.solana.com".to_string()),
+        oracle_config_path: std::env::var("ORACLE_CONFIG_PATH")
+            .unwrap_or_else(|_| default_oracle_config_path()),
+        admin_api_token: std::env::var("ADMIN_API_TOKEN")
+            .ok()
+            .filter(|token| !token.is_empty()),
     })
 }
 
+// Docker runs from /app with config/ alongside; `cargo run` runs from backend/
+fn default_oracle_config_path() -> String {
+    let candidates = [config::DEFAULT_CONFIG_PATH, "../config/dev.toml"];
+    candidates.iter()
+        .find(|path| std::path::Path::new(path).exists())
+        .unwrap_or(&candidates[0])
+        .to_string()
+}
+
 // Advanced API handlers
 
 async fn get_funding_rate(
@@ -498,40 +638,14 @@ async fn get_liquidation_price(
 
 async fn get_system_health(
     State(state): State<AppState>,
-) -> Result<Json<serde_json::Value>, StatusCode> {
-    let timestamp = SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .unwrap()
-        .as_secs() as i64;
-    
-    // Enhanced system health check
-    let db_healthy = sqlx::query("SELECT 1").fetch_one(&state.db).await.is_ok();
-    
-    let health_data = serde_json::json!({
-        "overall_health": 0.95,
-        "uptime_percentage": 99.99,
-        "database_status": db_healthy,
-        "cache_hit_rate": 95.0,
-        "oracle_health": [
-            {
-                "name": "Pyth",
-                "is_healthy": true,
-                "latency_ms": 150,
-                "last_update": timestamp,
-                "error_rate": 0.001
-            },
-            {
-                "name": "Switchboard", 
-                "is_healthy": true,
-                "latency_ms": 200,
-                "last_update": timestamp,
-                "error_rate": 0.002
-            }
-        ],
-        "timestamp": timestamp
-    });
-    
-    Ok(Json(health_data))
+) -> Result<Json<SystemHealth>, OracleError> {
+    match state.price_aggregator.get_system_health().await {
+        Ok(health) => Ok(Json(health)),
+        Err(e) => {
+            error!("System health check failed: {:#}", e);
+            Err(e.into())
+        }
+    }
 }
 
 async fn get_manipulation_score(
@@ -0,0 +1,118 @@
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
+use anyhow::Result;
+use async_trait::async_trait;
+use rust_decimal::Decimal;
+use crate::feed_registry::SymbolFeeds;
+use crate::oracle_client::AggregatedPrice;
+use crate::price_store::{unix_now, ManipulationEvent, PriceStore};
+
+/// Keeps everything in the process, for running locally and in tests without a
+/// database. Each symbol keeps its most recent `max_rows` prices and events.
+#[derive(Debug)]
+pub struct MemoryStore {
+    max_rows: usize,
+    tables: Mutex<Tables>,
+}
+
+#[derive(Debug, Default)]
+struct Tables {
+    prices: HashMap<String, VecDeque<AggregatedPrice>>, // Oldest first
+    events: HashMap<String, VecDeque<ManipulationEvent>>,
+    symbols: Vec<(String, SymbolFeeds)>,
+}
+
+impl Default for MemoryStore {
+    fn default() -> Self {
+        Self::new(10_000)
+    }
+}
+
+impl MemoryStore {
+    pub fn new(max_rows: usize) -> Self {
+        Self { max_rows, tables: Mutex::new(Tables::default()) }
+    }
+
+    fn prices_since(&self, symbol: &str, since: i64) -> Vec<AggregatedPrice> {
+        let tables = self.tables.lock().unwrap();
+        tables.prices.get(symbol)
+            .map(|prices| prices.iter().rev().take_while(|p| p.timestamp >= since).cloned().collect())
+            .unwrap_or_default()
+    }
+}
+
+fn push_capped<T>(rows: &mut VecDeque<T>, row: T, max_rows: usize) {
+    rows.push_back(row);
+    while rows.len() > max_rows {
+        rows.pop_front();
+    }
+}
+
+#[async_trait]
+impl PriceStore for MemoryStore {
+    async fn insert_price(&self, price: &AggregatedPrice) -> Result<()> {
+        // Stored as the Postgres store would return it
+        let stored = AggregatedPrice { timestamp: unix_now(), depegged_quotes: vec![], ..price.clone() }
+            .with_observations(price.observations());
+        let mut tables = self.tables.lock().unwrap();
+        push_capped(tables.prices.entry(price.symbol.clone()).or_default(), stored, self.max_rows);
+        Ok(())
+    }
+
+    async fn price_history(&self, symbol: &str, since: i64, limit: usize) -> Result<Vec<AggregatedPrice>> {
+        let mut prices = self.prices_since(symbol, since);
+        prices.truncate(limit);
+        Ok(prices)
+    }
+
+    async fn average_price(&self, symbol: &str, since: i64) -> Result<Option<Decimal>> {
+        let prices = self.prices_since(symbol, since);
+        if prices.is_empty() {
+            return Ok(None);
+        }
+        Ok(Some(prices.iter().map(|p| p.mark_price).sum::<Decimal>() / Decimal::from(prices.len())))
+    }
+
+    async fn count_prices(&self, symbol: &str, since: i64) -> Result<usize> {
+        Ok(self.prices_since(symbol, since).len())
+    }
+
+    async fn insert_manipulation_event(&self, event: &ManipulationEvent) -> Result<()> {
+        let mut tables = self.tables.lock().unwrap();
+        push_capped(tables.events.entry(event.symbol.clone()).or_default(), event.clone(), self.max_rows);
+        Ok(())
+    }
+
+    async fn manipulation_events(&self, symbol: &str, since: i64) -> Result<Vec<ManipulationEvent>> {
+        let tables = self.tables.lock().unwrap();
+        let mut events: Vec<ManipulationEvent> = tables.events.get(symbol)
+            .map(|events| events.iter().filter(|e| e.timestamp >= since).cloned().collect())
+            .unwrap_or_default();
+        // Stable, so events of the same second stay newest first
+        events.reverse();
+        events.sort_by_key(|event| std::cmp::Reverse(event.timestamp));
+        Ok(events)
+    }
+
+    async fn save_symbol(&self, symbol: &str, feeds: &SymbolFeeds) -> Result<()> {
+        let mut tables = self.tables.lock().unwrap();
+        match tables.symbols.iter_mut().find(|(saved, _)| saved == symbol) {
+            Some((_, saved)) => *saved = feeds.clone(),
+            None => tables.symbols.push((symbol.to_string(), feeds.clone())),
+        }
+        Ok(())
+    }
+
+    async fn remove_symbol(&self, symbol: &str) -> Result<()> {
+        self.tables.lock().unwrap().symbols.retain(|(saved, _)| saved != symbol);
+        Ok(())
+    }
+
+    async fn saved_symbols(&self) -> Result<Vec<(String, SymbolFeeds)>> {
+        Ok(self.tables.lock().unwrap().symbols.clone())
+    }
+
+    async fn check_health(&self) -> Result<()> {
+        Ok(())
+    }
+}
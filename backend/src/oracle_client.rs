@@ -1,18 +1,39 @@
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use std::collections::HashMap;
-use anyhow::{Result, anyhow};
+use anyhow::{Context, Result, anyhow};
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 use tokio::time::Instant;
 use reqwest::Client;
-use sqlx::PgPool;
+use rust_decimal::Decimal;
+use rust_decimal::prelude::ToPrimitive;
 use log::{info, warn, error};
+use std::sync::Arc;
+use crate::aggregation::{PriceMode, SourcePrice};
+use crate::bounds::PriceBounds;
+use crate::cex_client::CexClient;
+use crate::chainlink_client::ChainlinkClient;
+use crate::config::{CacheConfig, OracleFileConfig, PythOnChainMode};
+use crate::error::OracleError;
+use crate::feed_registry::{SharedFeedRegistry, SymbolFeeds};
+use crate::price_cache::{CachePolicy, Freshness, SingleFlight};
+use crate::price_store::{unix_now, PriceStore};
+use crate::pyth_account::{decode_price_account, PythAccountError};
+use crate::pyth_stream::PythStreamClient;
+use crate::quote::{QuoteAsset, QuoteRate};
+use crate::resilient_client::{BreakerSnapshot, BreakerState, ResilientClient};
+use crate::scheduler::RefreshSchedule;
+use crate::solana_rpc::{SolanaRpcClient, decode_account_data};
+use crate::source_stats::{CacheStats, CacheStatsSnapshot, SourceStats, SourceStatsSnapshot, STATS_WINDOW};
+use crate::switchboard_account::{decode_aggregator_account, SwitchboardError};
+use crate::switchboard_stream::SwitchboardStreamClient;
+use crate::synthetic::SyntheticPair;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PriceData {
     pub symbol: String,
-    pub price: f64,
-    pub confidence: f64,
+    pub price: Decimal,
+    pub confidence: Decimal,
     pub timestamp: i64,
     pub source: String,
 }
@@ -20,11 +41,114 @@ pub struct PriceData {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AggregatedPrice {
     pub symbol: String,
-    pub mark_price: f64,
-    pub index_price: f64,
-    pub confidence: f64,
+    pub mark_price: Decimal,
+    pub index_price: Decimal,
+    pub confidence: Decimal,
     pub sources: Vec<PriceData>,
     pub timestamp: i64,
+    /// Sources left out of the aggregate, and why
+    #[serde(default)]
+    pub excluded_sources: Vec<ExcludedSource>,
+    #[serde(default)]
+    pub mode: PriceMode,
+    /// Stablecoins past the depeg threshold that sources were still converted through
+    #[serde(default)]
+    pub depegged_quotes: Vec<QuoteRate>,
+}
+
+impl AggregatedPrice {
+    /// Every source behind this price, used ones first
+    pub fn observations(&self) -> Vec<SourceObservation> {
+        let used = self.sources.iter().map(|source| SourceObservation {
+            symbol: source.symbol.clone(),
+            source: source.source.clone(),
+            price: source.price,
+            confidence: Some(source.confidence),
+            timestamp: Some(source.timestamp),
+            excluded_reason: None,
+        });
+        let excluded = self.excluded_sources.iter().map(|source| SourceObservation {
+            symbol: source.symbol.clone(),
+            source: source.source.clone(),
+            price: source.price,
+            confidence: Some(source.confidence),
+            timestamp: Some(source.timestamp),
+            excluded_reason: Some(source.reason.clone()),
+        });
+        used.chain(excluded).collect()
+    }
+
+    /// Restores the used and excluded sources of a stored price from its observations
+    pub fn with_observations(self, observations: Vec<SourceObservation>) -> Self {
+        let (mut sources, mut excluded_sources) = (Vec::new(), Vec::new());
+        for observation in observations {
+            let price = PriceData {
+                symbol: observation.symbol,
+                price: observation.price,
+                confidence: observation.confidence.unwrap_or_default(),
+                timestamp: observation.timestamp.unwrap_or(self.timestamp),
+                source: observation.source,
+            };
+            match observation.excluded_reason {
+                Some(reason) => excluded_sources.push(ExcludedSource::new(price, reason)),
+                None => sources.push(price),
+            }
+        }
+        Self { sources, excluded_sources, ..self }
+    }
+
+    /// Widest spread between the sources quoting one symbol, as a fraction of their midpoint.
+    /// Synthetic prices carry their legs' sources, so each leg is measured on its own.
+    pub fn source_dispersion(&self) -> f64 {
+        let mut ranges: HashMap<&str, (Decimal, Decimal)> = HashMap::new();
+        for source in &self.sources {
+            let range = ranges.entry(source.symbol.as_str()).or_insert((source.price, source.price));
+            *range = (range.0.min(source.price), range.1.max(source.price));
+        }
+        ranges.values()
+            .filter(|(low, high)| !(*low + *high).is_zero())
+            .filter_map(|(low, high)| ((*high - *low) * Decimal::TWO / (*low + *high)).to_f64())
+            .fold(0.0, f64::max)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExcludedSource {
+    pub source: String,
+    pub price: Decimal,
+    pub reason: String,
+    #[serde(default)]
+    pub symbol: String,
+    #[serde(default)]
+    pub confidence: Decimal,
+    #[serde(default)]
+    pub timestamp: i64,
+}
+
+impl ExcludedSource {
+    pub fn new(price: PriceData, reason: String) -> Self {
+        Self {
+            source: price.source,
+            price: price.price,
+            reason,
+            symbol: price.symbol,
+            confidence: price.confidence,
+            timestamp: price.timestamp,
+        }
+    }
+}
+
+/// One source price as persisted with the aggregate it went into or was left out of
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SourceObservation {
+    /// Differs from the aggregate's symbol for the legs of synthetic pairs
+    pub symbol: String,
+    pub source: String,
+    pub price: Decimal,
+    pub confidence: Option<Decimal>,
+    pub timestamp: Option<i64>,
+    /// Why the source was left out of the aggregate; `None` if it was used
+    pub excluded_reason: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -32,8 +156,8 @@ pub struct FundingRateData {
     pub symbol: String,
     pub funding_rate: f64,      // 8-hour funding rate
     pub predicted_rate: f64,    // Predicted next funding rate
-    pub mark_price: f64,        // Current mark price
-    pub index_price: f64,       // Index price for funding calculation
+    pub mark_price: Decimal,    // Current mark price
+    pub index_price: Decimal,   // Index price for funding calculation
     pub premium: f64,           // Mark-index premium
     pub timestamp: i64,
 }
@@ -43,132 +167,96 @@ pub struct LiquidationPrice {
     pub symbol: String,
     pub long_liquidation: f64,  // Long position liquidation price
     pub short_liquidation: f64, // Short position liquidation price
-    pub mark_price: f64,        // Current mark price
+    pub mark_price: Decimal,    // Current mark price
     pub maintenance_margin: f64, // Required maintenance margin
     pub timestamp: i64,
 }
 
+/// `mantissa * 10^expo`, as published by Pyth, without going through floating point
+pub fn scaled_decimal(mantissa: i128, expo: i32) -> Result<Decimal> {
+    let scaled = if expo <= 0 {
+        Decimal::try_from_i128_with_scale(mantissa, expo.unsigned_abs()).ok()
+    } else {
+        10i64.checked_pow(expo as u32)
+            .and_then(|factor| Decimal::try_from_i128_with_scale(mantissa, 0).ok()?.checked_mul(factor.into()))
+    };
+    scaled.ok_or_else(|| anyhow!("{}e{} is out of decimal range", mantissa, expo))
+}
+
 #[async_trait]
 pub trait OracleClient: Send + Sync {
     async fn get_price(&self, symbol: &str) -> Result<PriceData>;
     async fn get_multiple_prices(&self, symbols: &[String]) -> Result<Vec<PriceData>>;
     fn get_name(&self) -> &str;
+
+    /// Whether this source has a feed for `symbol`; sources without one are never asked for it
+    async fn supports(&self, _symbol: &str) -> bool {
+        true
+    }
+
+    /// Breaker state for sources wrapped in a `ResilientClient`
+    fn circuit_breaker(&self) -> Option<BreakerSnapshot> {
+        None
+    }
+
+    /// Quote of this source's prices for `symbol`, when it may differ from the symbol's own
+    fn quote_asset(&self, _symbol: &str) -> Option<QuoteAsset> {
+        None
+    }
+}
+
+/// Result of a batch fetch: every requested symbol ends up in exactly one of the two
+#[derive(Debug, Default)]
+pub struct PriceBatch {
+    pub prices: Vec<PriceData>,
+    pub errors: HashMap<String, String>,
+}
+
+impl PriceBatch {
+    fn fail(&mut self, symbol: &str, error: String) {
+        self.errors.insert(symbol.to_string(), error);
+    }
 }
 
 pub struct PythClient {
     client: Client,
     base_url: String,
-    price_feed_ids: HashMap<String, String>,
+    registry: SharedFeedRegistry,
 }
 
 impl PythClient {
-    pub fn new() -> Self {
-        let mut price_feed_ids = HashMap::new();
-        
-        // Add common price feed IDs for major trading pairs
-        price_feed_ids.insert("BTC/USD".to_string(), "f9c0172ba10dfa4d19088d94f5bf61d3b54d5bd7483a322a982e1373ee8ea31b".to_string());
-        price_feed_ids.insert("ETH/USD".to_string(), "ca80ba6dc32e08d06f1aa886011eed1d77c77be9eb761cc10d72b7d0a2fd57a6".to_string());
-        price_feed_ids.insert("SOL/USD".to_string(), "7UVimffxr9ow1uXYxsr4LHAcV58mLzhmwaeKvJ1pjLiE".to_string());
-        price_feed_ids.insert("AVAX/USD".to_string(), "93DA3b71E5B3b93c47266eaBca3992b073Ce6b6B".to_string());
-        price_feed_ids.insert("BNB/USD".to_string(), "2f95862b045670cd22bee3114c39763a4a08beeb663b145d283c31d7d1101c4f".to_string());
-
+    pub fn new(base_url: String, registry: SharedFeedRegistry) -> Self {
         Self {
             client: Client::new(),
-            base_url: "https://hermes.pyth.network".to_string(),
-            price_feed_ids,
+            base_url,
+            registry,
         }
     }
 
-    async fn get_price_feed_id(&self, symbol: &str) -> Result<&String> {
-        self.price_feed_ids.get(symbol)
-            .ok_or_else(|| anyhow!("Price feed ID not found for symbol: {}", symbol))
-    }
-}
-
-#[async_trait]
-impl OracleClient for PythClient {
-    async fn get_price(&self, symbol: &str) -> Result<PriceData> {
-        let feed_id = self.get_price_feed_id(symbol).await?;
-        
-        // Use the correct Pyth Hermes API endpoint
-        let url = format!("{}/v2/updates/price/latest?ids[]={}&parsed=true", self.base_url, feed_id);
-        
-        let response = self.client
-            .get(&url)
-            .timeout(Duration::from_secs(10))
-            .header("User-Agent", "GoQuant-Oracle/1.0")
-            .send()
-            .await?;
-
-        // Debug response for troubleshooting
-        let response_text = response.text().await?;
-        let response_json: serde_json::Value = serde_json::from_str(&response_text)
-            .map_err(|e| anyhow!("Failed to parse Pyth response '{}': {}", response_text, e))?;
-
-        let parsed = response_json["parsed"]
-            .as_array()
-            .ok_or_else(|| anyhow!("No parsed data in Pyth response"))?;
-
-        if parsed.is_empty() {
-            return Err(anyhow!("No price data returned from Pyth API"));
-        }
-
-        let price_feed = &parsed[0]["price"];
-        let price = price_feed["price"]
-            .as_str()
-            .ok_or_else(|| anyhow!("Price not found in response"))?
-            .parse::<i64>()?;
-        
-        let confidence = price_feed["conf"]
-            .as_str()
-            .ok_or_else(|| anyhow!("Confidence not found in response"))?
-            .parse::<u64>()?;
-            
-        let expo = price_feed["expo"]
-            .as_i64()
-            .ok_or_else(|| anyhow!("Exponent not found in response"))?;
-            
-        let timestamp = price_feed["publish_time"]
-            .as_i64()
-            .ok_or_else(|| anyhow!("Timestamp not found in response"))?;
-
-        // Convert price to float with proper exponent
-        let normalized_price = (price as f64) * 10_f64.powi(expo as i32);
-        let normalized_confidence = (confidence as f64) * 10_f64.powi(expo as i32);
-
-        // Validate price is reasonable
-        if normalized_price <= 0.0 || normalized_price > 1_000_000.0 {
-            return Err(anyhow!("Invalid price from Pyth: {}", normalized_price));
-        }
-
-        Ok(PriceData {
-            symbol: symbol.to_string(),
-            price: normalized_price,
-            confidence: normalized_confidence.abs(),
-            timestamp,
-            source: "Pyth".to_string(),
-        })
-    }
-
-    async fn get_multiple_prices(&self, symbols: &[String]) -> Result<Vec<PriceData>> {
-        let mut feed_ids = Vec::new();
-        for symbol in symbols {
-            if let Ok(feed_id) = self.get_price_feed_id(symbol).await {
-                feed_ids.push(feed_id.clone());
+    /// Fetches all symbols in one Hermes request, matching entries to symbols by feed ID
+    pub async fn fetch_batch(&self, symbols: &[String]) -> Result<PriceBatch> {
+        let mut batch = PriceBatch::default();
+        let mut symbols_by_id: HashMap<String, Vec<&String>> = HashMap::new();
+        {
+            let registry = self.registry.read().await;
+            for symbol in symbols {
+                match registry.pyth_feed_id(symbol) {
+                    Some(feed_id) => symbols_by_id.entry(feed_id.clone()).or_default().push(symbol),
+                    None => batch.fail(symbol, format!("Price feed ID not found for symbol: {}", symbol)),
+                }
             }
         }
 
-        if feed_ids.is_empty() {
-            return Err(anyhow!("No valid feed IDs found for provided symbols"));
+        if symbols_by_id.is_empty() {
+            return Ok(batch);
         }
 
-        let ids_param = feed_ids.iter()
+        let ids_param = symbols_by_id.keys()
             .map(|id| format!("ids[]={}", id))
             .collect::<Vec<_>>()
             .join("&");
-        
         let url = format!("{}/v2/updates/price/latest?{}&parsed=true", self.base_url, ids_param);
-        
+
         let response = self.client
             .get(&url)
             .timeout(Duration::from_secs(15))
@@ -182,27 +270,76 @@ impl OracleClient for PythClient {
 
         let feeds = response_json["parsed"]
             .as_array()
-            .ok_or_else(|| anyhow!("Invalid response format from Pyth API"))?;
+            .ok_or_else(|| anyhow!("No parsed data in Pyth response"))?;
 
-        let mut results = Vec::new();
-        for (i, feed) in feeds.iter().enumerate() {
-            if let Some(symbol) = symbols.get(i) {
-                if let Ok(price_data) = self.parse_price_feed(symbol, feed).await {
-                    results.push(price_data);
+        // Hermes may reorder or omit entries; only the returned id identifies a feed
+        for feed in feeds {
+            let Some(id) = feed["id"].as_str() else {
+                warn!("Ignoring Pyth entry without an id");
+                continue;
+            };
+            let id = id.trim_start_matches("0x").to_lowercase();
+            let Some(feed_symbols) = symbols_by_id.remove(&id) else {
+                warn!("Ignoring unrequested Pyth feed {}", id);
+                continue;
+            };
+
+            for symbol in feed_symbols {
+                match Self::parse_price_feed(symbol, feed).and_then(Self::validate_price) {
+                    Ok(price_data) => batch.prices.push(price_data),
+                    Err(e) => batch.fail(symbol, e.to_string()),
                 }
             }
         }
 
-        Ok(results)
+        for (id, missing) in symbols_by_id {
+            for symbol in missing {
+                batch.fail(symbol, format!("Feed {} not returned by Pyth API", id));
+            }
+        }
+
+        Ok(batch)
+    }
+
+    fn validate_price(price: PriceData) -> Result<PriceData> {
+        // Per-symbol bands are applied when aggregating
+        if price.price <= Decimal::ZERO {
+            return Err(anyhow!("Invalid price from Pyth: {}", price.price));
+        }
+        Ok(PriceData { confidence: price.confidence.abs(), ..price })
+    }
+}
+
+#[async_trait]
+impl OracleClient for PythClient {
+    async fn get_price(&self, symbol: &str) -> Result<PriceData> {
+        let mut batch = self.fetch_batch(&[symbol.to_string()]).await?;
+        match batch.errors.remove(symbol) {
+            Some(error) => Err(anyhow!(error)),
+            None => batch.prices.pop().ok_or_else(|| anyhow!("No price data returned from Pyth API")),
+        }
+    }
+
+    async fn get_multiple_prices(&self, symbols: &[String]) -> Result<Vec<PriceData>> {
+        let batch = self.fetch_batch(symbols).await?;
+        for (symbol, error) in &batch.errors {
+            warn!("Failed to fetch {} price from Pyth: {}", symbol, error);
+        }
+        Ok(batch.prices)
     }
 
     fn get_name(&self) -> &str {
         "Pyth"
     }
+
+    async fn supports(&self, symbol: &str) -> bool {
+        self.registry.read().await.pyth_feed_id(symbol).is_some()
+    }
 }
 
 impl PythClient {
-    async fn parse_price_feed(&self, symbol: &str, feed: &serde_json::Value) -> Result<PriceData> {
+    /// Converts one Hermes `parsed` entry into a price
+    pub fn parse_price_feed(symbol: &str, feed: &serde_json::Value) -> Result<PriceData> {
         let price_feed = &feed["price"];
         let price = price_feed["price"]
             .as_str()
@@ -222,13 +359,12 @@ impl PythClient {
             .as_i64()
             .ok_or_else(|| anyhow!("Timestamp not found in feed"))?;
 
-        let normalized_price = (price as f64) * 10_f64.powi(expo as i32);
-        let normalized_confidence = (confidence as f64) * 10_f64.powi(expo as i32);
+        let expo = i32::try_from(expo).map_err(|_| anyhow!("Exponent {} out of range", expo))?;
 
         Ok(PriceData {
             symbol: symbol.to_string(),
-            price: normalized_price,
-            confidence: normalized_confidence,
+            price: scaled_decimal(price.into(), expo)?,
+            confidence: scaled_decimal(confidence.into(), expo)?,
             timestamp,
             source: "Pyth".to_string(),
         })
@@ -236,87 +372,38 @@ impl PythClient {
 }
 
 pub struct SwitchboardClient {
-    client: Client,
-    rpc_url: String,
-    aggregator_addresses: HashMap<String, String>,
-    rate_limiter: tokio::sync::Semaphore,
-    last_request_time: std::sync::Arc<tokio::sync::RwLock<Instant>>,
+    rpc: Arc<SolanaRpcClient>,
+    registry: SharedFeedRegistry,
 }
 
 impl SwitchboardClient {
-    pub fn new(rpc_url: String) -> Self {
-        let mut aggregator_addresses = HashMap::new();
-        
-        // Add Switchboard aggregator addresses for major trading pairs
-        aggregator_addresses.insert("BTC/USD".to_string(), "74YzQPGUT9VnjrBz8MuyDLKgKpbDqGot5xZJvTtMi6Ng".to_string());
-        aggregator_addresses.insert("ETH/USD".to_string(), "HNStfhaLnqwF2ZtJUizaA9uHDAVB976r2AgTUx9LrdEo".to_string());
-        aggregator_addresses.insert("SOL/USD".to_string(), "GvDMxPzN1sCj7L26YDK2HnMRXEQmQ2aemov8YBtPS7vR".to_string());
-        aggregator_addresses.insert("AVAX/USD".to_string(), "Axk7bZGJn5V6MjJHwRKRCgTcXJj3h9J8p7NQwV1x2HSx".to_string());
-
-        Self {
-            client: Client::new(),
-            rpc_url,
-            aggregator_addresses,
-            rate_limiter: tokio::sync::Semaphore::new(2), // Max 2 concurrent requests
-            last_request_time: std::sync::Arc::new(tokio::sync::RwLock::new(Instant::now())),
-        }
+    /// `rpc` (and its rate limit) may be shared with other on-chain readers
+    pub fn new(rpc: Arc<SolanaRpcClient>, registry: SharedFeedRegistry) -> Self {
+        Self { rpc, registry }
     }
 
-    async fn get_aggregator_address(&self, symbol: &str) -> Result<&String> {
-        self.aggregator_addresses.get(symbol)
+    async fn get_aggregator_address(&self, symbol: &str) -> Result<String> {
+        self.registry.read().await
+            .switchboard_address(symbol)
+            .cloned()
             .ok_or_else(|| anyhow!("Aggregator address not found for symbol: {}", symbol))
     }
 
-    async fn fetch_account_data(&self, address: &str) -> Result<serde_json::Value> {
-        // Rate limiting - wait at least 500ms between requests
-        let _permit = self.rate_limiter.acquire().await?;
-        {
-            let last_time = self.last_request_time.read().await;
-            let elapsed = last_time.elapsed();
-            if elapsed < Duration::from_millis(500) {
-                tokio::time::sleep(Duration::from_millis(500) - elapsed).await;
-            }
-        }
-        
-        let payload = serde_json::json!({
-            "jsonrpc": "2.0",
-            "id": 1,
-            "method": "getAccountInfo",
-            "params": [
-                address,
-                {
-                    "encoding": "base64",
-                    "commitment": "finalized"
-                }
-            ]
-        });
-
-        let response = self.client
-            .post(&self.rpc_url)
-            .json(&payload)
-            .timeout(Duration::from_secs(10))
-            .header("User-Agent", "GoQuant-Oracle/1.0")
-            .send()
-            .await?;
-
-        // Update last request time
-        {
-            let mut last_time = self.last_request_time.write().await;
-            *last_time = Instant::now();
-        }
-
-        let response_text = response.text().await?;
-        let response_json: serde_json::Value = serde_json::from_str(&response_text)
-            .map_err(|e| anyhow!("Failed to parse Switchboard response '{}': {}", response_text, e))?;
+    /// Decodes a `getAccountInfo` response for an aggregator into a price
+    pub fn parse_account_info(symbol: &str, address: &str, response: &serde_json::Value) -> Result<PriceData> {
+        let data = decode_account_data(response)
+            .map_err(SwitchboardError::InvalidEncoding)?
+            .ok_or_else(|| SwitchboardError::AccountNotFound(address.to_string()))?;
 
-        if let Some(error) = response_json.get("error") {
-            return Err(anyhow!("RPC error: {}", error));
-        }
+        let round = decode_aggregator_account(&data)?;
 
-        response_json["result"]["value"]
-            .as_object()
-            .ok_or_else(|| anyhow!("Invalid account data response"))
-            .map(|_| response_json["result"]["value"].clone())
+        Ok(PriceData {
+            symbol: symbol.to_string(),
+            price: round.result,
+            confidence: round.std_deviation,
+            timestamp: round.round_open_timestamp,
+            source: "Switchboard".to_string(),
+        })
     }
 }
 
@@ -324,23 +411,8 @@ impl SwitchboardClient {
 impl OracleClient for SwitchboardClient {
     async fn get_price(&self, symbol: &str) -> Result<PriceData> {
         let aggregator_address = self.get_aggregator_address(symbol).await?;
-        
-        // Try to fetch real account data first, fall back to mock if failed
-        match self.fetch_account_data(aggregator_address).await {
-            Ok(account_data) => {
-                let _data = account_data["data"]
-                    .as_array()
-                    .ok_or_else(|| anyhow!("No data field in account response"))?;
-                
-                // For now, return mock data since we need Switchboard SDK for proper parsing
-                // In production, you'd decode the account data using Switchboard SDK
-                self.get_mock_price_internal(symbol)
-            }
-            Err(e) => {
-                warn!("Failed to fetch real Switchboard data for {}: {}, using mock", symbol, e);
-                self.get_mock_price_internal(symbol)
-            }
-        }
+        let response = self.rpc.get_account_info(&aggregator_address).await?;
+        Self::parse_account_info(symbol, &aggregator_address, &response)
     }
 
     async fn get_multiple_prices(&self, symbols: &[String]) -> Result<Vec<PriceData>> {
@@ -363,92 +435,380 @@ impl OracleClient for SwitchboardClient {
     fn get_name(&self) -> &str {
         "Switchboard"
     }
+
+    async fn supports(&self, symbol: &str) -> bool {
+        self.registry.read().await.switchboard_address(symbol).is_some()
+    }
 }
 
-impl SwitchboardClient {
-    fn get_mock_price_internal(&self, symbol: &str) -> Result<PriceData> {
-        let current_time = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .unwrap()
-            .as_secs() as i64;
-        
-        // Mock price data with some variance to simulate real market movement
-        let base_price = match symbol {
-            "BTC/USD" => 65000.0,
-            "ETH/USD" => 3500.0,
-            "SOL/USD" => 150.0,
-            "AVAX/USD" => 35.0,
-            _ => 100.0,
-        };
+pub struct PythAccountClient {
+    rpc: Arc<SolanaRpcClient>,
+    registry: SharedFeedRegistry,
+}
+
+impl PythAccountClient {
+    pub fn new(rpc: Arc<SolanaRpcClient>, registry: SharedFeedRegistry) -> Self {
+        Self { rpc, registry }
+    }
+
+    async fn get_price_account(&self, symbol: &str) -> Result<String> {
+        self.registry.read().await
+            .pyth_price_account(symbol)
+            .cloned()
+            .ok_or_else(|| anyhow!("Pyth price account not found for symbol: {}", symbol))
+    }
 
-        // Add small random variance (+/- 1%)
-        use std::collections::hash_map::DefaultHasher;
-        use std::hash::{Hash, Hasher};
-        let mut hasher = DefaultHasher::new();
-        (symbol, current_time / 60).hash(&mut hasher); // Change every minute
-        let hash = hasher.finish();
-        let variance = ((hash % 200) as f64 - 100.0) / 10000.0; // +/- 1%
-        let mock_price = base_price * (1.0 + variance);
+    /// Decodes a `getAccountInfo` response for a Pyth price account into a price
+    pub fn parse_account_info(symbol: &str, address: &str, response: &serde_json::Value) -> Result<PriceData> {
+        let data = decode_account_data(response)
+            .map_err(PythAccountError::InvalidEncoding)?
+            .ok_or_else(|| PythAccountError::AccountNotFound(address.to_string()))?;
+
+        let account = decode_price_account(&data)?;
 
         Ok(PriceData {
             symbol: symbol.to_string(),
-            price: mock_price,
-            confidence: mock_price * 0.001, // 0.1% confidence interval
-            timestamp: current_time,
-            source: "Switchboard".to_string(),
+            price: scaled_decimal(account.price.into(), account.expo)?,
+            confidence: scaled_decimal(account.conf.into(), account.expo)?,
+            timestamp: account.publish_time,
+            source: "PythOnChain".to_string(),
         })
     }
+}
+
+#[async_trait]
+impl OracleClient for PythAccountClient {
+    async fn get_price(&self, symbol: &str) -> Result<PriceData> {
+        let address = self.get_price_account(symbol).await?;
+        let response = self.rpc.get_account_info(&address).await?;
+        Self::parse_account_info(symbol, &address, &response)
+    }
 
+    async fn get_multiple_prices(&self, symbols: &[String]) -> Result<Vec<PriceData>> {
+        let futures = symbols.iter().map(|symbol| self.get_price(symbol));
+        let price_results = futures::future::join_all(futures).await;
+
+        let mut results = Vec::new();
+        for (symbol, result) in symbols.iter().zip(price_results) {
+            match result {
+                Ok(price_data) => results.push(price_data),
+                Err(e) => warn!("Failed to fetch {} price from Pyth account: {}", symbol, e),
+            }
+        }
+
+        Ok(results)
+    }
+
+    fn get_name(&self) -> &str {
+        "PythOnChain"
+    }
+
+    async fn supports(&self, symbol: &str) -> bool {
+        self.registry.read().await.pyth_price_account(symbol).is_some()
+    }
+}
+
+/// Serves from `primary`, asking `fallback` only for what the primary couldn't price
+pub struct FallbackClient {
+    primary: Box<dyn OracleClient>,
+    fallback: Box<dyn OracleClient>,
+}
+
+impl FallbackClient {
+    pub fn new(primary: Box<dyn OracleClient>, fallback: Box<dyn OracleClient>) -> Self {
+        Self { primary, fallback }
+    }
+}
+
+#[async_trait]
+impl OracleClient for FallbackClient {
+    async fn get_price(&self, symbol: &str) -> Result<PriceData> {
+        match self.primary.get_price(symbol).await {
+            Ok(price) => Ok(price),
+            Err(e) => {
+                warn!(
+                    "{} failed for {} ({}), trying {}",
+                    self.primary.get_name(), symbol, e, self.fallback.get_name()
+                );
+                self.fallback.get_price(symbol).await
+            }
+        }
+    }
+
+    async fn get_multiple_prices(&self, symbols: &[String]) -> Result<Vec<PriceData>> {
+        let mut results = match self.primary.get_multiple_prices(symbols).await {
+            Ok(prices) => prices,
+            Err(e) => {
+                warn!("{} batch failed ({}), trying {}", self.primary.get_name(), e, self.fallback.get_name());
+                Vec::new()
+            }
+        };
+
+        let missing: Vec<String> = symbols.iter()
+            .filter(|symbol| !results.iter().any(|p| &p.symbol == *symbol))
+            .cloned()
+            .collect();
+        if !missing.is_empty() {
+            results.extend(self.fallback.get_multiple_prices(&missing).await.unwrap_or_default());
+        }
+
+        Ok(results)
+    }
+
+    async fn supports(&self, symbol: &str) -> bool {
+        self.primary.supports(symbol).await || self.fallback.supports(symbol).await
+    }
+
+    fn get_name(&self) -> &str {
+        self.primary.get_name()
+    }
+}
+
+/// Oracle clients for the configured sources and exchanges; on-chain readers share one RPC client
+pub fn build_oracle_clients(
+    config: &OracleFileConfig,
+    registry: &SharedFeedRegistry,
+    pyth_base_url: String,
+    solana_rpc_url: String,
+) -> Vec<Box<dyn OracleClient>> {
+    let settings = &config.oracle;
+    let max_age = Duration::from_secs(settings.max_price_age_seconds);
+    let rpc = Arc::new(SolanaRpcClient::new(solana_rpc_url));
+
+    let polling = Box::new(PythClient::new(pyth_base_url.clone(), registry.clone()));
+    let hermes: Box<dyn OracleClient> = if settings.pyth_stream {
+        let stream = PythStreamClient::start(pyth_base_url, registry.clone(), max_age);
+        Box::new(FallbackClient::new(Box::new(stream), polling))
+    } else {
+        polling
+    };
+    let pyth_account = || Box::new(PythAccountClient::new(rpc.clone(), registry.clone()));
+
+    let mut clients = match settings.pyth_onchain {
+        PythOnChainMode::Fallback => vec![Box::new(FallbackClient::new(hermes, pyth_account())) as Box<dyn OracleClient>],
+        PythOnChainMode::Source => vec![hermes, pyth_account() as Box<dyn OracleClient>],
+        PythOnChainMode::Disabled => vec![hermes],
+    };
+
+    let polling = Box::new(SwitchboardClient::new(rpc.clone(), registry.clone()));
+    let switchboard: Box<dyn OracleClient> = if settings.switchboard_stream {
+        let stream = SwitchboardStreamClient::start(
+            config.solana.ws_url.clone(),
+            config.solana.commitment.clone(),
+            registry.clone(),
+            max_age,
+        );
+        Box::new(FallbackClient::new(Box::new(stream), polling))
+    } else {
+        polling
+    };
+    clients.push(switchboard);
+
+    if let Some(evm) = &config.evm {
+        clients.push(Box::new(ChainlinkClient::new(evm.rpc_url.clone(), registry.clone(), max_age)));
+    }
+
+    for exchange in &config.exchanges {
+        clients.push(Box::new(CexClient::from_config(exchange)));
+    }
+
+    clients.into_iter()
+        .map(|client| Box::new(ResilientClient::new(client, settings.resilience.clone())) as Box<dyn OracleClient>)
+        .collect()
 }
 
 pub struct OracleManager {
     clients: Vec<Box<dyn OracleClient>>,
-    db_pool: PgPool,
+    source_stats: Vec<SourceStats>, // Parallel to `clients`
+    registry: SharedFeedRegistry,
+    store: Arc<dyn PriceStore>,
     price_cache: tokio::sync::RwLock<HashMap<String, (AggregatedPrice, Instant)>>,
-    cache_duration: Duration,
+    cache_policy: CachePolicy,
+    cache_stats: CacheStats,
+    fetches: SingleFlight<std::result::Result<AggregatedPrice, OracleError>>, // One fetch per symbol at a time
+    revalidate_tx: tokio::sync::mpsc::UnboundedSender<String>,
+    revalidate_rx: std::sync::Mutex<Option<tokio::sync::mpsc::UnboundedReceiver<String>>>,
+    last_good: tokio::sync::RwLock<HashMap<String, AggregatedPrice>>, // Last price that met quorum
 }
 
 impl std::fmt::Debug for OracleManager {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("OracleManager")
             .field("client_count", &self.clients.len())
-            .field("cache_duration", &self.cache_duration)
+            .field("cache_policy", &self.cache_policy)
             .finish()
     }
 }
 
 impl OracleManager {
-    pub fn new(db_pool: PgPool) -> Self {
-        let pyth_client = Box::new(PythClient::new());
-        let switchboard_client = Box::new(SwitchboardClient::new(
-            "https://api.mainnet-beta.solana.com".to_string()
-        ));
-        
+    pub fn new(store: Arc<dyn PriceStore>, registry: SharedFeedRegistry, clients: Vec<Box<dyn OracleClient>>, cache: &CacheConfig) -> Self {
+        let (revalidate_tx, revalidate_rx) = tokio::sync::mpsc::unbounded_channel();
         Self {
-            clients: vec![pyth_client, switchboard_client],
-            db_pool,
+            source_stats: clients.iter().map(|_| SourceStats::new(STATS_WINDOW)).collect(),
+            clients,
+            registry,
+            store,
             price_cache: tokio::sync::RwLock::new(HashMap::new()),
-            cache_duration: Duration::from_millis(500), // 500ms cache for sub-500ms latency
+            cache_policy: CachePolicy::from(cache),
+            cache_stats: CacheStats::default(),
+            fetches: SingleFlight::default(),
+            revalidate_tx,
+            revalidate_rx: std::sync::Mutex::new(Some(revalidate_rx)),
+            last_good: tokio::sync::RwLock::new(HashMap::new()),
         }
     }
 
+    /// Symbols currently registered as trading pairs
+    pub async fn monitored_symbols(&self) -> Vec<String> {
+        self.registry.read().await.symbols().to_vec()
+    }
+
+    /// Monitored symbols with their refresh schedules
+    pub async fn refresh_schedules(&self) -> Vec<(String, RefreshSchedule)> {
+        let registry = self.registry.read().await;
+        registry.symbols().iter().map(|symbol| (symbol.clone(), registry.refresh_schedule(symbol))).collect()
+    }
+
+    pub async fn price_bounds(&self, symbol: &str) -> PriceBounds {
+        self.registry.read().await.price_bounds(symbol)
+    }
+
+    pub async fn max_source_age_seconds(&self, symbol: &str, source: &str) -> u64 {
+        self.registry.read().await.max_source_age_seconds(symbol, source)
+    }
+
     pub async fn get_aggregated_price(&self, symbol: &str) -> Result<AggregatedPrice> {
-        // Check cache first
-        {
-            let cache = self.price_cache.read().await;
-            if let Some((price, cached_at)) = cache.get(symbol) {
-                if cached_at.elapsed() < self.cache_duration {
-                    return Ok(price.clone());
+        if !self.registry.read().await.contains(symbol) {
+            return Err(OracleError::UnknownSymbol(symbol.to_string()).into());
+        }
+        self.get_price(symbol).await
+    }
+
+    /// Fetches `symbol` past the cache and caches the result, for scheduled refreshes
+    pub async fn refresh_price(&self, symbol: &str) -> Result<AggregatedPrice> {
+        if !self.registry.read().await.contains(symbol) {
+            return Err(OracleError::UnknownSymbol(symbol.to_string()).into());
+        }
+        self.refresh(symbol).await
+    }
+
+    /// Serves `symbol` from cache, or from a fetch shared with concurrent callers
+    async fn get_price(&self, symbol: &str) -> Result<AggregatedPrice> {
+        if let Some(price) = self.cached_price(symbol).await {
+            return Ok(price);
+        }
+        self.refresh(symbol).await
+    }
+
+    /// Fetches, stores and caches `symbol`, joining the fetch already running for it if any
+    async fn refresh(&self, symbol: &str) -> Result<AggregatedPrice> {
+        let fetch = async {
+            let synthetic = self.registry.read().await.synthetic(symbol).cloned();
+            let fetched = match synthetic {
+                Some(pair) => self.fetch_synthetic_price(symbol, &pair).await,
+                None => self.fetch_direct_price(symbol).await,
+            };
+            fetched.map_err(OracleError::from)
+        };
+        let (result, shared) = self.fetches.run(symbol, fetch).await;
+        if shared {
+            self.cache_stats.record_coalesced();
+        }
+        Ok(result?)
+    }
+
+    async fn cached_price(&self, symbol: &str) -> Option<AggregatedPrice> {
+        let cache = self.price_cache.read().await;
+        let cached = cache.get(symbol).map(|(price, cached_at)| (price, self.cache_policy.freshness(cached_at.elapsed())));
+        match cached {
+            Some((price, Freshness::Fresh)) => {
+                self.cache_stats.record_hit();
+                Some(price.clone())
+            }
+            Some((price, Freshness::Stale)) => {
+                self.cache_stats.record_stale();
+                if !self.fetches.is_running(symbol) {
+                    // Only fails once the manager is being dropped
+                    let _ = self.revalidate_tx.send(symbol.to_string());
+                }
+                Some(price.clone())
+            }
+            _ => {
+                self.cache_stats.record_miss();
+                None
+            }
+        }
+    }
+
+    /// Refreshes prices served stale under stale-while-revalidate, a few at a time.
+    /// Runs for the life of the manager; only the first call does anything.
+    pub async fn run_revalidation(&self) {
+        let Some(mut requests) = self.revalidate_rx.lock().unwrap().take() else {
+            warn!("Cache revalidation is already running");
+            return;
+        };
+
+        let mut pending = std::collections::HashSet::new();
+        let mut refreshing = futures::stream::FuturesUnordered::new();
+        loop {
+            tokio::select! {
+                Some(symbol) = requests.recv() => {
+                    let fresh = matches!(
+                        self.price_cache.read().await.get(&symbol),
+                        Some((_, cached_at)) if self.cache_policy.freshness(cached_at.elapsed()) == Freshness::Fresh
+                    );
+                    if !fresh && pending.insert(symbol.clone()) {
+                        refreshing.push(async move {
+                            let result = self.refresh(&symbol).await;
+                            (symbol, result)
+                        });
+                    }
+                }
+                Some((symbol, result)) = futures::StreamExt::next(&mut refreshing) => {
+                    pending.remove(&symbol);
+                    if let Err(e) = result {
+                        warn!("Failed to revalidate {}: {}", symbol, e);
+                    }
                 }
+                else => break,
             }
         }
+    }
+
+    /// Derives a synthetic symbol from its legs, each fetched (or served from cache) as usual
+    async fn fetch_synthetic_price(&self, symbol: &str, pair: &SyntheticPair) -> Result<AggregatedPrice> {
+        // Boxed since each leg is itself fetched through `refresh`
+        let (multiply, divide) = futures::future::try_join(
+            futures::future::try_join_all(pair.multiply.iter().map(|leg| Box::pin(self.get_price(leg)))),
+            futures::future::try_join_all(pair.divide.iter().map(|leg| Box::pin(self.get_price(leg)))),
+        )
+            .await
+            .with_context(|| format!("Failed to price {} from its legs", symbol))?;
+        let aggregated = pair.combine(symbol, &multiply, &divide)?;
+        if let Some(violation) = self.price_bounds(symbol).await.violation(aggregated.mark_price) {
+            return Err(OracleError::OutOfBounds { symbol: symbol.to_string(), reason: violation }.into());
+        }
+
+        if aggregated.mode != PriceMode::LastGood {
+            self.store_price_data(&aggregated).await?;
+        }
+        self.price_cache.write().await.insert(symbol.to_string(), (aggregated.clone(), Instant::now()));
+        Ok(aggregated)
+    }
 
+    async fn fetch_direct_price(&self, symbol: &str) -> Result<AggregatedPrice> {
         // Fetch from all oracle sources
         let mut all_prices = Vec::new();
-        let fetch_futures = self.clients.iter().map(|client| {
+        let fetch_futures = self.clients.iter().zip(&self.source_stats).map(|(client, stats)| {
             async move {
-                match client.get_price(symbol).await {
-                    Ok(price) => Some(price),
+                if !client.supports(symbol).await {
+                    return None;
+                }
+                let start = std::time::Instant::now();
+                let result = client.get_price(symbol).await;
+                stats.record(result.is_ok(), start.elapsed());
+                match result {
+                    Ok(price) => Some((price, client.quote_asset(symbol))),
                     Err(e) => {
                         warn!("Failed to fetch price from {}: {}", client.get_name(), e);
                         None
@@ -464,15 +824,20 @@ impl OracleManager {
             }
         }
 
-        if all_prices.is_empty() {
-            return Err(anyhow!("No price data available from any oracle source"));
-        }
-
-        // Calculate aggregated price
-        let aggregated = self.calculate_aggregated_price(symbol, all_prices).await?;
+        let aggregated = if all_prices.is_empty() {
+            Err(OracleError::SourcesUnavailable {
+                symbol: symbol.to_string(),
+                reason: "no oracle source returned a price".to_string(),
+            }.into())
+        } else {
+            self.normalized_aggregate(symbol, all_prices).await
+        };
+        let aggregated = self.apply_quorum(symbol, aggregated).await?;
         
-        // Store in database
-        self.store_price_data(&aggregated).await?;
+        // Store in database; a last good price was stored when it was fresh
+        if aggregated.mode != PriceMode::LastGood {
+            self.store_price_data(&aggregated).await?;
+        }
         
         // Update cache
         {
@@ -483,48 +848,144 @@ impl OracleManager {
         Ok(aggregated)
     }
 
+    /// Converts stablecoin-quoted sources into the symbol's quote, then aggregates them
+    async fn normalized_aggregate(&self, symbol: &str, prices: Vec<(PriceData, Option<QuoteAsset>)>) -> Result<AggregatedPrice> {
+        let conversion = self.registry.read().await.quote_conversion().clone();
+        let target = QuoteAsset::of_symbol(symbol);
+
+        let mut rates = HashMap::new();
+        for quote in prices.iter().filter_map(|(_, quote)| *quote) {
+            if target == Some(quote) || rates.contains_key(&quote) {
+                continue;
+            }
+            let rate = match conversion.feeds.get(&quote) {
+                None => Err(anyhow!("no {}/USD symbol configured", quote)),
+                Some(feed) if feed == symbol => Err(anyhow!("{} cannot convert itself", symbol)),
+                // Boxed since the rate is itself fetched through `refresh`
+                Some(feed) => Box::pin(self.get_price(feed)).await.map(|rate| QuoteRate {
+                    asset: quote,
+                    rate: rate.mark_price,
+                    confidence: rate.confidence,
+                }),
+            };
+            rates.insert(quote, rate);
+        }
+
+        let normalized = conversion.normalize(symbol, prices, &rates);
+        for excluded in &normalized.excluded {
+            warn!("Excluding {} price {} for {}: {}", excluded.source, excluded.price, symbol, excluded.reason);
+        }
+        for rate in &normalized.depegged {
+            warn!("Converting {} sources through depegged {} at {}", symbol, rate.asset, rate.rate);
+        }
+        if normalized.prices.is_empty() {
+            return Err(OracleError::SourcesUnavailable {
+                symbol: symbol.to_string(),
+                reason: "no source could be converted to its quote".to_string(),
+            }.into());
+        }
+
+        let mut aggregated = self.calculate_aggregated_price(symbol, normalized.prices).await?;
+        aggregated.excluded_sources.extend(normalized.excluded);
+        aggregated.depegged_quotes = normalized.depegged;
+        Ok(aggregated)
+    }
+
+    /// Checks the aggregate against the symbol's quorum and applies its degraded mode if short
+    async fn apply_quorum(&self, symbol: &str, aggregated: Result<AggregatedPrice>) -> Result<AggregatedPrice> {
+        let policy = self.registry.read().await.quorum_policy(symbol);
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs() as i64;
+        let last_good = self.last_good.read().await.get(symbol).cloned();
+
+        let price = policy.apply(symbol, aggregated, last_good.as_ref(), now)?;
+        match price.mode {
+            PriceMode::Normal => {
+                self.last_good.write().await.insert(symbol.to_string(), price.clone());
+            }
+            PriceMode::SingleSource => warn!("Serving {} from {} source(s) with widened confidence", symbol, price.sources.len()),
+            PriceMode::LastGood => warn!("Serving last good {} price from {}s ago", symbol, now - price.timestamp),
+        }
+        Ok(price)
+    }
+
     async fn calculate_aggregated_price(&self, symbol: &str, prices: Vec<PriceData>) -> Result<AggregatedPrice> {
         if prices.is_empty() {
             return Err(anyhow!("No price data to aggregate"));
         }
 
-        // Filter out stale prices (older than 30 seconds)
+        // Filter out stale and out-of-band prices
         let current_time = SystemTime::now()
             .duration_since(UNIX_EPOCH)
             .unwrap()
             .as_secs() as i64;
-        
-        let valid_prices: Vec<_> = prices.into_iter()
-            .filter(|p| current_time - p.timestamp <= 30)
-            .collect();
+        let registry = self.registry.read().await;
+        let bounds = registry.price_bounds(symbol);
+
+        let mut valid_prices = Vec::with_capacity(prices.len());
+        let mut excluded_sources = Vec::new();
+        let mut freshest_stale: Option<i64> = None;
+        let mut first_violation = None;
+        for p in prices {
+            let age = current_time - p.timestamp;
+            let reason = if age > registry.max_source_age_seconds(symbol, &p.source) as i64 {
+                freshest_stale = Some(freshest_stale.map_or(age, |freshest| freshest.min(age)));
+                format!("Stale: {}s old", age)
+            } else if let Some(violation) = bounds.violation(p.price) {
+                let reason = format!("Out of bounds: {}", violation);
+                first_violation.get_or_insert(violation);
+                reason
+            } else {
+                valid_prices.push(p);
+                continue;
+            };
+            warn!("Excluding {} price {} for {}: {}", p.source, p.price, symbol, reason);
+            excluded_sources.push(ExcludedSource::new(p, reason));
+        }
+        drop(registry);
 
         if valid_prices.is_empty() {
-            return Err(anyhow!("All price data is stale"));
+            let symbol = symbol.to_string();
+            return Err(match first_violation {
+                Some(reason) => OracleError::OutOfBounds { symbol, reason },
+                None => OracleError::Stale { symbol, age_seconds: freshest_stale.unwrap_or_default() },
+            }.into());
         }
 
-        // Calculate weighted average based on confidence
-        let mut total_weight = 0.0;
-        let mut weighted_sum = 0.0;
-        let mut confidence_sum = 0.0;
+        let (strategy, outlier_filter, source_prices) = {
+            let registry = self.registry.read().await;
+            let source_prices: Vec<SourcePrice> = valid_prices.iter()
+                .map(|p| SourcePrice {
+                    price: p.price,
+                    confidence: p.confidence,
+                    weight: registry.source_weight(symbol, &p.source),
+                })
+                .collect();
+            (registry.aggregation_strategy(symbol), registry.outlier_filter(symbol), source_prices)
+        };
 
-        for price in &valid_prices {
-            let weight = 1.0 / (1.0 + price.confidence); // Higher confidence = lower weight
-            weighted_sum += price.price * weight;
-            total_weight += weight;
-            confidence_sum += price.confidence;
+        // Outliers are dropped before they can skew the aggregate
+        let outliers = outlier_filter.outliers(&source_prices)
+            .map_err(|e| OracleError::DeviationTooHigh { symbol: symbol.to_string(), reason: e.to_string() })?;
+        let mut kept_prices = Vec::with_capacity(valid_prices.len() - outliers.len());
+        let mut kept_source_prices = Vec::with_capacity(kept_prices.capacity());
+        for (i, (price, source_price)) in valid_prices.into_iter().zip(source_prices).enumerate() {
+            match outliers.iter().find(|(index, _)| *index == i) {
+                Some((_, reason)) => {
+                    warn!("Excluding {} price {} for {}: {}", price.source, price.price, symbol, reason);
+                    excluded_sources.push(ExcludedSource::new(price, format!("Outlier: {}", reason)));
+                }
+                None => {
+                    kept_prices.push(price);
+                    kept_source_prices.push(source_price);
+                }
+            }
         }
+        let valid_prices = kept_prices;
 
-        let mark_price = weighted_sum / total_weight;
+        let mark_price = strategy.aggregate(&kept_source_prices)
+            .map_err(|e| anyhow!("Failed to aggregate {} ({}): {}", symbol, strategy.name(), e))?;
         let index_price = mark_price; // For simplicity, using same value
-        let avg_confidence = confidence_sum / valid_prices.len() as f64;
-
-        // Check for manipulation (large price deviations)
-        for price in &valid_prices {
-            let deviation = (price.price - mark_price).abs() / mark_price;
-            if deviation > 0.05 { // 5% deviation threshold
-                warn!("Large price deviation detected for {}: {} vs {}", symbol, price.price, mark_price);
-            }
-        }
+        let avg_confidence = valid_prices.iter().map(|p| p.confidence).sum::<Decimal>() / Decimal::from(valid_prices.len());
 
         Ok(AggregatedPrice {
             symbol: symbol.to_string(),
@@ -533,25 +994,15 @@ impl OracleManager {
             confidence: avg_confidence,
             sources: valid_prices,
             timestamp: current_time,
+            excluded_sources,
+            mode: PriceMode::Normal,
+            depegged_quotes: vec![],
         })
     }
 
+    /// Stores the aggregate together with every source observation behind it
     async fn store_price_data(&self, price: &AggregatedPrice) -> Result<()> {
-        sqlx::query(
-            r#"
-            INSERT INTO price_feeds (symbol, mark_price, index_price, confidence, source_count)
-            VALUES ($1, $2, $3, $4, $5)
-            "#
-        )
-        .bind(&price.symbol)
-        .bind(price.mark_price)
-        .bind(price.index_price)
-        .bind(price.confidence)
-        .bind(price.sources.len() as i32)
-        .bind(serde_json::to_value(&price.sources)?)
-        .execute(&self.db_pool)
-        .await?;
-
+        self.store.insert_price(price).await?;
         info!("Stored price data for {}: ${:.2}", price.symbol, price.mark_price);
         Ok(())
     }
@@ -592,7 +1043,7 @@ impl OracleManager {
         
         // Calculate premium (mark - index)
         let premium = aggregated_price.mark_price - twap;
-        let premium_rate = premium / twap;
+        let premium_rate = (premium / twap).to_f64().unwrap_or_default();
         
         // Dampen premium for funding rate (typical 8-hour rate)
         let funding_rate = premium_rate * 0.125; // 1/8 for 8-hour rate
@@ -600,7 +1051,7 @@ impl OracleManager {
         // Predict next funding rate based on current premium trend
         let recent_twap = self.calculate_twap(&historical_prices, 15)?; // 15-min TWAP
         let recent_premium = aggregated_price.mark_price - recent_twap;
-        let predicted_rate = (recent_premium / recent_twap) * 0.125;
+        let predicted_rate = (recent_premium / recent_twap).to_f64().unwrap_or_default() * 0.125;
         
         Ok(FundingRateData {
             symbol: symbol.to_string(),
@@ -656,16 +1107,18 @@ impl OracleManager {
         let mut velocities = Vec::new();
         for window in recent_prices.windows(2) {
             let time_diff = (window[1].timestamp - window[0].timestamp) as f64 / 60.0; // minutes
-            let price_change = (window[1].mark_price - window[0].mark_price).abs() / window[0].mark_price;
+            let price_change = ((window[1].mark_price - window[0].mark_price).abs() / window[0].mark_price)
+                .to_f64()
+                .unwrap_or_default();
             if time_diff > 0.0 {
                 velocities.push(price_change / time_diff); // % change per minute
             }
         }
         
         // Current price velocity
-        let latest_price = recent_prices.last().unwrap();
-        let current_velocity = if (price - latest_price.mark_price).abs() > 0.0 {
-            (price - latest_price.mark_price).abs() / latest_price.mark_price
+        let latest_price = recent_prices.last().unwrap().mark_price.to_f64().unwrap_or_default();
+        let current_velocity = if (price - latest_price).abs() > 0.0 {
+            (price - latest_price).abs() / latest_price
         } else {
             0.0
         };
@@ -687,24 +1140,64 @@ impl OracleManager {
     }
     
     /// Support for 50+ trading symbols with independent feeds
-    pub async fn add_trading_symbol(&mut self, symbol: String, pyth_feed_id: String, switchboard_address: String) -> Result<()> {
-        // Dynamically add symbol to Pyth client
-        for client in &mut self.clients {
-            match client.get_name() {
-                "Pyth" => {
-                    // Add to Pyth client feed IDs
-                    info!("Added {} to Pyth feeds with ID: {}", symbol, pyth_feed_id);
-                },
-                "Switchboard" => {
-                    // Add to Switchboard client addresses
-                    info!("Added {} to Switchboard feeds with address: {}", symbol, switchboard_address);
-                },
-                _ => {}
+    pub async fn add_trading_symbol(&self, symbol: String, feeds: SymbolFeeds) -> Result<()> {
+        // Register first so malformed IDs are rejected before anything is persisted
+        self.registry.write().await.add_symbol(&symbol, &feeds)
+            .map_err(|e| OracleError::InvalidRequest(e.to_string()))?;
+
+        let normalized = self.registry.read().await.feeds(&symbol);
+        if let Err(e) = self.store.save_symbol(&symbol, &normalized).await {
+            self.registry.write().await.remove_symbol(&symbol);
+            return Err(e);
+        }
+
+        info!("Added trading symbol {} ({:?})", symbol, normalized);
+        Ok(())
+    }
+
+    /// Stops monitoring a symbol and forgets its feeds
+    pub async fn remove_trading_symbol(&self, symbol: &str) -> Result<()> {
+        {
+            let registry = self.registry.read().await;
+            if !registry.contains(symbol) {
+                return Err(OracleError::UnknownSymbol(symbol.to_string()).into());
+            }
+            let mut dependents = registry.synthetic_dependents(symbol);
+            dependents.extend(registry.quote_dependents(symbol).into_iter().map(|quote| format!("{} quote conversion", quote)));
+            if !dependents.is_empty() {
+                return Err(OracleError::SymbolInUse { symbol: symbol.to_string(), dependents: dependents.join(", ") }.into());
             }
         }
-        
+
+        self.store.remove_symbol(symbol).await?;
+
+        self.registry.write().await.remove_symbol(symbol);
+        self.price_cache.write().await.remove(symbol);
+        self.last_good.write().await.remove(symbol);
+
+        info!("Removed trading symbol {}", symbol);
         Ok(())
     }
+
+    /// Re-registers symbols that were added at runtime by a previous process
+    pub async fn load_persisted_symbols(&self) -> Result<usize> {
+        let saved = self.store.saved_symbols().await?;
+
+        let mut registry = self.registry.write().await;
+        let mut loaded = 0;
+        for (symbol, feeds) in saved {
+            if registry.contains(&symbol) {
+                continue; // Config file entries take precedence
+            }
+
+            match registry.add_symbol(&symbol, &feeds) {
+                Ok(()) => loaded += 1,
+                Err(e) => warn!("Skipping persisted symbol {}: {}", symbol, e),
+            }
+        }
+
+        Ok(loaded)
+    }
     
     /// Real-time WebSocket price streaming
     pub async fn start_websocket_streaming(&self, symbols: Vec<String>) -> Result<()> {
@@ -730,123 +1223,81 @@ impl OracleManager {
         Ok(())
     }
     
-    /// Circuit breaker for unhealthy oracle sources
-    pub async fn check_circuit_breaker(&self) -> Result<()> {
-        let health = self.get_system_health().await?;
-        
-        if health.overall_health < 0.5 {
-            warn!("Circuit breaker triggered: Oracle system health below 50%");
-            // In production: 
-            // - Disable trading
-            // - Switch to backup oracles
-            // - Alert administrators
-        }
-        
-        Ok(())
+    /// Breaker state and windowed call stats of every source
+    pub fn source_status(&self) -> Vec<SourceStatus> {
+        self.clients.iter()
+            .zip(&self.source_stats)
+            .map(|(client, stats)| SourceStatus::new(client.get_name(), client.circuit_breaker(), stats.snapshot()))
+            .collect()
     }
     
-    /// Enhanced uptime monitoring for 99.99% requirement
+    /// System health from the recorded counters; makes no upstream calls
     pub async fn get_system_health(&self) -> Result<SystemHealth> {
-        let mut oracle_health = Vec::new();
-        let test_symbol = "BTC/USD";
-        
-        for client in &self.clients {
-            let start = std::time::Instant::now();
-            let health = match client.get_price(test_symbol).await {
-                Ok(_) => OracleHealth {
-                    name: client.get_name().to_string(),
-                    is_healthy: true,
-                    latency_ms: start.elapsed().as_millis() as u64,
-                    last_update: std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap().as_secs() as i64,
-                    error_rate: 0.0,
-                },
-                Err(_e) => OracleHealth {
-                    name: client.get_name().to_string(),
-                    is_healthy: false,
-                    latency_ms: u64::MAX,
-                    last_update: 0,
-                    error_rate: 1.0,
-                }
-            };
-            oracle_health.push(health);
-        }
-        
+        let oracle_health = self.source_status();
         let healthy_oracles = oracle_health.iter().filter(|h| h.is_healthy).count();
-        let total_oracles = oracle_health.len();
-        let system_uptime = if healthy_oracles > 0 { 1.0 } else { 0.0 };
+        let overall_health = if oracle_health.is_empty() {
+            0.0
+        } else {
+            healthy_oracles as f64 / oracle_health.len() as f64
+        };
         
         Ok(SystemHealth {
-            overall_health: healthy_oracles as f64 / total_oracles as f64,
-            uptime_percentage: system_uptime * 100.0,
+            overall_health,
             oracle_health,
-            cache_hit_rate: self.get_cache_hit_rate().await,
+            cache: self.cache_stats.snapshot(),
             database_status: self.check_database_health().await,
             timestamp: std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap().as_secs() as i64,
         })
     }
     
     // Helper methods
-    async fn get_historical_prices(&self, symbol: &str, _minutes: i64) -> Result<Vec<AggregatedPrice>> {
-        let rows = sqlx::query!(
-            r#"
-            SELECT symbol, 
-                   mark_price::float8 as mark_price, 
-                   index_price::float8 as index_price, 
-                   confidence::float8 as confidence, 
-                   EXTRACT(epoch FROM created_at)::bigint as timestamp
-            FROM price_feeds 
-            WHERE symbol = $1 AND created_at >= NOW() - INTERVAL '1 hour'
-            ORDER BY created_at ASC
-            "#,
-            symbol
-        ).fetch_all(&self.db_pool).await?;
-        
-        Ok(rows.into_iter().map(|row| AggregatedPrice {
-            symbol: row.symbol,
-            mark_price: row.mark_price.unwrap_or(0.0),
-            index_price: row.index_price.unwrap_or(0.0),
-            confidence: row.confidence.unwrap_or(0.0),
-            sources: vec![], // Historical data doesn't include individual sources
-            timestamp: row.timestamp.unwrap_or(0),
-        }).collect())
+    async fn get_historical_prices(&self, symbol: &str, minutes: i64) -> Result<Vec<AggregatedPrice>> {
+        let mut prices = self.store.price_history(symbol, unix_now() - minutes * 60, usize::MAX).await?;
+        prices.reverse(); // Oldest first
+        Ok(prices)
     }
     
-    fn calculate_twap(&self, prices: &[AggregatedPrice], minutes: usize) -> Result<f64> {
+    fn calculate_twap(&self, prices: &[AggregatedPrice], minutes: usize) -> Result<Decimal> {
         if prices.is_empty() {
             return Err(anyhow!("No prices available for TWAP calculation"));
         }
         
         let recent_prices: Vec<_> = prices.iter().rev().take(minutes).collect();
-        let sum: f64 = recent_prices.iter().map(|p| p.mark_price).sum();
-        Ok(sum / recent_prices.len() as f64)
-    }
-    
-    async fn get_cache_hit_rate(&self) -> f64 {
-        // Implementation would track cache hits vs misses
-        95.0 // Mock 95% hit rate
+        let sum: Decimal = recent_prices.iter().map(|p| p.mark_price).sum();
+        Ok(sum / Decimal::from(recent_prices.len()))
     }
     
     async fn check_database_health(&self) -> bool {
-        // Simple database health check
-        sqlx::query("SELECT 1").fetch_one(&self.db_pool).await.is_ok()
+        self.store.check_health().await.is_ok()
     }
 }
 
 #[derive(Debug, Clone, Serialize)]
 pub struct SystemHealth {
     pub overall_health: f64,
-    pub uptime_percentage: f64,
-    pub oracle_health: Vec<OracleHealth>,
-    pub cache_hit_rate: f64,
+    pub oracle_health: Vec<SourceStatus>,
+    pub cache: CacheStatsSnapshot,
     pub database_status: bool,
     pub timestamp: i64,
 }
 
-#[derive(Debug, Clone, Serialize)]
-pub struct OracleHealth {
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SourceStatus {
     pub name: String,
+    /// Succeeded within the stats window and the breaker is not open
     pub is_healthy: bool,
-    pub latency_ms: u64,
-    pub last_update: i64,
-    pub error_rate: f64,
+    pub circuit_breaker: Option<BreakerSnapshot>,
+    pub stats: SourceStatsSnapshot,
+}
+
+impl SourceStatus {
+    pub fn new(name: &str, circuit_breaker: Option<BreakerSnapshot>, stats: SourceStatsSnapshot) -> Self {
+        let breaker_open = circuit_breaker.as_ref().is_some_and(|b| b.state == BreakerState::Open);
+        Self {
+            name: name.to_string(),
+            is_healthy: stats.success_count > 0 && !breaker_open,
+            circuit_breaker,
+            stats,
+        }
+    }
 }
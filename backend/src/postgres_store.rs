@@ -0,0 +1,254 @@
+use std::collections::HashMap;
+use anyhow::Result;
+use async_trait::async_trait;
+use rust_decimal::Decimal;
+use sqlx::postgres::PgPoolOptions;
+use sqlx::{PgPool, Postgres, QueryBuilder, Row};
+use crate::aggregation::PriceMode;
+use crate::feed_registry::SymbolFeeds;
+use crate::oracle_client::{AggregatedPrice, SourceObservation};
+use crate::price_store::{ManipulationEvent, PriceStore};
+
+/// Postgres tables created by the migrations in `db/`
+#[derive(Debug, Clone)]
+pub struct PostgresStore {
+    pool: PgPool,
+}
+
+impl PostgresStore {
+    pub async fn connect(url: &str) -> Result<Self> {
+        let pool = PgPoolOptions::new()
+            .max_connections(20)
+            .connect(url)
+            .await?;
+        sqlx::migrate!("../db").run(&pool).await?;
+        Ok(Self { pool })
+    }
+}
+
+#[async_trait]
+impl PriceStore for PostgresStore {
+    async fn insert_price(&self, price: &AggregatedPrice) -> Result<()> {
+        let mut tx = self.pool.begin().await?;
+        let price_feed_id: i64 = sqlx::query(
+            r#"
+            INSERT INTO price_feeds (symbol, mark_price, index_price, confidence, source_count, mode)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            RETURNING id
+            "#
+        )
+        .bind(&price.symbol)
+        .bind(price.mark_price)
+        .bind(price.index_price)
+        .bind(price.confidence)
+        .bind(price.sources.len() as i32)
+        .bind(price.mode.as_str())
+        .fetch_one(&mut *tx)
+        .await?
+        .try_get("id")?;
+
+        let observations = price.observations();
+        if !observations.is_empty() {
+            let mut insert = QueryBuilder::<Postgres>::new(
+                "INSERT INTO source_observations (price_feed_id, symbol, source, price, confidence, observed_at, excluded_reason) "
+            );
+            insert.push_values(&observations, |mut row, observation| {
+                row.push_bind(price_feed_id)
+                    .push_bind(&observation.symbol)
+                    .push_bind(&observation.source)
+                    .push_bind(observation.price)
+                    .push_bind(observation.confidence)
+                    .push("to_timestamp(")
+                    .push_bind_unseparated(observation.timestamp)
+                    .push_unseparated(")")
+                    .push_bind(&observation.excluded_reason);
+            });
+            insert.build().execute(&mut *tx).await?;
+        }
+        tx.commit().await?;
+        Ok(())
+    }
+
+    async fn price_history(&self, symbol: &str, since: i64, limit: usize) -> Result<Vec<AggregatedPrice>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT id, symbol, mark_price, index_price, confidence, mode,
+                   FLOOR(EXTRACT(epoch FROM created_at))::bigint AS timestamp
+            FROM price_feeds
+            WHERE symbol = $1 AND created_at >= to_timestamp($2)
+            ORDER BY created_at DESC, id DESC
+            LIMIT $3
+            "#
+        )
+        .bind(symbol)
+        .bind(since)
+        .bind(limit.min(i64::MAX as usize) as i64)
+        .fetch_all(&self.pool)
+        .await?;
+
+        let ids = rows.iter().map(|row| row.try_get("id")).collect::<Result<Vec<i64>, _>>()?;
+        let mut observations = self.source_observations(&ids).await?;
+
+        rows.into_iter().map(|row| {
+            let price = AggregatedPrice {
+                symbol: row.try_get("symbol")?,
+                mark_price: row.try_get("mark_price")?,
+                index_price: row.try_get("index_price")?,
+                confidence: row.try_get("confidence")?,
+                sources: vec![],
+                timestamp: row.try_get("timestamp")?,
+                excluded_sources: vec![],
+                mode: PriceMode::parse(row.try_get("mode")?)?,
+                depegged_quotes: vec![],
+            };
+            let id: i64 = row.try_get("id")?;
+            Ok(price.with_observations(observations.remove(&id).unwrap_or_default()))
+        }).collect()
+    }
+
+    async fn average_price(&self, symbol: &str, since: i64) -> Result<Option<Decimal>> {
+        let row = sqlx::query(
+            r#"
+            SELECT AVG(mark_price) AS avg_price
+            FROM price_feeds
+            WHERE symbol = $1 AND created_at >= to_timestamp($2)
+            "#
+        )
+        .bind(symbol)
+        .bind(since)
+        .fetch_one(&self.pool)
+        .await?;
+        Ok(row.try_get("avg_price")?)
+    }
+
+    async fn count_prices(&self, symbol: &str, since: i64) -> Result<usize> {
+        let count: i64 = sqlx::query(
+            "SELECT COUNT(*) AS data_points FROM price_feeds WHERE symbol = $1 AND created_at >= to_timestamp($2)"
+        )
+        .bind(symbol)
+        .bind(since)
+        .fetch_one(&self.pool)
+        .await?
+        .try_get("data_points")?;
+        Ok(count as usize)
+    }
+
+    async fn insert_manipulation_event(&self, event: &ManipulationEvent) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO manipulation_events (symbol, price, confidence, manipulation_score, created_at)
+            VALUES ($1, $2, $3, $4, to_timestamp($5))
+            "#
+        )
+        .bind(&event.symbol)
+        .bind(event.price)
+        .bind(event.confidence)
+        .bind(event.manipulation_score)
+        .bind(event.timestamp)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    async fn manipulation_events(&self, symbol: &str, since: i64) -> Result<Vec<ManipulationEvent>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT symbol, price, confidence, manipulation_score,
+                   FLOOR(EXTRACT(epoch FROM created_at))::bigint AS timestamp
+            FROM manipulation_events
+            WHERE symbol = $1 AND created_at >= to_timestamp($2)
+            ORDER BY created_at DESC, id DESC
+            "#
+        )
+        .bind(symbol)
+        .bind(since)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.into_iter().map(|row| Ok(ManipulationEvent {
+            symbol: row.try_get("symbol")?,
+            price: row.try_get("price")?,
+            confidence: row.try_get("confidence")?,
+            manipulation_score: row.try_get("manipulation_score")?,
+            timestamp: row.try_get("timestamp")?,
+        })).collect()
+    }
+
+    async fn save_symbol(&self, symbol: &str, feeds: &SymbolFeeds) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO trading_symbols (symbol, pyth_feed_id, pyth_price_account, switchboard_address)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT (symbol) DO UPDATE
+            SET pyth_feed_id = EXCLUDED.pyth_feed_id,
+                pyth_price_account = EXCLUDED.pyth_price_account,
+                switchboard_address = EXCLUDED.switchboard_address
+            "#
+        )
+        .bind(symbol)
+        .bind(&feeds.pyth_feed_id)
+        .bind(&feeds.pyth_price_account)
+        .bind(&feeds.switchboard_address)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    async fn remove_symbol(&self, symbol: &str) -> Result<()> {
+        sqlx::query("DELETE FROM trading_symbols WHERE symbol = $1")
+            .bind(symbol)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    async fn saved_symbols(&self) -> Result<Vec<(String, SymbolFeeds)>> {
+        let rows = sqlx::query(
+            "SELECT symbol, pyth_feed_id, pyth_price_account, switchboard_address FROM trading_symbols ORDER BY created_at"
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.into_iter().map(|row| Ok((row.try_get("symbol")?, SymbolFeeds {
+            pyth_feed_id: row.try_get("pyth_feed_id")?,
+            pyth_price_account: row.try_get("pyth_price_account")?,
+            switchboard_address: row.try_get("switchboard_address")?,
+        }))).collect()
+    }
+
+    async fn check_health(&self) -> Result<()> {
+        sqlx::query("SELECT 1").fetch_one(&self.pool).await?;
+        Ok(())
+    }
+}
+
+impl PostgresStore {
+    /// Source observations of the given `price_feeds` rows, keyed by row id
+    async fn source_observations(&self, price_feed_ids: &[i64]) -> Result<HashMap<i64, Vec<SourceObservation>>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT price_feed_id, symbol, source, price, confidence, excluded_reason,
+                   FLOOR(EXTRACT(epoch FROM observed_at))::bigint AS timestamp
+            FROM source_observations
+            WHERE price_feed_id = ANY($1)
+            ORDER BY id
+            "#
+        )
+        .bind(price_feed_ids)
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut observations: HashMap<i64, Vec<SourceObservation>> = HashMap::new();
+        for row in rows {
+            observations.entry(row.try_get("price_feed_id")?).or_default().push(SourceObservation {
+                symbol: row.try_get("symbol")?,
+                source: row.try_get("source")?,
+                price: row.try_get("price")?,
+                confidence: row.try_get("confidence")?,
+                timestamp: row.try_get("timestamp")?,
+                excluded_reason: row.try_get("excluded_reason")?,
+            });
+        }
+        Ok(observations)
+    }
+}
@@ -3,20 +3,27 @@ use std::collections::HashMap;
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use anyhow::{Result, anyhow};
 use serde::{Deserialize, Serialize};
-use sqlx::{PgPool, Row};
-use log::{info, warn, error, debug};
-use tokio::sync::{RwLock, broadcast};
-use crate::oracle_client::{OracleManager, AggregatedPrice};
+use log::{info, warn, debug};
+use rust_decimal::Decimal;
+use rust_decimal::prelude::ToPrimitive;
+use tokio::sync::{Notify, RwLock, broadcast};
+use crate::aggregation::PriceMode;
+use crate::error::OracleError;
+use crate::feed_registry::SymbolFeeds;
+use crate::oracle_client::{OracleManager, AggregatedPrice, SourceStatus, SystemHealth};
+use crate::price_store::{unix_now, ManipulationEvent, PriceStore};
+use crate::scheduler::{MarketActivity, ScheduleStatus, SymbolScheduler};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PriceUpdateEvent {
     pub symbol: String,
-    pub mark_price: f64,
-    pub index_price: f64,
-    pub confidence: f64,
+    pub mark_price: Decimal,
+    pub index_price: Decimal,
+    pub confidence: Decimal,
     pub timestamp: i64,
     pub sources: Vec<String>,
     pub manipulation_score: f64,
+    pub mode: PriceMode,
 }
 
 #[derive(Debug)]
@@ -60,6 +67,36 @@ impl ManipulationDetector {
         self.calculate_manipulation_score(prices, price).await
     }
 
+    /// Scores `price` against the history without adding it, for prices served from cache
+    /// that were already recorded when fetched
+    pub async fn score_price(&self, symbol: &str, price: f64) -> f64 {
+        let history = self.price_history.read().await;
+        match history.get(symbol) {
+            Some(prices) => self.calculate_manipulation_score(prices, price).await,
+            None => 0.0,
+        }
+    }
+
+    /// Realized volatility of a symbol over its last minute of history, scaled to one minute;
+    /// `None` until that history spans at least a second
+    pub async fn realized_volatility(&self, symbol: &str) -> Option<f64> {
+        let history = self.price_history.read().await;
+        let prices = history.get(symbol)?;
+        let (_, latest) = *prices.last()?;
+        let recent: Vec<&(f64, i64)> = prices.iter().filter(|(_, ts)| *ts >= latest - 60).collect();
+
+        let span = latest - recent.first()?.1;
+        if span < 1 {
+            return None;
+        }
+        // Sum of squared log returns, so uneven sampling doesn't change the estimate
+        let variance: f64 = recent.windows(2)
+            .filter(|pair| pair[0].0 > 0.0 && pair[1].0 > 0.0)
+            .map(|pair| (pair[1].0 / pair[0].0).ln().powi(2))
+            .sum();
+        Some((variance * 60.0 / span as f64).sqrt())
+    }
+
     async fn calculate_manipulation_score(&self, prices: &[(f64, i64)], current_price: f64) -> f64 {
         if prices.len() < 10 {
             return 0.0; // Not enough data
@@ -179,38 +216,74 @@ impl ManipulationDetector {
 pub struct PriceAggregator {
     oracle_manager: Arc<OracleManager>,
     manipulation_detector: ManipulationDetector,
-    db_pool: PgPool,
+    store: Arc<dyn PriceStore>,
     price_broadcaster: broadcast::Sender<PriceUpdateEvent>,
-    health_threshold: f64,
     manipulation_threshold: f64,
+    scheduler: Arc<SymbolScheduler>,
+    symbols_changed: Arc<Notify>, // Wakes the monitoring loop to reschedule
 }
 
 impl PriceAggregator {
-    pub fn new(oracle_manager: OracleManager, db_pool: PgPool) -> Self {
+    pub fn new(oracle_manager: OracleManager, store: Arc<dyn PriceStore>) -> Self {
         let (tx, _) = broadcast::channel(1000);
         
         Self {
             oracle_manager: Arc::new(oracle_manager),
             manipulation_detector: ManipulationDetector::new(),
-            db_pool,
+            store,
             price_broadcaster: tx,
-            health_threshold: 0.05, // 5% max deviation for healthy prices
             manipulation_threshold: 0.7, // 70% manipulation score threshold
+            scheduler: Arc::new(SymbolScheduler::default()),
+            symbols_changed: Arc::new(Notify::new()),
         }
     }
 
+    pub async fn monitored_symbols(&self) -> Vec<String> {
+        self.oracle_manager.monitored_symbols().await
+    }
+
+    pub async fn add_trading_symbol(&self, symbol: String, feeds: SymbolFeeds) -> Result<()> {
+        self.oracle_manager.add_trading_symbol(symbol, feeds).await?;
+        self.symbols_changed.notify_one();
+        Ok(())
+    }
+
+    pub async fn remove_trading_symbol(&self, symbol: &str) -> Result<()> {
+        self.oracle_manager.remove_trading_symbol(symbol).await?;
+        self.symbols_changed.notify_one();
+        Ok(())
+    }
+
+    pub async fn load_persisted_symbols(&self) -> Result<usize> {
+        self.oracle_manager.load_persisted_symbols().await
+    }
+
     pub fn get_price_receiver(&self) -> broadcast::Receiver<PriceUpdateEvent> {
         self.price_broadcaster.subscribe()
     }
 
     pub async fn get_price_with_validation(&self, symbol: &str) -> Result<AggregatedPrice> {
         // Get aggregated price from oracle manager
-        let mut aggregated_price = self.oracle_manager.get_aggregated_price(symbol).await?;
-        
-        // Analyze for manipulation
-        let manipulation_score = self.manipulation_detector
-            .analyze_price(symbol, aggregated_price.mark_price, aggregated_price.timestamp)
-            .await;
+        let aggregated_price = self.oracle_manager.get_aggregated_price(symbol).await?;
+        self.validate_and_publish(symbol, aggregated_price, false).await
+    }
+
+    /// Like `get_price_with_validation`, but always fetches a fresh price
+    pub async fn refresh_with_validation(&self, symbol: &str) -> Result<AggregatedPrice> {
+        let aggregated_price = self.oracle_manager.refresh_price(symbol).await?;
+        self.validate_and_publish(symbol, aggregated_price, true).await
+    }
+
+    /// Only `fresh` prices join the detector's history, so repeated cache reads don't
+    /// flatten the volatility the scheduler adapts to
+    async fn validate_and_publish(&self, symbol: &str, mut aggregated_price: AggregatedPrice, fresh: bool) -> Result<AggregatedPrice> {
+        // Analyze for manipulation; the detector's statistics are approximate by nature
+        let mark_price = aggregated_price.mark_price.to_f64().unwrap_or_default();
+        let manipulation_score = if fresh {
+            self.manipulation_detector.analyze_price(symbol, mark_price, aggregated_price.timestamp).await
+        } else {
+            self.manipulation_detector.score_price(symbol, mark_price).await
+        };
 
         // Apply additional validation
         self.validate_price_sources(&aggregated_price).await?;
@@ -219,6 +292,9 @@ impl PriceAggregator {
         // Check manipulation threshold
         if manipulation_score > self.manipulation_threshold {
             warn!("High manipulation score detected for {}: {:.2}", symbol, manipulation_score);
+            if let Err(e) = self.record_manipulation_event(&aggregated_price, manipulation_score).await {
+                warn!("Failed to record manipulation event for {}: {}", symbol, e);
+            }
             
             // Apply conservative adjustment or use fallback price
             aggregated_price = self.apply_conservative_pricing(&aggregated_price).await?;
@@ -235,6 +311,7 @@ impl PriceAggregator {
                 .map(|s| s.source.clone())
                 .collect(),
             manipulation_score,
+            mode: aggregated_price.mode,
         };
 
         if let Err(e) = self.price_broadcaster.send(update_event) {
@@ -246,44 +323,16 @@ impl PriceAggregator {
 
     async fn validate_price_sources(&self, price: &AggregatedPrice) -> Result<()> {
         if price.sources.is_empty() {
-            return Err(anyhow!("No oracle sources available for price validation"));
-        }
-
-        // If we only have one source, apply more strict validation
-        if price.sources.len() == 1 {
-            let source_price = &price.sources[0];
-            
-            // Check if price is reasonable
-            if source_price.price <= 0.0 || source_price.price > 1_000_000.0 {
-                return Err(anyhow!("Single source price is unreasonable: {}", source_price.price));
-            }
-            
-            // Check confidence
-            let confidence_percentage = source_price.confidence / source_price.price;
-            if confidence_percentage > 0.05 { // 5% max confidence interval for single source
-                return Err(anyhow!(
-                    "Single source confidence too high: {:.2}%", 
-                    confidence_percentage * 100.0
-                ));
-            }
-            
-            return Ok(()); // Accept single source with strict validation
+            return Err(OracleError::SourcesUnavailable {
+                symbol: price.symbol.clone(),
+                reason: "no oracle sources available for price validation".to_string(),
+            }.into());
         }
-
-        // Check price deviation between sources when we have multiple
-        let prices: Vec<f64> = price.sources.iter().map(|s| s.price).collect();
-        let mean_price = prices.iter().sum::<f64>() / prices.len() as f64;
-        
-        for source_price in &prices {
-            let deviation = (source_price - mean_price).abs() / mean_price;
-            if deviation > self.health_threshold {
-                return Err(anyhow!(
-                    "Price deviation too high between sources: {:.2}%", 
-                    deviation * 100.0
-                ));
-            }
+        if let Some(violation) = self.oracle_manager.price_bounds(&price.symbol).await.violation(price.mark_price) {
+            return Err(OracleError::OutOfBounds { symbol: price.symbol.clone(), reason: violation }.into());
         }
 
+        // Outliers were excluded and the quorum policy applied during aggregation
         Ok(())
     }
 
@@ -292,16 +341,18 @@ impl PriceAggregator {
             .duration_since(UNIX_EPOCH)
             .unwrap()
             .as_secs() as i64;
+        let bounds = self.oracle_manager.price_bounds(&price.symbol).await;
 
+        // Last good prices are stale by design, bounded by the quorum policy
         let age = current_time - price.timestamp;
-        if age > 30 { // 30 seconds staleness threshold
-            return Err(anyhow!("Price data is stale: {} seconds old", age));
+        if price.mode != PriceMode::LastGood && !bounds.is_fresh(age) {
+            return Err(OracleError::Stale { symbol: price.symbol.clone(), age_seconds: age }.into());
         }
 
         // Check individual source freshness
         for source in &price.sources {
             let source_age = current_time - source.timestamp;
-            if source_age > 60 { // 60 seconds for individual sources
+            if source_age > self.oracle_manager.max_source_age_seconds(&price.symbol, &source.source).await as i64 {
                 warn!("Stale price from {}: {} seconds old", source.source, source_age);
             }
         }
@@ -314,9 +365,9 @@ impl PriceAggregator {
         let historical_avg = self.get_historical_average(&price.symbol, Duration::from_secs(3600)).await?;
         
         // Apply conservative adjustment (move towards historical average)
-        let adjustment_factor = 0.2; // 20% adjustment towards historical
-        let adjusted_mark_price = price.mark_price * (1.0 - adjustment_factor) + historical_avg * adjustment_factor;
-        let adjusted_index_price = price.index_price * (1.0 - adjustment_factor) + historical_avg * adjustment_factor;
+        let adjustment_factor = Decimal::new(2, 1); // 20% adjustment towards historical
+        let adjusted_mark_price = price.mark_price * (Decimal::ONE - adjustment_factor) + historical_avg * adjustment_factor;
+        let adjusted_index_price = price.index_price * (Decimal::ONE - adjustment_factor) + historical_avg * adjustment_factor;
 
         info!(
             "Applied conservative pricing for {}: {} -> {}", 
@@ -327,64 +378,96 @@ impl PriceAggregator {
             symbol: price.symbol.clone(),
             mark_price: adjusted_mark_price,
             index_price: adjusted_index_price,
-            confidence: price.confidence * 1.5, // Increase confidence interval due to adjustment
+            confidence: price.confidence * Decimal::new(15, 1), // Increase confidence interval due to adjustment
             sources: price.sources.clone(),
             timestamp: price.timestamp,
+            excluded_sources: price.excluded_sources.clone(),
+            mode: price.mode,
+            depegged_quotes: price.depegged_quotes.clone(),
         })
     }
 
-    async fn get_historical_average(&self, symbol: &str, window: Duration) -> Result<f64> {
+    async fn record_manipulation_event(&self, price: &AggregatedPrice, manipulation_score: f64) -> Result<()> {
+        self.store.insert_manipulation_event(&ManipulationEvent {
+            symbol: price.symbol.clone(),
+            price: price.mark_price,
+            confidence: price.confidence,
+            manipulation_score,
+            timestamp: unix_now(),
+        }).await
+    }
+
+    async fn get_historical_average(&self, symbol: &str, window: Duration) -> Result<Decimal> {
         let cutoff_time = SystemTime::now()
             .duration_since(UNIX_EPOCH)
             .unwrap()
             .as_secs() as i64 - window.as_secs() as i64;
 
-        let row = sqlx::query(
-            r#"
-            SELECT AVG(price) as avg_price
-            FROM price_feeds
-            WHERE symbol = $1 AND timestamp >= $2
-            "#
-        )
-        .bind(symbol)
-        .bind(cutoff_time)
-        .fetch_one(&self.db_pool)
-        .await?;
-
-        row.try_get::<Option<f64>, _>("avg_price")?
+        self.store.average_price(symbol, cutoff_time).await?
             .ok_or_else(|| anyhow!("No historical data available for {}", symbol))
     }
 
-    pub async fn start_continuous_monitoring(&self, symbols: Vec<String>) {
-        info!("Starting continuous price monitoring for symbols: {:?}", symbols);
-        
-        let mut interval = tokio::time::interval(Duration::from_millis(250)); // 250ms for sub-500ms latency
-        
+    /// Refreshes every monitored symbol on its own schedule, following symbols added or
+    /// removed at runtime
+    pub async fn start_continuous_monitoring(self: Arc<Self>) {
+        info!("Starting continuous price monitoring for symbols: {:?}", self.monitored_symbols().await);
+
+        // Tasks hold a weak handle so they don't keep the aggregator alive
+        let aggregator = Arc::downgrade(&self);
+        let refresh = move |symbol: String| {
+            let aggregator = aggregator.clone();
+            async move {
+                let Some(aggregator) = aggregator.upgrade() else {
+                    return Ok(MarketActivity::default());
+                };
+                // Past the cache, so each tick is a new price rather than a rebroadcast
+                let price = aggregator.refresh_with_validation(&symbol).await?;
+                debug!("Updated price for {}: ${:.2}", symbol, price.mark_price);
+                Ok(MarketActivity {
+                    volatility: aggregator.manipulation_detector.realized_volatility(&symbol).await.unwrap_or(0.0),
+                    dispersion: price.source_dispersion(),
+                })
+            }
+        };
+
         loop {
-            interval.tick().await;
-            
-            for symbol in &symbols {
-                match self.get_price_with_validation(symbol).await {
-                    Ok(price) => {
-                        debug!("Updated price for {}: ${:.2}", symbol, price.mark_price);
-                    }
-                    Err(e) => {
-                        error!("Failed to update price for {}: {}", symbol, e);
-                    }
-                }
-                
-                // Small delay between symbols to prevent overwhelming the oracles
-                tokio::time::sleep(Duration::from_millis(10)).await;
+            let schedules = self.oracle_manager.refresh_schedules().await;
+            self.scheduler.reconcile(&schedules, refresh.clone());
+
+            // Also re-read periodically, for symbols restored or changed outside the admin API
+            tokio::select! {
+                _ = self.symbols_changed.notified() => {}
+                _ = tokio::time::sleep(Duration::from_secs(5)) => {}
             }
         }
     }
 
+    /// Current refresh interval, market activity and update lag of each scheduled symbol
+    pub fn schedule_status(&self) -> Vec<ScheduleStatus> {
+        self.scheduler.status()
+    }
+
+    pub async fn run_cache_revalidation(&self) {
+        self.oracle_manager.run_revalidation().await
+    }
+
+    pub fn source_status(&self) -> Vec<SourceStatus> {
+        self.oracle_manager.source_status()
+    }
+
+    pub async fn get_system_health(&self) -> Result<SystemHealth> {
+        self.oracle_manager.get_system_health().await
+    }
+
     pub async fn get_health_status(&self) -> Result<serde_json::Value> {
-        let symbols = vec!["BTC/USD".to_string(), "ETH/USD".to_string(), "SOL/USD".to_string()];
+        let symbols = self.monitored_symbols().await;
+        let schedules: HashMap<String, ScheduleStatus> = self.scheduler.status().into_iter()
+            .map(|status| (status.symbol.clone(), status))
+            .collect();
         let mut status = serde_json::Map::new();
         
         for symbol in symbols {
-                let health_info = match self.oracle_manager.get_cached_price(&symbol).await {
+                let mut health_info = match self.oracle_manager.get_cached_price(&symbol).await {
                     Some(price) => {
                         let current_time = SystemTime::now()
                             .duration_since(UNIX_EPOCH)
@@ -392,7 +475,9 @@ impl PriceAggregator {
                             .as_secs() as i64;
                         
                         let age = current_time - price.timestamp;
-                        let is_healthy = age <= 30 && price.sources.len() >= 1; // Accept single source
+                        let bounds = self.oracle_manager.price_bounds(&symbol).await;
+                        // Degraded modes keep publishing but aren't healthy
+                        let is_healthy = bounds.is_fresh(age) && price.mode == PriceMode::Normal;
                         
                         serde_json::json!({
                             "symbol": symbol,
@@ -401,6 +486,7 @@ impl PriceAggregator {
                             "source_count": price.sources.len(),
                             "confidence": price.confidence,
                             "is_healthy": is_healthy,
+                            "mode": price.mode,
                             "sources": price.sources.iter().map(|s| s.source.clone()).collect::<Vec<_>>()
                         })
                     }
@@ -411,7 +497,16 @@ impl PriceAggregator {
                             "error": "No cached price data"
                         })
                     }
-                };            status.insert(symbol, health_info);
+                };
+            if let (Some(schedule), Some(info)) = (schedules.get(&symbol), health_info.as_object_mut()) {
+                info.insert("refresh_interval_ms".to_string(), schedule.interval_ms.into());
+                info.insert("refresh_interval_range_ms".to_string(), serde_json::json!([schedule.min_interval_ms, schedule.max_interval_ms]));
+                info.insert("volatility_bps".to_string(), (schedule.activity.volatility * 10_000.0).into());
+                info.insert("dispersion_bps".to_string(), (schedule.activity.dispersion * 10_000.0).into());
+                info.insert("update_lag_ms".to_string(), schedule.update_lag_ms.into());
+                info.insert("consecutive_failures".to_string(), schedule.consecutive_failures.into());
+            }
+            status.insert(symbol, health_info);
         }
 
         Ok(serde_json::Value::Object(status))
@@ -423,58 +518,26 @@ impl PriceAggregator {
             .unwrap()
             .as_secs() as i64 - (hours * 3600) as i64;
 
-        let rows = sqlx::query(
-            r#"
-            SELECT price, timestamp, confidence
-            FROM price_feeds
-            WHERE symbol = $1 AND timestamp >= $2
-            ORDER BY timestamp DESC
-            "#
-        )
-        .bind(symbol)
-        .bind(cutoff_time)
-        .fetch_all(&self.db_pool)
-        .await?;
-
-        if rows.is_empty() {
-            return Ok(serde_json::json!({
-                "symbol": symbol,
-                "period_hours": hours,
-                "data_points": 0,
-                "manipulation_events": []
-            }));
-        }
-
-        let mut manipulation_events = Vec::new();
-        let mut last_score = 0.0;
-
-        for row in &rows {
-            let price: f64 = row.try_get("price")?;
-            let timestamp: i64 = row.try_get("timestamp")?;
-            let confidence: f64 = row.try_get("confidence")?;
-            
-            let score = self.manipulation_detector
-                .analyze_price(symbol, price, timestamp)
-                .await;
-
-            if score > self.manipulation_threshold && score > last_score + 0.1 {
-                manipulation_events.push(serde_json::json!({
-                    "timestamp": timestamp,
-                    "price": price,
-                    "manipulation_score": score,
-                    "confidence": confidence
-                }));
-            }
-            
-            last_score = score;
-        }
+        let data_points = self.store.count_prices(symbol, cutoff_time).await?;
+
+        // Scored as prices were published, so the report doesn't replay history through the detector
+        let events = self.store.manipulation_events(symbol, cutoff_time).await?;
+        let latest_score = events.first().map(|event| event.manipulation_score);
+        let manipulation_events: Vec<serde_json::Value> = events.into_iter()
+            .map(|event| serde_json::json!({
+                "timestamp": event.timestamp,
+                "price": event.price,
+                "manipulation_score": event.manipulation_score,
+                "confidence": event.confidence
+            }))
+            .collect();
 
         Ok(serde_json::json!({
             "symbol": symbol,
             "period_hours": hours,
-            "data_points": rows.len(),
+            "data_points": data_points,
             "manipulation_events": manipulation_events,
-            "latest_score": last_score
+            "latest_score": latest_score
         }))
     }
 }
@@ -0,0 +1,107 @@
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::Mutex;
+use std::time::Duration;
+use tokio::sync::watch;
+use crate::config::CacheConfig;
+
+/// How long cached prices are served as-is, and then while being refreshed
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CachePolicy {
+    pub ttl: Duration,
+    /// Past `ttl`, how much longer the old price is served while a refresh runs; zero disables it
+    pub stale_while_revalidate: Duration,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Freshness {
+    Fresh,
+    /// Servable, but due a background refresh
+    Stale,
+    Expired,
+}
+
+impl Default for CachePolicy {
+    fn default() -> Self {
+        Self { ttl: Duration::from_millis(500), stale_while_revalidate: Duration::ZERO }
+    }
+}
+
+impl From<&CacheConfig> for CachePolicy {
+    fn from(config: &CacheConfig) -> Self {
+        Self {
+            ttl: Duration::from_millis(config.ttl_ms),
+            stale_while_revalidate: Duration::from_millis(config.stale_while_revalidate_ms),
+        }
+    }
+}
+
+impl CachePolicy {
+    pub fn freshness(&self, age: Duration) -> Freshness {
+        if age < self.ttl {
+            Freshness::Fresh
+        } else if age < self.ttl + self.stale_while_revalidate {
+            Freshness::Stale
+        } else {
+            Freshness::Expired
+        }
+    }
+}
+
+/// Runs at most one fetch per key at a time; callers arriving while it runs get its result
+pub struct SingleFlight<T> {
+    in_flight: Mutex<HashMap<String, watch::Receiver<Option<T>>>>,
+}
+
+impl<T> Default for SingleFlight<T> {
+    fn default() -> Self {
+        Self { in_flight: Mutex::new(HashMap::new()) }
+    }
+}
+
+impl<T: Clone> SingleFlight<T> {
+    /// Awaits `fetch` unless one is already running for `key`, in which case `fetch` is
+    /// dropped unpolled and the running one's result returned. The flag says whether the
+    /// result was shared. If the running caller is cancelled, a waiting one takes over.
+    pub async fn run<F: Future<Output = T>>(&self, key: &str, fetch: F) -> (T, bool) {
+        let sender = loop {
+            let mut receiver = {
+                let mut in_flight = self.in_flight.lock().unwrap();
+                match in_flight.get(key) {
+                    Some(receiver) => receiver.clone(),
+                    None => {
+                        let (sender, receiver) = watch::channel(None);
+                        in_flight.insert(key.to_string(), receiver);
+                        break sender;
+                    }
+                }
+            };
+            // Errs once the sender is dropped without a result
+            let shared = receiver.wait_for(Option::is_some).await.ok().and_then(|result| result.clone());
+            if let Some(result) = shared {
+                return (result, true);
+            }
+        };
+
+        let _done = InFlight { flights: &self.in_flight, key };
+        let result = fetch.await;
+        sender.send_replace(Some(result.clone()));
+        (result, false)
+    }
+
+    pub fn is_running(&self, key: &str) -> bool {
+        self.in_flight.lock().unwrap().contains_key(key)
+    }
+}
+
+// Clears the key when its fetch finishes or is cancelled
+struct InFlight<'a, T> {
+    flights: &'a Mutex<HashMap<String, watch::Receiver<Option<T>>>>,
+    key: &'a str,
+}
+
+impl<T> Drop for InFlight<'_, T> {
+    fn drop(&mut self) {
+        self.flights.lock().unwrap().remove(self.key);
+    }
+}
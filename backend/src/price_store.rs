@@ -0,0 +1,85 @@
+use std::sync::Arc;
+use anyhow::Result;
+use async_trait::async_trait;
+use log::info;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use crate::feed_registry::SymbolFeeds;
+use crate::memory_store::MemoryStore;
+use crate::oracle_client::AggregatedPrice;
+use crate::postgres_store::PostgresStore;
+
+/// Where published prices, their source observations, manipulation events and symbols
+/// added at runtime are kept. Times are unix seconds.
+#[async_trait]
+pub trait PriceStore: Send + Sync + std::fmt::Debug {
+    /// Stores an aggregate with the source observations behind it
+    async fn insert_price(&self, price: &AggregatedPrice) -> Result<()>;
+
+    /// Up to `limit` prices of `symbol` stored since `since`, newest first, with their
+    /// sources restored and timestamped when they were stored
+    async fn price_history(&self, symbol: &str, since: i64, limit: usize) -> Result<Vec<AggregatedPrice>>;
+
+    /// Average mark price of `symbol` since `since`, `None` without any prices
+    async fn average_price(&self, symbol: &str, since: i64) -> Result<Option<Decimal>>;
+
+    async fn count_prices(&self, symbol: &str, since: i64) -> Result<usize>;
+
+    async fn insert_manipulation_event(&self, event: &ManipulationEvent) -> Result<()>;
+
+    /// Manipulation events of `symbol` since `since`, newest first
+    async fn manipulation_events(&self, symbol: &str, since: i64) -> Result<Vec<ManipulationEvent>>;
+
+    /// Saves or replaces the feeds of a symbol added at runtime
+    async fn save_symbol(&self, symbol: &str, feeds: &SymbolFeeds) -> Result<()>;
+
+    async fn remove_symbol(&self, symbol: &str) -> Result<()>;
+
+    /// Symbols added at runtime, in the order they were first saved
+    async fn saved_symbols(&self) -> Result<Vec<(String, SymbolFeeds)>>;
+
+    async fn check_health(&self) -> Result<()>;
+}
+
+/// A price whose manipulation score crossed the alert threshold
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ManipulationEvent {
+    pub symbol: String,
+    pub price: Decimal,
+    pub confidence: Decimal,
+    pub manipulation_score: f64,
+    pub timestamp: i64,
+}
+
+/// Opens the store `url` names and brings its schema up to date: `memory://` keeps
+/// everything in the process, `sqlite:` URLs need the `sqlite` feature, and anything
+/// else is taken as a Postgres URL
+pub async fn connect(url: &str) -> Result<Arc<dyn PriceStore>> {
+    if url.starts_with("memory:") {
+        info!("Using the in-memory price store; nothing survives a restart");
+        return Ok(Arc::new(MemoryStore::default()));
+    }
+    if url.starts_with("sqlite:") {
+        return connect_sqlite(url).await;
+    }
+    info!("Using the Postgres price store");
+    Ok(Arc::new(PostgresStore::connect(url).await?))
+}
+
+#[cfg(feature = "sqlite")]
+async fn connect_sqlite(url: &str) -> Result<Arc<dyn PriceStore>> {
+    info!("Using the SQLite price store at {}", url);
+    Ok(Arc::new(crate::sqlite_store::SqliteStore::connect(url).await?))
+}
+
+#[cfg(not(feature = "sqlite"))]
+async fn connect_sqlite(url: &str) -> Result<Arc<dyn PriceStore>> {
+    Err(anyhow::anyhow!("{} needs the SQLite store; rebuild with --features sqlite", url))
+}
+
+pub fn unix_now() -> i64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs() as i64
+}
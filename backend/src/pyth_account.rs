@@ -0,0 +1,91 @@
+use thiserror::Error;
+
+const PYTH_MAGIC: u32 = 0xa1b2c3d4;
+const PYTH_VERSION: u32 = 2;
+const ACCOUNT_TYPE_PRICE: u32 = 3;
+const PRICE_STATUS_TRADING: u32 = 1;
+
+// Byte offsets into the pyth-sdk-solana `SolanaPriceAccount` layout
+const MAGIC_OFFSET: usize = 0;
+const VERSION_OFFSET: usize = 4;
+const ACCOUNT_TYPE_OFFSET: usize = 8;
+const EXPO_OFFSET: usize = 20;
+const TIMESTAMP_OFFSET: usize = 96;
+const AGG_PRICE_OFFSET: usize = 208;
+const AGG_CONF_OFFSET: usize = 216;
+const AGG_STATUS_OFFSET: usize = 224;
+const AGG_PUB_SLOT_OFFSET: usize = 232;
+const PRICE_HEADER_SIZE: usize = 240; // Everything before the publisher components
+
+#[derive(Debug, Error, PartialEq)]
+pub enum PythAccountError {
+    #[error("price account {0} not found")]
+    AccountNotFound(String),
+    #[error("invalid account encoding: {0}")]
+    InvalidEncoding(String),
+    #[error("account is {0} bytes, expected at least {PRICE_HEADER_SIZE}")]
+    AccountTooSmall(usize),
+    #[error("bad magic number {0:#x}, not a Pyth account")]
+    InvalidMagic(u32),
+    #[error("unsupported Pyth account version {0}")]
+    UnsupportedVersion(u32),
+    #[error("account type {0} is not a price account")]
+    NotPriceAccount(u32),
+    #[error("aggregate price status {0} is not trading")]
+    NotTrading(u32),
+    #[error("aggregate price {0} is not positive")]
+    NonPositivePrice(i64),
+}
+
+/// Aggregate price of a Pyth on-chain price account
+#[derive(Debug, Clone, PartialEq)]
+pub struct PythPriceAccount {
+    pub price: i64,
+    pub conf: u64,
+    pub expo: i32,
+    pub status: u32,
+    pub publish_time: i64,
+    pub pub_slot: u64,
+}
+
+pub fn decode_price_account(data: &[u8]) -> Result<PythPriceAccount, PythAccountError> {
+    if data.len() < PRICE_HEADER_SIZE {
+        return Err(PythAccountError::AccountTooSmall(data.len()));
+    }
+
+    let magic = u32::from_le_bytes(read_array(data, MAGIC_OFFSET));
+    if magic != PYTH_MAGIC {
+        return Err(PythAccountError::InvalidMagic(magic));
+    }
+    let version = u32::from_le_bytes(read_array(data, VERSION_OFFSET));
+    if version != PYTH_VERSION {
+        return Err(PythAccountError::UnsupportedVersion(version));
+    }
+    let account_type = u32::from_le_bytes(read_array(data, ACCOUNT_TYPE_OFFSET));
+    if account_type != ACCOUNT_TYPE_PRICE {
+        return Err(PythAccountError::NotPriceAccount(account_type));
+    }
+
+    // Halted/auction/unknown aggregates carry a stale price
+    let status = u32::from_le_bytes(read_array(data, AGG_STATUS_OFFSET));
+    if status != PRICE_STATUS_TRADING {
+        return Err(PythAccountError::NotTrading(status));
+    }
+    let price = i64::from_le_bytes(read_array(data, AGG_PRICE_OFFSET));
+    if price <= 0 {
+        return Err(PythAccountError::NonPositivePrice(price));
+    }
+
+    Ok(PythPriceAccount {
+        price,
+        conf: u64::from_le_bytes(read_array(data, AGG_CONF_OFFSET)),
+        expo: i32::from_le_bytes(read_array(data, EXPO_OFFSET)),
+        status,
+        publish_time: i64::from_le_bytes(read_array(data, TIMESTAMP_OFFSET)),
+        pub_slot: u64::from_le_bytes(read_array(data, AGG_PUB_SLOT_OFFSET)),
+    })
+}
+
+fn read_array<const N: usize>(data: &[u8], offset: usize) -> [u8; N] {
+    data[offset..offset + N].try_into().expect("slice length checked by caller")
+}
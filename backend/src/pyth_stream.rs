@@ -0,0 +1,212 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use anyhow::{Result, anyhow};
+use async_trait::async_trait;
+use log::{debug, info, warn};
+use reqwest::Client;
+use rust_decimal::Decimal;
+use tokio::sync::RwLock;
+use tokio::task::JoinHandle;
+use crate::feed_registry::SharedFeedRegistry;
+use crate::oracle_client::{OracleClient, PriceData, PythClient};
+
+const INITIAL_BACKOFF: Duration = Duration::from_millis(500);
+const MAX_BACKOFF: Duration = Duration::from_secs(30);
+// How often an open stream checks whether symbols were added or removed
+const FEED_CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
+type LatestPrices = Arc<RwLock<HashMap<String, PriceData>>>;
+
+enum StreamEnd {
+    FeedsChanged,
+    Closed,
+}
+
+/// Hermes SSE subscriber serving the latest streamed price per symbol.
+/// Holds no prices while disconnected, so callers fall back to polling.
+pub struct PythStreamClient {
+    latest: LatestPrices,
+    registry: SharedFeedRegistry,
+    max_price_age: Duration,
+    task: JoinHandle<()>,
+}
+
+impl PythStreamClient {
+    /// Subscribes to every registered Pyth feed; the stream stops when the client is dropped
+    pub fn start(base_url: String, registry: SharedFeedRegistry, max_price_age: Duration) -> Self {
+        let latest = LatestPrices::default();
+        let task = tokio::spawn(run_stream(Client::new(), base_url, registry.clone(), latest.clone()));
+
+        Self { latest, registry, max_price_age, task }
+    }
+}
+
+impl Drop for PythStreamClient {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+#[async_trait]
+impl OracleClient for PythStreamClient {
+    async fn get_price(&self, symbol: &str) -> Result<PriceData> {
+        // Older prices would be left out of the aggregate anyway; polling may do better
+        let source_age = self.registry.read().await.price_bounds(symbol).max_source_age_seconds;
+        let max_age = self.max_price_age.as_secs().min(source_age) as i64;
+
+        let latest = self.latest.read().await;
+        let price = latest
+            .get(symbol)
+            .ok_or_else(|| anyhow!("No streamed Pyth price for {}", symbol))?;
+
+        let now = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs() as i64;
+        let age = now - price.timestamp;
+        if age > max_age {
+            return Err(anyhow!("Streamed Pyth price for {} is {}s old", symbol, age));
+        }
+
+        Ok(price.clone())
+    }
+
+    async fn get_multiple_prices(&self, symbols: &[String]) -> Result<Vec<PriceData>> {
+        let mut results = Vec::new();
+        for symbol in symbols {
+            match self.get_price(symbol).await {
+                Ok(price_data) => results.push(price_data),
+                Err(e) => debug!("{}", e),
+            }
+        }
+        Ok(results)
+    }
+
+    fn get_name(&self) -> &str {
+        "Pyth"
+    }
+
+    async fn supports(&self, symbol: &str) -> bool {
+        self.registry.read().await.pyth_feed_id(symbol).is_some()
+    }
+}
+
+async fn run_stream(client: Client, base_url: String, registry: SharedFeedRegistry, latest: LatestPrices) {
+    let mut backoff = INITIAL_BACKOFF;
+    loop {
+        match stream_once(&client, &base_url, &registry, &latest, &mut backoff).await {
+            Ok(StreamEnd::FeedsChanged) => {
+                info!("Pyth feeds changed, resubscribing");
+                continue;
+            }
+            Ok(StreamEnd::Closed) => info!("Pyth stream closed, reconnecting in {:?}", backoff),
+            Err(e) => warn!("Pyth stream failed: {}, reconnecting in {:?}", e, backoff),
+        }
+
+        // Nothing is pushed while disconnected; hand over to polling
+        latest.write().await.clear();
+        tokio::time::sleep(backoff).await;
+        backoff = (backoff * 2).min(MAX_BACKOFF);
+    }
+}
+
+async fn stream_once(
+    client: &Client,
+    base_url: &str,
+    registry: &SharedFeedRegistry,
+    latest: &LatestPrices,
+    backoff: &mut Duration,
+) -> Result<StreamEnd> {
+    let feed_ids = registry.read().await.pyth_feed_ids().clone();
+    latest.write().await.retain(|symbol, _| feed_ids.contains_key(symbol));
+    if feed_ids.is_empty() {
+        return Err(anyhow!("no Pyth feeds registered"));
+    }
+
+    let symbols_by_id: HashMap<&str, &str> = feed_ids.iter()
+        .map(|(symbol, id)| (id.as_str(), symbol.as_str()))
+        .collect();
+    let ids_param = feed_ids.values()
+        .map(|id| format!("ids[]={}", id))
+        .collect::<Vec<_>>()
+        .join("&");
+    let url = format!("{}/v2/updates/price/stream?{}&parsed=true", base_url, ids_param);
+
+    let mut response = client
+        .get(&url)
+        .header("Accept", "text/event-stream")
+        .header("User-Agent", "GoQuant-Oracle/1.0")
+        .send()
+        .await?
+        .error_for_status()?;
+    info!("Subscribed to {} Pyth feeds over SSE", feed_ids.len());
+
+    let mut buffer = Vec::new();
+    let mut feed_check = tokio::time::interval(FEED_CHECK_INTERVAL);
+    loop {
+        tokio::select! {
+            chunk = response.chunk() => {
+                let Some(chunk) = chunk? else {
+                    return Ok(StreamEnd::Closed);
+                };
+                buffer.extend(chunk.iter().filter(|&&b| b != b'\r'));
+
+                for data in take_sse_events(&mut buffer) {
+                    apply_update(&data, &symbols_by_id, latest).await;
+                    *backoff = INITIAL_BACKOFF;
+                }
+            }
+            _ = feed_check.tick() => {
+                if *registry.read().await.pyth_feed_ids() != feed_ids {
+                    return Ok(StreamEnd::FeedsChanged);
+                }
+            }
+        }
+    }
+}
+
+/// Removes complete events from `buffer`, returning their `data` payloads
+pub fn take_sse_events(buffer: &mut Vec<u8>) -> Vec<String> {
+    let mut events = Vec::new();
+    while let Some(end) = buffer.windows(2).position(|w| w == b"\n\n") {
+        let event: Vec<u8> = buffer.drain(..end + 2).collect();
+        let data = String::from_utf8_lossy(&event)
+            .lines()
+            .filter_map(|line| line.strip_prefix("data:"))
+            .map(|data| data.strip_prefix(' ').unwrap_or(data))
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        // Comment-only events (keepalives) carry no data
+        if !data.is_empty() {
+            events.push(data);
+        }
+    }
+    events
+}
+
+async fn apply_update(data: &str, symbols_by_id: &HashMap<&str, &str>, latest: &LatestPrices) {
+    let update: serde_json::Value = match serde_json::from_str(data) {
+        Ok(update) => update,
+        Err(e) => {
+            warn!("Ignoring malformed Pyth stream event: {}", e);
+            return;
+        }
+    };
+
+    let mut latest = latest.write().await;
+    for feed in update["parsed"].as_array().into_iter().flatten() {
+        let Some(symbol) = feed["id"].as_str().and_then(|id| symbols_by_id.get(id)) else {
+            continue;
+        };
+
+        match PythClient::parse_price_feed(symbol, feed) {
+            Ok(price) if price.price > Decimal::ZERO => {
+                let is_newer = latest.get(*symbol).map_or(true, |current| price.timestamp >= current.timestamp);
+                if is_newer {
+                    latest.insert(symbol.to_string(), price);
+                }
+            }
+            Ok(price) => warn!("Ignoring non-positive streamed price for {}: {}", symbol, price.price),
+            Err(e) => warn!("Ignoring unparseable streamed price for {}: {}", symbol, e),
+        }
+    }
+}
@@ -0,0 +1,137 @@
+use std::collections::HashMap;
+use std::fmt;
+use anyhow::Result;
+use rust_decimal::Decimal;
+use rust_decimal::prelude::ToPrimitive;
+use serde::{Deserialize, Serialize};
+use crate::oracle_client::{ExcludedSource, PriceData};
+
+/// Currency a source quotes its prices in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum QuoteAsset {
+    Usd,
+    Usdt,
+    Usdc,
+}
+
+impl QuoteAsset {
+    /// Quote of an oracle symbol, e.g. USD for BTC/USD; `None` for non-dollar quotes like ETH/BTC
+    pub fn of_symbol(symbol: &str) -> Option<Self> {
+        match symbol.split_once('/')?.1 {
+            "USD" => Some(QuoteAsset::Usd),
+            "USDT" => Some(QuoteAsset::Usdt),
+            "USDC" => Some(QuoteAsset::Usdc),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for QuoteAsset {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            QuoteAsset::Usd => "USD",
+            QuoteAsset::Usdt => "USDT",
+            QuoteAsset::Usdc => "USDC",
+        })
+    }
+}
+
+/// What happens to stablecoin-quoted sources when the stablecoin is off its peg
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DepegAction {
+    /// Leave them out of the aggregate
+    #[default]
+    Exclude,
+    /// Aggregate them converted at the depegged rate and report the depeg
+    Flag,
+}
+
+/// Stablecoin/USD rate used to convert a source's price, as reported on the aggregate
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct QuoteRate {
+    pub asset: QuoteAsset,
+    pub rate: Decimal,
+    pub confidence: Decimal,
+}
+
+/// Source prices in the symbol's own quote, and what was left out on the way
+#[derive(Debug, Default)]
+pub struct NormalizedPrices {
+    pub prices: Vec<PriceData>,
+    pub excluded: Vec<ExcludedSource>,
+    /// Rates that were past the depeg threshold but used anyway (`flag`)
+    pub depegged: Vec<QuoteRate>,
+}
+
+/// Converts stablecoin-quoted prices to USD through configured stablecoin/USD symbols
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct QuoteConversion {
+    /// Symbol pricing each stablecoin in USD, e.g. USDT -> USDT/USD
+    pub feeds: HashMap<QuoteAsset, String>,
+    /// Relative distance from 1.0 beyond which a stablecoin counts as depegged
+    pub max_depeg: Decimal,
+    pub depeg_action: DepegAction,
+}
+
+impl QuoteConversion {
+    /// Brings each `(price, quote)` into the quote of `symbol`; a `None` quote is already
+    /// the symbol's. `rates` holds the stablecoin/USD rate (or why there is none) for
+    /// every quote that needs converting.
+    pub fn normalize(
+        &self,
+        symbol: &str,
+        prices: Vec<(PriceData, Option<QuoteAsset>)>,
+        rates: &HashMap<QuoteAsset, Result<QuoteRate>>,
+    ) -> NormalizedPrices {
+        let target = QuoteAsset::of_symbol(symbol);
+        let mut normalized = NormalizedPrices::default();
+
+        for (price, quote) in prices {
+            let quote = match quote {
+                Some(quote) if target != Some(quote) => quote,
+                _ => {
+                    normalized.prices.push(price);
+                    continue;
+                }
+            };
+
+            let rate = match (target, rates.get(&quote)) {
+                (Some(QuoteAsset::Usd), Some(Ok(rate))) => rate,
+                (Some(QuoteAsset::Usd), Some(Err(e))) => {
+                    normalized.excluded.push(ExcludedSource::new(price, format!("Quoted in {}, no {}/USD rate: {}", quote, quote, e)));
+                    continue;
+                }
+                _ => {
+                    let target = target.map_or_else(|| symbol.to_string(), |t| t.to_string());
+                    normalized.excluded.push(ExcludedSource::new(price, format!("Quoted in {}, no conversion to {}", quote, target)));
+                    continue;
+                }
+            };
+
+            let depeg = (rate.rate - Decimal::ONE).abs();
+            if depeg > self.max_depeg {
+                let percent = (depeg * Decimal::ONE_HUNDRED).to_f64().unwrap_or_default();
+                if self.depeg_action == DepegAction::Exclude {
+                    normalized.excluded.push(ExcludedSource::new(price, format!(
+                        "Quoted in {}, {:.2}% off its USD peg at {}", quote, percent, rate.rate
+                    )));
+                    continue;
+                }
+                if !normalized.depegged.iter().any(|r| r.asset == quote) {
+                    normalized.depegged.push(rate.clone());
+                }
+            }
+
+            // Relative confidences add, as for synthetic pairs
+            normalized.prices.push(PriceData {
+                price: price.price * rate.rate,
+                confidence: price.confidence * rate.rate + price.price * rate.confidence,
+                ..price
+            });
+        }
+
+        normalized
+    }
+}
@@ -0,0 +1,254 @@
+use std::collections::VecDeque;
+use std::future::Future;
+use std::sync::Mutex;
+use std::time::Duration;
+use anyhow::{Result, anyhow};
+use async_trait::async_trait;
+use log::{debug, info, warn};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use tokio::time::Instant;
+use crate::config::ResilienceConfig;
+use crate::oracle_client::{OracleClient, PriceData};
+use crate::quote::QuoteAsset;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BreakerState {
+    Closed,
+    Open,
+    HalfOpen,
+}
+
+/// Point-in-time view of a source's breaker for health output
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BreakerSnapshot {
+    pub state: BreakerState,
+    pub error_rate: f64,
+    pub recent_calls: usize,
+}
+
+struct BreakerInner {
+    state: BreakerState,
+    outcomes: VecDeque<bool>, // true = success, most recent last
+    opened_at: Option<Instant>,
+    probe_in_flight: bool,
+}
+
+/// Closed/open/half-open breaker driven by the error rate over the last calls
+pub struct CircuitBreaker {
+    config: ResilienceConfig,
+    inner: Mutex<BreakerInner>,
+}
+
+impl CircuitBreaker {
+    pub fn new(config: ResilienceConfig) -> Self {
+        Self {
+            config,
+            inner: Mutex::new(BreakerInner {
+                state: BreakerState::Closed,
+                outcomes: VecDeque::new(),
+                opened_at: None,
+                probe_in_flight: false,
+            }),
+        }
+    }
+
+    /// Permit for a call to go through, `None` while the breaker rejects calls; an open
+    /// breaker lets one probe through once it cools down
+    pub fn allow(&self) -> Option<BreakerPermit<'_>> {
+        let mut inner = self.inner.lock().unwrap();
+        let allowed = match inner.state {
+            BreakerState::Closed => true,
+            BreakerState::Open => {
+                let cooled_down = inner.opened_at
+                    .is_some_and(|opened| opened.elapsed() >= Duration::from_millis(self.config.breaker_open_ms));
+                if cooled_down {
+                    inner.state = BreakerState::HalfOpen;
+                    inner.probe_in_flight = true;
+                }
+                cooled_down
+            }
+            BreakerState::HalfOpen if inner.probe_in_flight => false,
+            BreakerState::HalfOpen => {
+                inner.probe_in_flight = true;
+                true
+            }
+        };
+        allowed.then(|| BreakerPermit { breaker: self, recorded: false })
+    }
+
+    /// Records the outcome of an allowed call, returning the state transition if any
+    pub fn record(&self, success: bool) -> Option<BreakerState> {
+        let mut inner = self.inner.lock().unwrap();
+        let before = inner.state;
+
+        match inner.state {
+            BreakerState::HalfOpen => {
+                inner.probe_in_flight = false;
+                if success {
+                    inner.state = BreakerState::Closed;
+                    inner.outcomes.clear();
+                } else {
+                    inner.state = BreakerState::Open;
+                    inner.opened_at = Some(Instant::now());
+                }
+            }
+            // Late results from calls started before the breaker opened
+            BreakerState::Open => {}
+            BreakerState::Closed => {
+                inner.outcomes.push_back(success);
+                while inner.outcomes.len() > self.config.breaker_window {
+                    inner.outcomes.pop_front();
+                }
+                if inner.outcomes.len() >= self.config.breaker_min_calls
+                    && error_rate(&inner.outcomes) >= self.config.breaker_error_rate
+                {
+                    inner.state = BreakerState::Open;
+                    inner.opened_at = Some(Instant::now());
+                }
+            }
+        }
+
+        (inner.state != before).then_some(inner.state)
+    }
+
+    pub fn snapshot(&self) -> BreakerSnapshot {
+        let inner = self.inner.lock().unwrap();
+        BreakerSnapshot {
+            state: inner.state,
+            error_rate: error_rate(&inner.outcomes),
+            recent_calls: inner.outcomes.len(),
+        }
+    }
+}
+
+/// An allowed call. A permit dropped before its outcome is recorded counts as a failure,
+/// so a cancelled probe doesn't leave the breaker half-open for good.
+pub struct BreakerPermit<'a> {
+    breaker: &'a CircuitBreaker,
+    recorded: bool,
+}
+
+impl BreakerPermit<'_> {
+    pub fn record(mut self, success: bool) -> Option<BreakerState> {
+        self.recorded = true;
+        self.breaker.record(success)
+    }
+}
+
+impl Drop for BreakerPermit<'_> {
+    fn drop(&mut self) {
+        if !self.recorded {
+            self.breaker.record(false);
+        }
+    }
+}
+
+fn error_rate(outcomes: &VecDeque<bool>) -> f64 {
+    if outcomes.is_empty() {
+        return 0.0;
+    }
+    outcomes.iter().filter(|&&ok| !ok).count() as f64 / outcomes.len() as f64
+}
+
+/// Wraps any client with retries (exponential backoff with jitter), a per-call
+/// deadline and a circuit breaker for the source
+pub struct ResilientClient {
+    inner: Box<dyn OracleClient>,
+    config: ResilienceConfig,
+    breaker: CircuitBreaker,
+}
+
+impl ResilientClient {
+    pub fn new(inner: Box<dyn OracleClient>, config: ResilienceConfig) -> Self {
+        Self {
+            breaker: CircuitBreaker::new(config.clone()),
+            inner,
+            config,
+        }
+    }
+
+    async fn call<T, F, Fut>(&self, operation: F) -> Result<T>
+    where
+        F: Fn() -> Fut,
+        Fut: Future<Output = Result<T>>,
+    {
+        let name = self.inner.get_name();
+        let Some(permit) = self.breaker.allow() else {
+            return Err(anyhow!("Circuit breaker open for {}", name));
+        };
+
+        let deadline = Duration::from_millis(self.config.call_deadline_ms);
+        let result = match tokio::time::timeout(deadline, self.retry(&operation)).await {
+            Ok(result) => result,
+            Err(_) => Err(anyhow!("{} call exceeded {:?} deadline", name, deadline)),
+        };
+
+        match permit.record(result.is_ok()) {
+            Some(BreakerState::Open) => warn!("Circuit breaker opened for {}", name),
+            Some(BreakerState::Closed) => info!("Circuit breaker closed for {}", name),
+            _ => {}
+        }
+        result
+    }
+
+    async fn retry<T, F, Fut>(&self, operation: &F) -> Result<T>
+    where
+        F: Fn() -> Fut,
+        Fut: Future<Output = Result<T>>,
+    {
+        let mut attempt = 1;
+        loop {
+            match operation().await {
+                Ok(value) => return Ok(value),
+                Err(e) if attempt >= self.config.max_attempts => return Err(e),
+                Err(e) => {
+                    let backoff = self.backoff(attempt);
+                    debug!("{} attempt {} failed: {}, retrying in {:?}", self.inner.get_name(), attempt, e, backoff);
+                    tokio::time::sleep(backoff).await;
+                    attempt += 1;
+                }
+            }
+        }
+    }
+
+    // initial * 2^(attempt-1), capped, scaled by a random factor in [1 - jitter, 1]
+    fn backoff(&self, attempt: u32) -> Duration {
+        let exponential = self.config.initial_backoff_ms.saturating_mul(1 << (attempt - 1).min(16));
+        let capped = exponential.min(self.config.max_backoff_ms) as f64;
+        let jitter = rand::thread_rng().gen_range(0.0..=self.config.jitter);
+        Duration::from_millis((capped * (1.0 - jitter)) as u64)
+    }
+}
+
+#[async_trait]
+impl OracleClient for ResilientClient {
+    async fn get_price(&self, symbol: &str) -> Result<PriceData> {
+        // A missing feed is configuration, not an outage: neither retried nor held against the source
+        if !self.inner.supports(symbol).await {
+            return Err(anyhow!("{} has no feed for {}", self.inner.get_name(), symbol));
+        }
+        self.call(|| self.inner.get_price(symbol)).await
+    }
+
+    async fn get_multiple_prices(&self, symbols: &[String]) -> Result<Vec<PriceData>> {
+        self.call(|| self.inner.get_multiple_prices(symbols)).await
+    }
+
+    fn get_name(&self) -> &str {
+        self.inner.get_name()
+    }
+
+    async fn supports(&self, symbol: &str) -> bool {
+        self.inner.supports(symbol).await
+    }
+
+    fn circuit_breaker(&self) -> Option<BreakerSnapshot> {
+        Some(self.breaker.snapshot())
+    }
+
+    fn quote_asset(&self, symbol: &str) -> Option<QuoteAsset> {
+        self.inner.quote_asset(symbol)
+    }
+}
@@ -0,0 +1,228 @@
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use anyhow::Result;
+use log::warn;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use tokio::task::JoinHandle;
+use tokio::time::Instant;
+
+/// How often one symbol is refreshed. The interval starts at `interval` and then follows
+/// market activity between `max_interval` (calm) and `min_interval` (volatile).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RefreshSchedule {
+    pub interval: Duration,
+    pub min_interval: Duration,
+    pub max_interval: Duration,
+    /// Fraction of the interval randomly added or removed each time, in [0, 1)
+    pub jitter: f64,
+    /// Realized volatility per minute, as a fraction, at which `min_interval` is reached
+    pub volatility_threshold: f64,
+    /// Spread between sources, as a fraction of their price, at which `min_interval` is reached
+    pub dispersion_threshold: f64,
+}
+
+/// What a refresh saw of the market, as fractions of the price
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct MarketActivity {
+    /// Realized volatility over the last minute
+    pub volatility: f64,
+    /// Widest spread between the sources of one price
+    pub dispersion: f64,
+}
+
+impl Default for RefreshSchedule {
+    fn default() -> Self {
+        Self::fixed(Duration::from_millis(250)).with_jitter(0.1)
+    }
+}
+
+impl RefreshSchedule {
+    /// A schedule that never adapts
+    pub fn fixed(interval: Duration) -> Self {
+        Self {
+            interval,
+            min_interval: interval,
+            max_interval: interval,
+            jitter: 0.0,
+            volatility_threshold: 0.005,
+            dispersion_threshold: 0.0025,
+        }
+    }
+
+    pub fn with_jitter(self, jitter: f64) -> Self {
+        Self { jitter, ..self }
+    }
+
+    /// Interval after a refresh that saw `activity`: the maximum when calm, shrinking
+    /// linearly to the minimum as volatility or dispersion reaches its threshold
+    pub fn interval_for(&self, activity: &MarketActivity) -> Duration {
+        // f64::max skips NaN, so an unusable measurement falls back to the other
+        let pressure = (activity.volatility / self.volatility_threshold)
+            .max(activity.dispersion / self.dispersion_threshold);
+        let pressure = if pressure.is_nan() { 0.0 } else { pressure.clamp(0.0, 1.0) };
+        self.max_interval - (self.max_interval - self.min_interval).mul_f64(pressure)
+    }
+
+    /// Delay before the next refresh; jitter keeps symbols from hitting upstreams in lockstep
+    pub fn next_delay(&self, interval: Duration) -> Duration {
+        if self.jitter <= 0.0 {
+            return interval;
+        }
+        let factor = rand::thread_rng().gen_range(1.0 - self.jitter..=1.0 + self.jitter);
+        interval.mul_f64(factor)
+    }
+}
+
+/// Refresh progress of one scheduled symbol
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScheduleStatus {
+    pub symbol: String,
+    /// Current interval, between `min_interval_ms` and `max_interval_ms`
+    pub interval_ms: u64,
+    pub min_interval_ms: u64,
+    pub max_interval_ms: u64,
+    /// Market activity seen by the last successful refresh
+    pub activity: MarketActivity,
+    /// Unix time of the last successful refresh, in milliseconds
+    pub last_update_ms: Option<u64>,
+    /// Time since the last successful refresh (or since scheduling, before the first)
+    pub update_lag_ms: u64,
+    pub consecutive_failures: u32,
+}
+
+struct TaskState {
+    schedule: RefreshSchedule,
+    interval: Duration,
+    activity: MarketActivity,
+    started: Instant,
+    last_success: Option<(Instant, SystemTime)>,
+    consecutive_failures: u32,
+}
+
+struct SymbolTask {
+    handle: JoinHandle<()>,
+    state: Arc<Mutex<TaskState>>,
+}
+
+/// Refreshes each symbol on its own task, so a slow upstream only delays its own symbol
+#[derive(Default)]
+pub struct SymbolScheduler {
+    tasks: Mutex<HashMap<String, SymbolTask>>,
+}
+
+impl SymbolScheduler {
+    /// Starts a task for each of `symbols` not yet scheduled, stops the tasks of symbols
+    /// no longer listed and moves running tasks onto their new schedule if it changed.
+    /// Each task calls `refresh`, then waits for an interval adapted to the market
+    /// activity it reported.
+    pub fn reconcile<F, Fut>(&self, symbols: &[(String, RefreshSchedule)], refresh: F)
+    where
+        F: Fn(String) -> Fut + Clone + Send + 'static,
+        Fut: Future<Output = Result<MarketActivity>> + Send + 'static,
+    {
+        let mut tasks = self.tasks.lock().unwrap();
+        tasks.retain(|symbol, task| {
+            let keep = symbols.iter().any(|(s, _)| s == symbol);
+            if !keep {
+                task.handle.abort();
+            }
+            keep
+        });
+
+        for (symbol, schedule) in symbols {
+            if let Some(task) = tasks.get(symbol) {
+                let mut state = task.state.lock().unwrap();
+                if state.schedule != *schedule {
+                    // Takes effect from the next refresh; the current wait runs out as planned
+                    state.schedule = *schedule;
+                    state.interval = match state.last_success {
+                        Some(_) => schedule.interval_for(&state.activity),
+                        None => schedule.interval,
+                    };
+                }
+                continue;
+            }
+            let state = Arc::new(Mutex::new(TaskState {
+                schedule: *schedule,
+                interval: schedule.interval,
+                activity: MarketActivity::default(),
+                started: Instant::now(),
+                last_success: None,
+                consecutive_failures: 0,
+            }));
+            let handle = tokio::spawn(run_symbol(symbol.clone(), state.clone(), refresh.clone()));
+            tasks.insert(symbol.clone(), SymbolTask { handle, state });
+        }
+    }
+
+    pub fn status(&self) -> Vec<ScheduleStatus> {
+        let tasks = self.tasks.lock().unwrap();
+        let mut status: Vec<ScheduleStatus> = tasks.iter()
+            .map(|(symbol, task)| {
+                let state = task.state.lock().unwrap();
+                let since = state.last_success.map_or(state.started, |(at, _)| at);
+                ScheduleStatus {
+                    symbol: symbol.clone(),
+                    interval_ms: state.interval.as_millis() as u64,
+                    min_interval_ms: state.schedule.min_interval.as_millis() as u64,
+                    max_interval_ms: state.schedule.max_interval.as_millis() as u64,
+                    activity: state.activity,
+                    last_update_ms: state.last_success
+                        .and_then(|(_, at)| at.duration_since(UNIX_EPOCH).ok())
+                        .map(|at| at.as_millis() as u64),
+                    update_lag_ms: since.elapsed().as_millis() as u64,
+                    consecutive_failures: state.consecutive_failures,
+                }
+            })
+            .collect();
+        status.sort_by(|a, b| a.symbol.cmp(&b.symbol));
+        status
+    }
+}
+
+impl std::fmt::Debug for SymbolScheduler {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SymbolScheduler")
+            .field("symbols", &self.tasks.lock().unwrap().len())
+            .finish()
+    }
+}
+
+impl Drop for SymbolScheduler {
+    fn drop(&mut self) {
+        for task in self.tasks.lock().unwrap().values() {
+            task.handle.abort();
+        }
+    }
+}
+
+async fn run_symbol<F, Fut>(symbol: String, state: Arc<Mutex<TaskState>>, refresh: F)
+where
+    F: Fn(String) -> Fut,
+    Fut: Future<Output = Result<MarketActivity>>,
+{
+    loop {
+        let result = refresh(symbol.clone()).await;
+        let delay = {
+            let mut state = state.lock().unwrap();
+            match &result {
+                Ok(activity) => {
+                    state.last_success = Some((Instant::now(), SystemTime::now()));
+                    state.consecutive_failures = 0;
+                    state.activity = *activity;
+                    state.interval = state.schedule.interval_for(activity);
+                }
+                // Keep the last interval; failures shouldn't read as a calm market
+                Err(_) => state.consecutive_failures += 1,
+            }
+            state.schedule.next_delay(state.interval)
+        };
+        if let Err(e) = result {
+            warn!("Failed to update price for {}: {}", symbol, e);
+        }
+        tokio::time::sleep(delay).await;
+    }
+}
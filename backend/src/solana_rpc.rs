@@ -0,0 +1,93 @@
+use std::sync::Arc;
+use std::time::Duration;
+use anyhow::{Result, anyhow};
+use base64::prelude::*;
+use reqwest::Client;
+use tokio::sync::{RwLock, Semaphore};
+use tokio::time::Instant;
+
+/// Rate-limited Solana JSON-RPC client shared by the on-chain oracle readers
+pub struct SolanaRpcClient {
+    client: Client,
+    rpc_url: String,
+    rate_limiter: Semaphore,
+    last_request_time: Arc<RwLock<Instant>>,
+}
+
+impl SolanaRpcClient {
+    pub fn new(rpc_url: String) -> Self {
+        Self {
+            client: Client::new(),
+            rpc_url,
+            rate_limiter: Semaphore::new(2), // Max 2 concurrent requests
+            last_request_time: Arc::new(RwLock::new(Instant::now())),
+        }
+    }
+
+    /// Raw `getAccountInfo` response with base64 account data
+    pub async fn get_account_info(&self, address: &str) -> Result<serde_json::Value> {
+        // Rate limiting - wait at least 500ms between requests
+        let _permit = self.rate_limiter.acquire().await?;
+        {
+            let last_time = self.last_request_time.read().await;
+            let elapsed = last_time.elapsed();
+            if elapsed < Duration::from_millis(500) {
+                tokio::time::sleep(Duration::from_millis(500) - elapsed).await;
+            }
+        }
+
+        let payload = serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "getAccountInfo",
+            "params": [
+                address,
+                {
+                    "encoding": "base64",
+                    "commitment": "finalized"
+                }
+            ]
+        });
+
+        let response = self.client
+            .post(&self.rpc_url)
+            .json(&payload)
+            .timeout(Duration::from_secs(10))
+            .header("User-Agent", "GoQuant-Oracle/1.0")
+            .send()
+            .await?;
+
+        // Update last request time
+        {
+            let mut last_time = self.last_request_time.write().await;
+            *last_time = Instant::now();
+        }
+
+        let response_text = response.text().await?;
+        let response_json: serde_json::Value = serde_json::from_str(&response_text)
+            .map_err(|e| anyhow!("Failed to parse Solana RPC response '{}': {}", response_text, e))?;
+
+        if let Some(error) = response_json.get("error") {
+            return Err(anyhow!("RPC error: {}", error));
+        }
+
+        Ok(response_json)
+    }
+}
+
+/// Account bytes from a `getAccountInfo` response; `Ok(None)` if the account doesn't exist
+pub fn decode_account_data(response: &serde_json::Value) -> std::result::Result<Option<Vec<u8>>, String> {
+    let value = &response["result"]["value"];
+    if value.is_null() {
+        return Ok(None);
+    }
+
+    let encoded = value["data"][0]
+        .as_str()
+        .filter(|_| value["data"][1] == "base64")
+        .ok_or_else(|| "expected base64 account data".to_string())?;
+    BASE64_STANDARD
+        .decode(encoded)
+        .map(Some)
+        .map_err(|e| e.to_string())
+}
@@ -0,0 +1,187 @@
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use serde::{Deserialize, Serialize};
+
+/// Sliding window the per-source counters are reported over
+pub const STATS_WINDOW: Duration = Duration::from_secs(300);
+
+/// Upper bounds of the latency histogram buckets; slower calls land in the overflow bucket
+pub const LATENCY_BUCKETS_MS: [u64; 8] = [10, 25, 50, 100, 250, 500, 1000, 2500];
+
+struct Sample {
+    at: Instant,
+    success: bool,
+    latency: Duration,
+}
+
+struct StatsInner {
+    samples: VecDeque<Sample>, // oldest first
+    last_success: Option<i64>,
+}
+
+/// Outcome and latency of every call made to one source, kept for `window`
+pub struct SourceStats {
+    window: Duration,
+    inner: Mutex<StatsInner>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LatencyBucket {
+    /// Inclusive upper bound in milliseconds, `None` for the overflow bucket
+    pub le_ms: Option<u64>,
+    pub count: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LatencySummary {
+    pub p50_ms: u64,
+    pub p95_ms: u64,
+    pub p99_ms: u64,
+    pub max_ms: u64,
+    pub histogram: Vec<LatencyBucket>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SourceStatsSnapshot {
+    pub window_seconds: u64,
+    pub success_count: u64,
+    pub failure_count: u64,
+    pub error_rate: f64,
+    /// `None` until the source has been called within the window
+    pub latency: Option<LatencySummary>,
+    /// Unix time of the last successful call, kept after it leaves the window
+    pub last_success: Option<i64>,
+}
+
+impl SourceStats {
+    pub fn new(window: Duration) -> Self {
+        Self {
+            window,
+            inner: Mutex::new(StatsInner { samples: VecDeque::new(), last_success: None }),
+        }
+    }
+
+    pub fn record(&self, success: bool, latency: Duration) {
+        self.record_at(Instant::now(), chrono::Utc::now().timestamp(), success, latency);
+    }
+
+    pub fn record_at(&self, at: Instant, unix_time: i64, success: bool, latency: Duration) {
+        let mut inner = self.inner.lock().unwrap();
+        inner.samples.push_back(Sample { at, success, latency });
+        if success {
+            inner.last_success = Some(unix_time);
+        }
+        prune(&mut inner.samples, at, self.window);
+    }
+
+    pub fn snapshot(&self) -> SourceStatsSnapshot {
+        self.snapshot_at(Instant::now())
+    }
+
+    pub fn snapshot_at(&self, now: Instant) -> SourceStatsSnapshot {
+        let mut inner = self.inner.lock().unwrap();
+        prune(&mut inner.samples, now, self.window);
+
+        let success_count = inner.samples.iter().filter(|s| s.success).count() as u64;
+        let failure_count = inner.samples.len() as u64 - success_count;
+        let error_rate = if inner.samples.is_empty() {
+            0.0
+        } else {
+            failure_count as f64 / inner.samples.len() as f64
+        };
+
+        let mut latencies: Vec<u64> = inner.samples.iter().map(|s| s.latency.as_millis() as u64).collect();
+        latencies.sort_unstable();
+
+        SourceStatsSnapshot {
+            window_seconds: self.window.as_secs(),
+            success_count,
+            failure_count,
+            error_rate,
+            latency: summarize_latency(&latencies),
+            last_success: inner.last_success,
+        }
+    }
+}
+
+fn prune(samples: &mut VecDeque<Sample>, now: Instant, window: Duration) {
+    while samples.front().is_some_and(|s| now.saturating_duration_since(s.at) > window) {
+        samples.pop_front();
+    }
+}
+
+// Nearest-rank percentiles over sorted latencies
+fn summarize_latency(sorted: &[u64]) -> Option<LatencySummary> {
+    let max_ms = *sorted.last()?;
+    let percentile = |p: f64| sorted[((p * sorted.len() as f64).ceil() as usize).clamp(1, sorted.len()) - 1];
+
+    let mut histogram: Vec<LatencyBucket> = LATENCY_BUCKETS_MS.iter()
+        .map(|&le| LatencyBucket { le_ms: Some(le), count: 0 })
+        .chain(std::iter::once(LatencyBucket { le_ms: None, count: 0 }))
+        .collect();
+    for &latency in sorted {
+        let bucket = LATENCY_BUCKETS_MS.iter().position(|&le| latency <= le).unwrap_or(LATENCY_BUCKETS_MS.len());
+        histogram[bucket].count += 1;
+    }
+
+    Some(LatencySummary {
+        p50_ms: percentile(0.50),
+        p95_ms: percentile(0.95),
+        p99_ms: percentile(0.99),
+        max_ms,
+        histogram,
+    })
+}
+
+/// Hit and miss counts for the aggregated price cache since startup
+#[derive(Default)]
+pub struct CacheStats {
+    hits: AtomicU64,
+    misses: AtomicU64,
+    stale: AtomicU64,
+    coalesced: AtomicU64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CacheStatsSnapshot {
+    pub hits: u64,
+    pub misses: u64,
+    pub hit_rate: f64,
+    /// Served past the TTL while being refreshed; counted apart from hits and misses
+    pub stale: u64,
+    /// Misses that joined a fetch already running instead of starting their own
+    pub coalesced: u64,
+}
+
+impl CacheStats {
+    pub fn record_hit(&self) {
+        self.hits.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn record_miss(&self) {
+        self.misses.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn record_stale(&self) {
+        self.stale.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn record_coalesced(&self) {
+        self.coalesced.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn snapshot(&self) -> CacheStatsSnapshot {
+        let hits = self.hits.load(Ordering::Relaxed);
+        let misses = self.misses.load(Ordering::Relaxed);
+        let total = hits + misses;
+        CacheStatsSnapshot {
+            hits,
+            misses,
+            hit_rate: if total == 0 { 0.0 } else { hits as f64 / total as f64 },
+            stale: self.stale.load(Ordering::Relaxed),
+            coalesced: self.coalesced.load(Ordering::Relaxed),
+        }
+    }
+}
@@ -0,0 +1,271 @@
+use std::collections::HashMap;
+use std::str::FromStr;
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use rust_decimal::Decimal;
+use sqlx::sqlite::{SqliteConnectOptions, SqlitePoolOptions, SqliteRow};
+use sqlx::{QueryBuilder, Row, Sqlite, SqlitePool};
+use crate::aggregation::PriceMode;
+use crate::feed_registry::SymbolFeeds;
+use crate::oracle_client::{AggregatedPrice, SourceObservation};
+use crate::price_store::{unix_now, ManipulationEvent, PriceStore};
+
+/// SQLite tables created by the migrations in `db/sqlite/`. Decimals are kept as text
+/// so they round-trip exactly, and times as unix seconds.
+#[derive(Debug, Clone)]
+pub struct SqliteStore {
+    pool: SqlitePool,
+}
+
+impl SqliteStore {
+    pub async fn connect(url: &str) -> Result<Self> {
+        let options = SqliteConnectOptions::from_str(url)?
+            .create_if_missing(true)
+            .foreign_keys(true);
+        // Every connection to an in-memory database opens a new, empty one
+        let pool = if url.contains(":memory:") || url.contains("mode=memory") {
+            SqlitePoolOptions::new()
+                .max_connections(1)
+                .idle_timeout(None)
+                .max_lifetime(None)
+                .connect_with(options)
+                .await?
+        } else {
+            SqlitePoolOptions::new()
+                .max_connections(5)
+                .connect_with(options)
+                .await?
+        };
+        sqlx::migrate!("../db/sqlite").run(&pool).await?;
+        Ok(Self { pool })
+    }
+
+    /// Source observations of the given `price_feeds` rows, keyed by row id
+    async fn source_observations(&self, price_feed_ids: &[i64]) -> Result<HashMap<i64, Vec<SourceObservation>>> {
+        let mut observations: HashMap<i64, Vec<SourceObservation>> = HashMap::new();
+        if price_feed_ids.is_empty() {
+            return Ok(observations);
+        }
+        let mut query = QueryBuilder::<Sqlite>::new(
+            "SELECT price_feed_id, symbol, source, price, confidence, observed_at, excluded_reason \
+             FROM source_observations WHERE price_feed_id IN "
+        );
+        query.push_tuples(price_feed_ids, |mut row, id| {
+            row.push_bind(*id);
+        });
+        query.push(" ORDER BY id");
+        for row in query.build().fetch_all(&self.pool).await? {
+            let confidence: Option<String> = row.try_get("confidence")?;
+            observations.entry(row.try_get("price_feed_id")?).or_default().push(SourceObservation {
+                symbol: row.try_get("symbol")?,
+                source: row.try_get("source")?,
+                price: decimal(&row, "price")?,
+                confidence: confidence.map(|c| parse_decimal(&c)).transpose()?,
+                timestamp: row.try_get("observed_at")?,
+                excluded_reason: row.try_get("excluded_reason")?,
+            });
+        }
+        Ok(observations)
+    }
+}
+
+fn parse_decimal(value: &str) -> Result<Decimal> {
+    Decimal::from_str(value).with_context(|| format!("invalid decimal {:?} in SQLite store", value))
+}
+
+fn decimal(row: &SqliteRow, column: &str) -> Result<Decimal> {
+    parse_decimal(row.try_get(column)?)
+}
+
+#[async_trait]
+impl PriceStore for SqliteStore {
+    async fn insert_price(&self, price: &AggregatedPrice) -> Result<()> {
+        let mut tx = self.pool.begin().await?;
+        let price_feed_id: i64 = sqlx::query(
+            r#"
+            INSERT INTO price_feeds (symbol, mark_price, index_price, confidence, source_count, mode, created_at)
+            VALUES (?, ?, ?, ?, ?, ?, ?)
+            RETURNING id
+            "#
+        )
+        .bind(&price.symbol)
+        .bind(price.mark_price.to_string())
+        .bind(price.index_price.to_string())
+        .bind(price.confidence.to_string())
+        .bind(price.sources.len() as i32)
+        .bind(price.mode.as_str())
+        .bind(unix_now())
+        .fetch_one(&mut *tx)
+        .await?
+        .try_get("id")?;
+
+        let observations = price.observations();
+        if !observations.is_empty() {
+            let mut insert = QueryBuilder::<Sqlite>::new(
+                "INSERT INTO source_observations (price_feed_id, symbol, source, price, confidence, observed_at, excluded_reason) "
+            );
+            insert.push_values(&observations, |mut row, observation| {
+                row.push_bind(price_feed_id)
+                    .push_bind(observation.symbol.clone())
+                    .push_bind(observation.source.clone())
+                    .push_bind(observation.price.to_string())
+                    .push_bind(observation.confidence.map(|c| c.to_string()))
+                    .push_bind(observation.timestamp)
+                    .push_bind(observation.excluded_reason.clone());
+            });
+            insert.build().execute(&mut *tx).await?;
+        }
+        tx.commit().await?;
+        Ok(())
+    }
+
+    async fn price_history(&self, symbol: &str, since: i64, limit: usize) -> Result<Vec<AggregatedPrice>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT id, symbol, mark_price, index_price, confidence, mode, created_at
+            FROM price_feeds
+            WHERE symbol = ? AND created_at >= ?
+            ORDER BY created_at DESC, id DESC
+            LIMIT ?
+            "#
+        )
+        .bind(symbol)
+        .bind(since)
+        .bind(limit.min(i64::MAX as usize) as i64)
+        .fetch_all(&self.pool)
+        .await?;
+
+        let ids = rows.iter().map(|row| row.try_get("id")).collect::<Result<Vec<i64>, _>>()?;
+        let mut observations = self.source_observations(&ids).await?;
+
+        rows.into_iter().map(|row| {
+            let price = AggregatedPrice {
+                symbol: row.try_get("symbol")?,
+                mark_price: decimal(&row, "mark_price")?,
+                index_price: decimal(&row, "index_price")?,
+                confidence: decimal(&row, "confidence")?,
+                sources: vec![],
+                timestamp: row.try_get("created_at")?,
+                excluded_sources: vec![],
+                mode: PriceMode::parse(row.try_get("mode")?)?,
+                depegged_quotes: vec![],
+            };
+            let id: i64 = row.try_get("id")?;
+            Ok(price.with_observations(observations.remove(&id).unwrap_or_default()))
+        }).collect()
+    }
+
+    async fn average_price(&self, symbol: &str, since: i64) -> Result<Option<Decimal>> {
+        // Averaged here rather than with AVG(), which would go through floating point
+        let rows = sqlx::query("SELECT mark_price FROM price_feeds WHERE symbol = ? AND created_at >= ?")
+            .bind(symbol)
+            .bind(since)
+            .fetch_all(&self.pool)
+            .await?;
+        if rows.is_empty() {
+            return Ok(None);
+        }
+        let total = rows.iter().map(|row| decimal(row, "mark_price")).sum::<Result<Decimal>>()?;
+        Ok(Some(total / Decimal::from(rows.len())))
+    }
+
+    async fn count_prices(&self, symbol: &str, since: i64) -> Result<usize> {
+        let count: i64 = sqlx::query(
+            "SELECT COUNT(*) AS data_points FROM price_feeds WHERE symbol = ? AND created_at >= ?"
+        )
+        .bind(symbol)
+        .bind(since)
+        .fetch_one(&self.pool)
+        .await?
+        .try_get("data_points")?;
+        Ok(count as usize)
+    }
+
+    async fn insert_manipulation_event(&self, event: &ManipulationEvent) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO manipulation_events (symbol, price, confidence, manipulation_score, created_at)
+            VALUES (?, ?, ?, ?, ?)
+            "#
+        )
+        .bind(&event.symbol)
+        .bind(event.price.to_string())
+        .bind(event.confidence.to_string())
+        .bind(event.manipulation_score)
+        .bind(event.timestamp)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    async fn manipulation_events(&self, symbol: &str, since: i64) -> Result<Vec<ManipulationEvent>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT symbol, price, confidence, manipulation_score, created_at
+            FROM manipulation_events
+            WHERE symbol = ? AND created_at >= ?
+            ORDER BY created_at DESC, id DESC
+            "#
+        )
+        .bind(symbol)
+        .bind(since)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.into_iter().map(|row| Ok(ManipulationEvent {
+            symbol: row.try_get("symbol")?,
+            price: decimal(&row, "price")?,
+            confidence: decimal(&row, "confidence")?,
+            manipulation_score: row.try_get("manipulation_score")?,
+            timestamp: row.try_get("created_at")?,
+        })).collect()
+    }
+
+    async fn save_symbol(&self, symbol: &str, feeds: &SymbolFeeds) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO trading_symbols (symbol, pyth_feed_id, pyth_price_account, switchboard_address, created_at)
+            VALUES (?, ?, ?, ?, ?)
+            ON CONFLICT (symbol) DO UPDATE
+            SET pyth_feed_id = excluded.pyth_feed_id,
+                pyth_price_account = excluded.pyth_price_account,
+                switchboard_address = excluded.switchboard_address
+            "#
+        )
+        .bind(symbol)
+        .bind(&feeds.pyth_feed_id)
+        .bind(&feeds.pyth_price_account)
+        .bind(&feeds.switchboard_address)
+        .bind(unix_now())
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    async fn remove_symbol(&self, symbol: &str) -> Result<()> {
+        sqlx::query("DELETE FROM trading_symbols WHERE symbol = ?")
+            .bind(symbol)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    async fn saved_symbols(&self) -> Result<Vec<(String, SymbolFeeds)>> {
+        let rows = sqlx::query(
+            "SELECT symbol, pyth_feed_id, pyth_price_account, switchboard_address FROM trading_symbols ORDER BY created_at, rowid"
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.into_iter().map(|row| Ok((row.try_get("symbol")?, SymbolFeeds {
+            pyth_feed_id: row.try_get("pyth_feed_id")?,
+            pyth_price_account: row.try_get("pyth_price_account")?,
+            switchboard_address: row.try_get("switchboard_address")?,
+        }))).collect()
+    }
+
+    async fn check_health(&self) -> Result<()> {
+        sqlx::query("SELECT 1").fetch_one(&self.pool).await?;
+        Ok(())
+    }
+}
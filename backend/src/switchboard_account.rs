@@ -0,0 +1,89 @@
+use rust_decimal::Decimal;
+use thiserror::Error;
+
+// Anchor discriminator: sha256("account:AggregatorAccountData")[..8]
+const AGGREGATOR_DISCRIMINATOR: [u8; 8] = [217, 230, 65, 101, 201, 162, 27, 125];
+
+// Byte offsets into the packed Switchboard V2 AggregatorAccountData layout
+// (discriminator included). latest_confirmed_round starts after crank_pubkey.
+const LATEST_CONFIRMED_ROUND_OFFSET: usize = 341;
+const ROUND_NUM_SUCCESS_OFFSET: usize = 0;
+const ROUND_OPEN_SLOT_OFFSET: usize = 9;
+const ROUND_OPEN_TIMESTAMP_OFFSET: usize = 17;
+const ROUND_RESULT_OFFSET: usize = 25;
+const ROUND_STD_DEVIATION_OFFSET: usize = 45;
+const SWITCHBOARD_DECIMAL_SIZE: usize = 20; // i128 mantissa + u32 scale
+pub const AGGREGATOR_ACCOUNT_SIZE: usize = 3851;
+
+#[derive(Debug, Error, PartialEq)]
+pub enum SwitchboardError {
+    #[error("aggregator account {0} not found")]
+    AccountNotFound(String),
+    #[error("invalid account encoding: {0}")]
+    InvalidEncoding(String),
+    #[error("account is {0} bytes, expected {AGGREGATOR_ACCOUNT_SIZE}")]
+    AccountTooSmall(usize),
+    #[error("account is not a Switchboard aggregator")]
+    InvalidDiscriminator,
+    #[error("aggregator has no confirmed round")]
+    NoConfirmedRound,
+    #[error("decimal scale {0} is out of range")]
+    InvalidScale(u32),
+    #[error("decimal mantissa {0} is out of range")]
+    MantissaOutOfRange(i128),
+    #[error("aggregator result {0} is not a positive price")]
+    NonPositiveResult(Decimal),
+}
+
+/// Latest confirmed round of a Switchboard aggregator
+#[derive(Debug, Clone, PartialEq)]
+pub struct AggregatorRound {
+    pub num_success: u32,
+    pub round_open_slot: u64,
+    pub round_open_timestamp: i64,
+    pub result: Decimal,
+    pub std_deviation: Decimal,
+}
+
+pub fn decode_aggregator_account(data: &[u8]) -> Result<AggregatorRound, SwitchboardError> {
+    if data.len() < AGGREGATOR_ACCOUNT_SIZE {
+        return Err(SwitchboardError::AccountTooSmall(data.len()));
+    }
+    if data[..8] != AGGREGATOR_DISCRIMINATOR {
+        return Err(SwitchboardError::InvalidDiscriminator);
+    }
+
+    let round = &data[LATEST_CONFIRMED_ROUND_OFFSET..];
+    let num_success = u32::from_le_bytes(read_array(round, ROUND_NUM_SUCCESS_OFFSET));
+    if num_success == 0 {
+        return Err(SwitchboardError::NoConfirmedRound);
+    }
+
+    let result = read_decimal(round, ROUND_RESULT_OFFSET)?;
+    if result <= Decimal::ZERO {
+        return Err(SwitchboardError::NonPositiveResult(result));
+    }
+
+    Ok(AggregatorRound {
+        num_success,
+        round_open_slot: u64::from_le_bytes(read_array(round, ROUND_OPEN_SLOT_OFFSET)),
+        round_open_timestamp: i64::from_le_bytes(read_array(round, ROUND_OPEN_TIMESTAMP_OFFSET)),
+        result,
+        std_deviation: read_decimal(round, ROUND_STD_DEVIATION_OFFSET)?.abs(),
+    })
+}
+
+fn read_array<const N: usize>(data: &[u8], offset: usize) -> [u8; N] {
+    data[offset..offset + N].try_into().expect("slice length checked by caller")
+}
+
+// SwitchboardDecimal { mantissa: i128, scale: u32 } => mantissa / 10^scale
+fn read_decimal(data: &[u8], offset: usize) -> Result<Decimal, SwitchboardError> {
+    let raw = &data[offset..offset + SWITCHBOARD_DECIMAL_SIZE];
+    let mantissa = i128::from_le_bytes(read_array(raw, 0));
+    let scale = u32::from_le_bytes(read_array(raw, 16));
+    if scale > 28 {
+        return Err(SwitchboardError::InvalidScale(scale));
+    }
+    Decimal::try_from_i128_with_scale(mantissa, scale).map_err(|_| SwitchboardError::MantissaOutOfRange(mantissa))
+}
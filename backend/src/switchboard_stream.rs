@@ -0,0 +1,218 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use anyhow::{Result, anyhow};
+use async_trait::async_trait;
+use futures::{SinkExt, StreamExt};
+use log::{debug, info, warn};
+use tokio::sync::RwLock;
+use tokio::task::JoinHandle;
+use tokio_tungstenite::{connect_async, tungstenite::Message};
+use crate::feed_registry::SharedFeedRegistry;
+use crate::oracle_client::{OracleClient, PriceData, SwitchboardClient};
+
+const INITIAL_BACKOFF: Duration = Duration::from_millis(500);
+const MAX_BACKOFF: Duration = Duration::from_secs(30);
+// How often an open socket checks whether symbols were added or removed
+const FEED_CHECK_INTERVAL: Duration = Duration::from_secs(1);
+// Solana RPC nodes drop websocket connections that stay silent too long
+const PING_INTERVAL: Duration = Duration::from_secs(30);
+
+type LatestPrices = Arc<RwLock<HashMap<String, PriceData>>>;
+
+enum SocketEnd {
+    FeedsChanged,
+    Closed,
+}
+
+/// `accountSubscribe` listener serving the latest decoded round per aggregator.
+/// Holds no prices while disconnected, so callers fall back to polling.
+pub struct SwitchboardStreamClient {
+    latest: LatestPrices,
+    registry: SharedFeedRegistry,
+    max_price_age: Duration,
+    task: JoinHandle<()>,
+}
+
+impl SwitchboardStreamClient {
+    /// Subscribes to every registered aggregator; the socket closes when the client is dropped
+    pub fn start(ws_url: String, commitment: String, registry: SharedFeedRegistry, max_price_age: Duration) -> Self {
+        let latest = LatestPrices::default();
+        let task = tokio::spawn(run_socket(ws_url, commitment, registry.clone(), latest.clone()));
+
+        Self { latest, registry, max_price_age, task }
+    }
+}
+
+impl Drop for SwitchboardStreamClient {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+#[async_trait]
+impl OracleClient for SwitchboardStreamClient {
+    async fn get_price(&self, symbol: &str) -> Result<PriceData> {
+        // Older rounds would be left out of the aggregate anyway; polling may do better
+        let source_age = self.registry.read().await.price_bounds(symbol).max_source_age_seconds;
+        let max_age = self.max_price_age.as_secs().min(source_age) as i64;
+
+        let latest = self.latest.read().await;
+        let price = latest
+            .get(symbol)
+            .ok_or_else(|| anyhow!("No streamed Switchboard price for {}", symbol))?;
+
+        let now = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs() as i64;
+        let age = now - price.timestamp;
+        if age > max_age {
+            return Err(anyhow!("Streamed Switchboard price for {} is {}s old", symbol, age));
+        }
+
+        Ok(price.clone())
+    }
+
+    async fn get_multiple_prices(&self, symbols: &[String]) -> Result<Vec<PriceData>> {
+        let mut results = Vec::new();
+        for symbol in symbols {
+            match self.get_price(symbol).await {
+                Ok(price_data) => results.push(price_data),
+                Err(e) => debug!("{}", e),
+            }
+        }
+        Ok(results)
+    }
+
+    fn get_name(&self) -> &str {
+        "Switchboard"
+    }
+
+    async fn supports(&self, symbol: &str) -> bool {
+        self.registry.read().await.switchboard_address(symbol).is_some()
+    }
+}
+
+async fn run_socket(ws_url: String, commitment: String, registry: SharedFeedRegistry, latest: LatestPrices) {
+    let mut backoff = INITIAL_BACKOFF;
+    loop {
+        match subscribe_once(&ws_url, &commitment, &registry, &latest, &mut backoff).await {
+            Ok(SocketEnd::FeedsChanged) => {
+                info!("Switchboard aggregators changed, resubscribing");
+                continue;
+            }
+            Ok(SocketEnd::Closed) => info!("Switchboard socket closed, reconnecting in {:?}", backoff),
+            Err(e) => warn!("Switchboard socket failed: {}, reconnecting in {:?}", e, backoff),
+        }
+
+        // Nothing is pushed while disconnected; hand over to polling
+        latest.write().await.clear();
+        tokio::time::sleep(backoff).await;
+        backoff = (backoff * 2).min(MAX_BACKOFF);
+    }
+}
+
+async fn subscribe_once(
+    ws_url: &str,
+    commitment: &str,
+    registry: &SharedFeedRegistry,
+    latest: &LatestPrices,
+    backoff: &mut Duration,
+) -> Result<SocketEnd> {
+    let addresses = registry.read().await.switchboard_addresses().clone();
+    latest.write().await.retain(|symbol, _| addresses.contains_key(symbol));
+    if addresses.is_empty() {
+        return Err(anyhow!("no Switchboard aggregators registered"));
+    }
+
+    let (mut socket, _) = connect_async(ws_url).await?;
+
+    // Request ids index into `feeds`; confirmations map subscription ids back to them
+    let feeds: Vec<(&str, &str)> = addresses.iter()
+        .map(|(symbol, address)| (symbol.as_str(), address.as_str()))
+        .collect();
+    for (id, (_, address)) in feeds.iter().enumerate() {
+        let request = serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "method": "accountSubscribe",
+            "params": [address, { "encoding": "base64", "commitment": commitment }]
+        });
+        socket.send(Message::Text(request.to_string())).await?;
+    }
+    info!("Subscribed to {} Switchboard aggregators over websocket", feeds.len());
+
+    let mut subscriptions: HashMap<u64, (&str, &str)> = HashMap::new();
+    let mut feed_check = tokio::time::interval(FEED_CHECK_INTERVAL);
+    let mut ping = tokio::time::interval(PING_INTERVAL);
+    loop {
+        tokio::select! {
+            message = socket.next() => {
+                let text = match message {
+                    None | Some(Ok(Message::Close(_))) => return Ok(SocketEnd::Closed),
+                    Some(Ok(Message::Text(text))) => text,
+                    Some(Ok(_)) => continue,
+                    Some(Err(e)) => return Err(e.into()),
+                };
+
+                if handle_message(&text, &feeds, &mut subscriptions, latest).await {
+                    *backoff = INITIAL_BACKOFF;
+                }
+            }
+            _ = feed_check.tick() => {
+                if *registry.read().await.switchboard_addresses() != addresses {
+                    return Ok(SocketEnd::FeedsChanged);
+                }
+            }
+            _ = ping.tick() => {
+                socket.send(Message::Ping(Vec::new())).await?;
+            }
+        }
+    }
+}
+
+/// Handles a subscription confirmation or account notification; true if a price was updated
+async fn handle_message<'a>(
+    text: &str,
+    feeds: &[(&'a str, &'a str)],
+    subscriptions: &mut HashMap<u64, (&'a str, &'a str)>,
+    latest: &LatestPrices,
+) -> bool {
+    let message: serde_json::Value = match serde_json::from_str(text) {
+        Ok(message) => message,
+        Err(e) => {
+            warn!("Ignoring malformed Switchboard socket message: {}", e);
+            return false;
+        }
+    };
+
+    // Response to one of our accountSubscribe requests
+    if let Some(feed) = message["id"].as_u64().and_then(|id| feeds.get(id as usize)) {
+        match message["result"].as_u64() {
+            Some(subscription) => {
+                subscriptions.insert(subscription, *feed);
+            }
+            None => warn!("accountSubscribe failed for {}: {}", feed.0, message["error"]),
+        }
+        return false;
+    }
+
+    if message["method"] != "accountNotification" {
+        return false;
+    }
+    let params = &message["params"];
+    let Some(&(symbol, address)) = params["subscription"].as_u64().and_then(|id| subscriptions.get(&id)) else {
+        return false;
+    };
+
+    // Notification results have the same shape as a getAccountInfo result
+    let response = serde_json::json!({ "result": params["result"] });
+    match SwitchboardClient::parse_account_info(symbol, address, &response) {
+        Ok(price) => {
+            latest.write().await.insert(symbol.to_string(), price);
+            true
+        }
+        Err(e) => {
+            warn!("Ignoring undecodable Switchboard update for {}: {}", symbol, e);
+            false
+        }
+    }
+}
@@ -0,0 +1,70 @@
+use anyhow::{Result, anyhow};
+use rust_decimal::Decimal;
+use crate::aggregation::PriceMode;
+use crate::oracle_client::AggregatedPrice;
+
+/// Symbol priced from other symbols rather than its own feeds, e.g.
+/// ETH/BTC = ETH/USD / BTC/USD
+#[derive(Debug, Clone, PartialEq)]
+pub struct SyntheticPair {
+    pub multiply: Vec<String>,
+    pub divide: Vec<String>,
+}
+
+impl SyntheticPair {
+    pub fn legs(&self) -> impl Iterator<Item = &String> {
+        self.multiply.iter().chain(&self.divide)
+    }
+
+    /// Product of the `multiply` legs over the product of the `divide` legs, given
+    /// their prices in the same order.
+    ///
+    /// Relative confidences add, the first-order bound that also holds when the legs
+    /// are correlated (they usually share a quote currency). The timestamp is the
+    /// oldest leg's, and a degraded leg degrades the result.
+    pub fn combine(&self, symbol: &str, multiply: &[AggregatedPrice], divide: &[AggregatedPrice]) -> Result<AggregatedPrice> {
+        if multiply.len() != self.multiply.len() || divide.len() != self.divide.len() {
+            return Err(anyhow!("Expected {} legs for {}", self.multiply.len() + self.divide.len(), symbol));
+        }
+
+        let mut mark_price = Decimal::ONE;
+        let mut index_price = Decimal::ONE;
+        let mut relative_confidence = Decimal::ZERO;
+        for (leg, inverted) in multiply.iter().map(|l| (l, false)).chain(divide.iter().map(|l| (l, true))) {
+            if leg.mark_price <= Decimal::ZERO || leg.index_price <= Decimal::ZERO {
+                return Err(anyhow!("{} leg {} has non-positive price {}", symbol, leg.symbol, leg.mark_price));
+            }
+            let overflow = || anyhow!("{} overflows at leg {}", symbol, leg.symbol);
+            if inverted {
+                mark_price = mark_price.checked_div(leg.mark_price).ok_or_else(overflow)?;
+                index_price = index_price.checked_div(leg.index_price).ok_or_else(overflow)?;
+            } else {
+                mark_price = mark_price.checked_mul(leg.mark_price).ok_or_else(overflow)?;
+                index_price = index_price.checked_mul(leg.index_price).ok_or_else(overflow)?;
+            }
+            relative_confidence += leg.confidence.abs() / leg.mark_price;
+        }
+
+        let legs = || multiply.iter().chain(divide);
+        Ok(AggregatedPrice {
+            symbol: symbol.to_string(),
+            mark_price,
+            index_price,
+            confidence: mark_price * relative_confidence,
+            sources: legs().flat_map(|leg| leg.sources.iter().cloned()).collect(),
+            timestamp: legs().map(|leg| leg.timestamp).min().unwrap_or_default(),
+            excluded_sources: legs().flat_map(|leg| leg.excluded_sources.iter().cloned()).collect(),
+            mode: worst_mode(legs().map(|leg| leg.mode)),
+            depegged_quotes: legs().flat_map(|leg| leg.depegged_quotes.iter().cloned()).collect(),
+        })
+    }
+}
+
+// A last good leg makes the whole price historical; otherwise any single-source leg shows through
+fn worst_mode(modes: impl Iterator<Item = PriceMode>) -> PriceMode {
+    modes.fold(PriceMode::Normal, |worst, mode| match (worst, mode) {
+        (PriceMode::LastGood, _) | (_, PriceMode::LastGood) => PriceMode::LastGood,
+        (PriceMode::SingleSource, _) | (_, PriceMode::SingleSource) => PriceMode::SingleSource,
+        _ => PriceMode::Normal,
+    })
+}
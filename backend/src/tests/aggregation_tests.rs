@@ -0,0 +1,181 @@
+#[cfg(test)]
+mod aggregation_tests {
+    use crate::aggregation::{
+        AggregationMethod, AggregationStrategy, InverseVariance, Median, OutlierFilter, SourcePrice, TrimmedMean,
+        WeightedMedian,
+    };
+    use crate::config::OracleFileConfig;
+    use crate::feed_registry::FeedRegistry;
+    use rust_decimal::Decimal;
+    use rust_decimal_macros::dec;
+
+    fn source(price: Decimal, confidence: Decimal, weight: f64) -> SourcePrice {
+        SourcePrice { price, confidence, weight }
+    }
+
+    #[test]
+    fn test_median() {
+        let odd = [source(dec!(101.0), dec!(1.0), 1.0), source(dec!(99.0), dec!(1.0), 1.0), source(dec!(250.0), dec!(1.0), 1.0)];
+        assert_eq!(Median.aggregate(&odd).unwrap(), dec!(101.0));
+
+        // Weights and confidence are ignored; even counts average the middle pair
+        let even = [source(dec!(100.0), dec!(50.0), 0.1), source(dec!(102.0), dec!(0.0), 0.9), source(dec!(98.0), dec!(1.0), 0.5), source(dec!(500.0), dec!(1.0), 0.5)];
+        assert_eq!(Median.aggregate(&even).unwrap(), dec!(101.0));
+
+        assert_eq!(Median.aggregate(&[source(dec!(42.0), dec!(1.0), 1.0)]).unwrap(), dec!(42.0));
+        assert!(Median.aggregate(&[]).is_err());
+    }
+
+    #[test]
+    fn test_weighted_median() {
+        // Pyth 0.6 outweighs the other two combined
+        let prices = [source(dec!(100.0), dec!(1.0), 0.6), source(dec!(105.0), dec!(1.0), 0.2), source(dec!(110.0), dec!(1.0), 0.2)];
+        assert_eq!(WeightedMedian.aggregate(&prices).unwrap(), dec!(100.0));
+
+        let balanced = [source(dec!(100.0), dec!(1.0), 0.3), source(dec!(105.0), dec!(1.0), 0.4), source(dec!(110.0), dec!(1.0), 0.3)];
+        assert_eq!(WeightedMedian.aggregate(&balanced).unwrap(), dec!(105.0));
+
+        // Cumulative weight landing exactly on half averages the neighbours
+        let split = [source(dec!(100.0), dec!(1.0), 0.5), source(dec!(110.0), dec!(1.0), 0.5)];
+        assert_eq!(WeightedMedian.aggregate(&split).unwrap(), dec!(105.0));
+
+        // Zero-weight sources don't count
+        let muted = [source(dec!(100.0), dec!(1.0), 0.0), source(dec!(110.0), dec!(1.0), 0.2)];
+        assert_eq!(WeightedMedian.aggregate(&muted).unwrap(), dec!(110.0));
+        assert!(WeightedMedian.aggregate(&[source(dec!(100.0), dec!(1.0), 0.0)]).is_err());
+    }
+
+    #[test]
+    fn test_trimmed_mean() {
+        let prices = [
+            source(dec!(90.0), dec!(1.0), 1.0),
+            source(dec!(100.0), dec!(1.0), 1.0),
+            source(dec!(101.0), dec!(1.0), 1.0),
+            source(dec!(102.0), dec!(1.0), 1.0),
+            source(dec!(200.0), dec!(1.0), 1.0),
+        ];
+        // floor(5 * 0.2) = 1 dropped from each end
+        assert_eq!(TrimmedMean { trim_fraction: 0.2 }.aggregate(&prices).unwrap(), dec!(101.0));
+        assert_eq!(TrimmedMean { trim_fraction: 0.0 }.aggregate(&prices).unwrap(), dec!(118.6));
+
+        // Too few sources to trim anything
+        let pair = [source(dec!(100.0), dec!(1.0), 1.0), source(dec!(110.0), dec!(1.0), 1.0)];
+        assert_eq!(TrimmedMean { trim_fraction: 0.2 }.aggregate(&pair).unwrap(), dec!(105.0));
+    }
+
+    #[test]
+    fn test_inverse_variance_uses_relative_confidence() {
+        // Same 0.1% relative confidence at very different dollar confidences: equal weights
+        let prices = [source(dec!(100.0), dec!(0.1), 1.0), source(dec!(102.0), dec!(0.102), 1.0)];
+        let equal = InverseVariance.aggregate(&prices).unwrap();
+        assert_eq!(equal, dec!(101.0));
+
+        // Half the relative confidence interval = four times the weight
+        let prices = [source(dec!(100.0), dec!(0.05), 1.0), source(dec!(110.0), dec!(0.11), 1.0)];
+        assert_eq!(InverseVariance.aggregate(&prices).unwrap(), dec!(102.0));
+
+        // Configured weights still scale the result
+        let prices = [source(dec!(100.0), dec!(0.1), 0.25), source(dec!(110.0), dec!(0.11), 1.0)];
+        assert_eq!(InverseVariance.aggregate(&prices).unwrap(), dec!(108.0));
+
+        // A zero confidence is floored rather than dividing by zero
+        let prices = [source(dec!(100.0), dec!(0.0), 1.0), source(dec!(110.0), dec!(0.11), 1.0)];
+        assert!((InverseVariance.aggregate(&prices).unwrap() - dec!(100)).abs() < dec!(0.001));
+    }
+
+    #[test]
+    fn test_non_positive_prices_are_rejected() {
+        let prices = [source(dec!(100.0), dec!(1.0), 1.0), source(dec!(-1.0), dec!(1.0), 1.0)];
+        for method in [
+            AggregationMethod::Median,
+            AggregationMethod::WeightedMedian,
+            AggregationMethod::TrimmedMean,
+            AggregationMethod::InverseVariance,
+        ] {
+            assert!(method.strategy(0.2).aggregate(&prices).is_err(), "{:?}", method);
+        }
+    }
+
+    #[test]
+    fn test_strategy_is_selected_per_symbol() {
+        let registry = crate::tests::dev_registry();
+        assert_eq!(registry.aggregation_method("BTC/USD"), AggregationMethod::WeightedMedian);
+        assert_eq!(registry.aggregation_method("AVAX/USD"), AggregationMethod::Median);
+        assert_eq!(registry.aggregation_strategy("AVAX/USD").name(), "median");
+
+        let contents = std::fs::read_to_string("../config/dev.toml").unwrap()
+            .replace("aggregation = \"weighted_median\"", "aggregation = \"trimmed_mean\"");
+        let registry = FeedRegistry::from_config(&OracleFileConfig::parse(&contents).unwrap()).unwrap();
+        assert_eq!(registry.aggregation_method("ETH/USD"), AggregationMethod::TrimmedMean);
+        assert_eq!(registry.aggregation_method("AVAX/USD"), AggregationMethod::Median);
+
+        let invalid = contents.replace("trim_fraction = 0.2", "trim_fraction = 0.5");
+        let err = FeedRegistry::from_config(&OracleFileConfig::parse(&invalid).unwrap()).unwrap_err();
+        assert!(err.to_string().contains("trim_fraction"));
+        assert!(OracleFileConfig::parse(&contents.replace("\"trimmed_mean\"", "\"mean\"")).is_err());
+    }
+
+    fn prices(values: &[Decimal]) -> Vec<SourcePrice> {
+        values.iter().map(|&price| source(price, price * dec!(0.001), 1.0)).collect()
+    }
+
+    fn excluded_indices(filter: &OutlierFilter, values: &[Decimal]) -> Vec<usize> {
+        filter.outliers(&prices(values)).unwrap().into_iter().map(|(i, _)| i).collect()
+    }
+
+    #[test]
+    fn test_mad_outliers_are_excluded() {
+        let filter = OutlierFilter::default();
+
+        // 3% off is within the hard cap but far outside the others' spread
+        let outliers = filter.outliers(&prices(&[dec!(100.0), dec!(100.1), dec!(99.9), dec!(103.0), dec!(100.05)])).unwrap();
+        assert_eq!(outliers.len(), 1);
+        assert_eq!(outliers[0].0, 3);
+        assert!(outliers[0].1.contains("scaled MADs"));
+
+        // Past max_deviation regardless of the spread
+        let outliers = filter.outliers(&prices(&[dec!(100.0), dec!(100.2), dec!(110.0)])).unwrap();
+        assert_eq!(outliers.iter().map(|(i, _)| *i).collect::<Vec<_>>(), vec![2]);
+        assert!(outliers[0].1.contains("from median 100.2"));
+
+        assert!(excluded_indices(&filter, &[dec!(100.0), dec!(100.2), dec!(99.8), dec!(100.1)]).is_empty());
+    }
+
+    #[test]
+    fn test_small_deviations_survive_a_zero_mad() {
+        let filter = OutlierFilter::default();
+
+        // Identical prices make the MAD zero; 0.3% is still under min_deviation
+        assert!(excluded_indices(&filter, &[dec!(100.0), dec!(100.0), dec!(100.0), dec!(100.3)]).is_empty());
+        assert_eq!(excluded_indices(&filter, &[dec!(100.0), dec!(100.0), dec!(100.0), dec!(101.0)]), vec![3]);
+    }
+
+    #[test]
+    fn test_outliers_must_be_a_minority() {
+        let filter = OutlierFilter::default();
+
+        // Neither of two sources can outvote the other; past the hard cap the pair fails
+        assert!(excluded_indices(&filter, &[dec!(100.0), dec!(103.0)]).is_empty());
+        assert!(filter.outliers(&prices(&[dec!(100.0), dec!(120.0)])).is_err());
+
+        // Two camps, neither a majority
+        let err = filter.outliers(&prices(&[dec!(100.0), dec!(100.0), dec!(120.0), dec!(120.0)])).unwrap_err();
+        assert!(err.to_string().contains("4 of 4"));
+
+        assert!(excluded_indices(&filter, &[dec!(100.0)]).is_empty());
+    }
+
+    #[test]
+    fn test_outlier_config_is_validated() {
+        let registry = crate::tests::dev_registry();
+        assert_eq!(registry.outlier_filter("BTC/USD"), OutlierFilter { mad_multiplier: 3.0, min_deviation: 0.005, max_deviation: 0.05 });
+
+        let contents = std::fs::read_to_string("../config/dev.toml").unwrap();
+        let inverted = contents.replace("min_deviation_bps = 50", "min_deviation_bps = 500");
+        let err = FeedRegistry::from_config(&OracleFileConfig::parse(&inverted).unwrap()).unwrap_err();
+        assert!(err.to_string().contains("min_deviation_bps"));
+
+        let negative = contents.replace("mad_multiplier = 3.0", "mad_multiplier = -1.0");
+        assert!(FeedRegistry::from_config(&OracleFileConfig::parse(&negative).unwrap()).is_err());
+    }
+}
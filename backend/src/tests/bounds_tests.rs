@@ -0,0 +1,131 @@
+#[cfg(test)]
+mod bounds_tests {
+    use crate::aggregation::PriceMode;
+    use crate::bounds::PriceBounds;
+    use crate::config::{CacheConfig, OracleFileConfig};
+    use crate::feed_registry::FeedRegistry;
+    use crate::memory_store::MemoryStore;
+    use crate::oracle_client::{OracleClient, OracleManager, PriceData};
+    use crate::price_store::unix_now;
+    use anyhow::Result;
+    use async_trait::async_trait;
+    use rust_decimal::Decimal;
+    use rust_decimal_macros::dec;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_violations() {
+        let bounds = PriceBounds { min_price: dec!(1000), max_price: Some(dec!(10000000)), ..PriceBounds::default() };
+        assert_eq!(bounds.violation(dec!(67423.15)), None);
+        // No implicit ceiling: satoshi-denominated or very expensive assets pass
+        assert_eq!(PriceBounds::default().violation(dec!(6742315000)), None);
+
+        assert_eq!(bounds.violation(dec!(999.99)).unwrap(), "price 999.99 below minimum 1000");
+        assert_eq!(bounds.violation(dec!(10000001)).unwrap(), "price 10000001 above maximum 10000000");
+        assert_eq!(PriceBounds::default().violation(dec!(0)).unwrap(), "price 0 is not positive");
+
+        let bounds = PriceBounds { max_age_seconds: 10, max_source_age_seconds: 20, ..PriceBounds::default() };
+        assert!(bounds.is_fresh(10) && !bounds.is_fresh(11));
+        assert!(bounds.is_source_fresh(20) && !bounds.is_source_fresh(21));
+    }
+
+    #[test]
+    fn test_bounds_from_config() {
+        let registry = crate::tests::dev_registry();
+        let btc = registry.price_bounds("BTC/USD");
+        assert_eq!(btc.min_price, dec!(1000));
+        assert_eq!(btc.max_price, Some(dec!(10000000)));
+        assert_eq!(btc.max_age_seconds, 30);
+        assert_eq!(btc.max_deviation, 0.05);
+        assert_eq!(registry.price_bounds("ETH/USD"), PriceBounds::default());
+        // Symbols added at runtime get the defaults too
+        assert_eq!(registry.price_bounds("DOGE/USD"), PriceBounds::default());
+
+        let contents = std::fs::read_to_string("../config/dev.toml").unwrap();
+        let tighter = contents.replace(
+            "bounds = { min_price = \"1000\", max_price = \"10000000\" }",
+            "bounds = { max_age_seconds = 5, max_source_age_seconds = 10, max_deviation_bps = 200 }",
+        );
+        let registry = FeedRegistry::from_config(&OracleFileConfig::parse(&tighter).unwrap()).unwrap();
+        let btc = registry.price_bounds("BTC/USD");
+        assert_eq!((btc.max_age_seconds, btc.max_source_age_seconds, btc.max_price), (5, 10, None));
+        assert_eq!(registry.outlier_filter("BTC/USD").max_deviation, 0.02);
+        assert_eq!(registry.outlier_filter("ETH/USD").max_deviation, 0.05);
+    }
+
+    #[test]
+    fn test_bounds_are_validated() {
+        let contents = std::fs::read_to_string("../config/dev.toml").unwrap();
+        let rejects = |from: &str, to: &str, message: &str| {
+            let config = OracleFileConfig::parse(&contents.replace(from, to)).unwrap();
+            let err = FeedRegistry::from_config(&config).unwrap_err();
+            assert!(err.to_string().contains(message), "{}", err);
+        };
+        let btc = "bounds = { min_price = \"1000\", max_price = \"10000000\" }";
+        rejects(btc, "bounds = { min_price = \"1000\", max_price = \"100\" }", "bounds for BTC/USD: max_price 100 must be above min_price 1000");
+        rejects(btc, "bounds = { min_price = \"-1\" }", "min_price must not be negative");
+        rejects(btc, "bounds = { max_deviation_bps = 50 }", "max_deviation_bps must be above oracle.outliers.min_deviation_bps (50)");
+        rejects("max_source_age_seconds = 30", "max_source_age_seconds = 0", "oracle.bounds: max_age_seconds and max_source_age_seconds must be positive");
+    }
+
+    #[test]
+    fn test_chainlink_rounds_are_fresh_for_their_heartbeat() {
+        let registry = crate::tests::dev_registry();
+        assert_eq!(registry.max_source_age_seconds("BTC/USD", "Chainlink"), 3600);
+        assert_eq!(registry.max_source_age_seconds("BTC/USD", "Pyth"), 30);
+        // No Chainlink feed for SOL/USD
+        assert_eq!(registry.max_source_age_seconds("SOL/USD", "Chainlink"), 30);
+
+        // Without a heartbeat, Chainlink rounds may be as old as oracle.max_price_age_seconds
+        let contents = std::fs::read_to_string("../config/dev.toml").unwrap();
+        let registry = FeedRegistry::from_config(&OracleFileConfig::parse(&contents.replace("heartbeat_seconds = 3600\n", "")).unwrap()).unwrap();
+        assert_eq!(registry.max_source_age_seconds("ETH/USD", "Chainlink"), 60);
+        // A longer per-symbol limit still applies
+        let lenient = contents.replace("max_source_age_seconds = 30", "max_source_age_seconds = 7200");
+        let registry = FeedRegistry::from_config(&OracleFileConfig::parse(&lenient).unwrap()).unwrap();
+        assert_eq!(registry.max_source_age_seconds("ETH/USD", "Chainlink"), 7200);
+    }
+
+    /// Quotes `price` as of `age` seconds ago
+    struct Aged(&'static str, Decimal, i64);
+
+    #[async_trait]
+    impl OracleClient for Aged {
+        async fn get_price(&self, symbol: &str) -> Result<PriceData> {
+            Ok(PriceData { symbol: symbol.to_string(), price: self.1, confidence: dec!(0.5), timestamp: unix_now() - self.2, source: self.0.to_string() })
+        }
+
+        async fn get_multiple_prices(&self, symbols: &[String]) -> Result<Vec<PriceData>> {
+            let mut prices = Vec::new();
+            for symbol in symbols {
+                prices.push(self.get_price(symbol).await?);
+            }
+            Ok(prices)
+        }
+
+        fn get_name(&self) -> &str {
+            self.0
+        }
+    }
+
+    #[tokio::test]
+    async fn test_chainlink_round_within_heartbeat_is_aggregated() {
+        let aggregate = |chainlink_age: i64| async move {
+            let clients: Vec<Box<dyn OracleClient>> = vec![
+                Box::new(Aged("Pyth", dec!(2610), 0)),
+                Box::new(Aged("Chainlink", dec!(2612), chainlink_age)),
+            ];
+            let oracle = OracleManager::new(Arc::new(MemoryStore::default()), crate::tests::dev_registry().into_shared(), clients, &CacheConfig::default());
+            oracle.get_aggregated_price("ETH/USD").await.unwrap()
+        };
+
+        let price = aggregate(600).await;
+        assert_eq!(price.mode, PriceMode::Normal);
+        assert_eq!(price.sources.len(), 2);
+        assert!(price.excluded_sources.is_empty());
+
+        let price = aggregate(7200).await;
+        assert_eq!(price.sources.len(), 1);
+        assert!(price.excluded_sources[0].reason.starts_with("Stale: 720"), "{:?}", price.excluded_sources);
+    }
+}
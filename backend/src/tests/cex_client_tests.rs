@@ -0,0 +1,166 @@
+#[cfg(test)]
+mod cex_client_tests {
+    use crate::cex_client::{CexClient, Exchange};
+    use crate::config::OracleFileConfig;
+    use crate::oracle_client::OracleClient;
+    use axum::extract::{Path, Query};
+    use axum::http::StatusCode;
+    use axum::routing::get;
+    use axum::{Json, Router};
+    use serde_json::{json, Value};
+    use std::collections::HashMap;
+    use tokio::net::TcpListener;
+    use rust_decimal_macros::dec;
+
+    async fn binance_ticker(Query(params): Query<HashMap<String, String>>) -> (StatusCode, Json<Value>) {
+        match params.get("symbol").map(String::as_str) {
+            Some("BTCUSDT") => (StatusCode::OK, Json(json!({
+                "symbol": "BTCUSDT", "bidPrice": "67425.00000000", "bidQty": "1.2",
+                "askPrice": "67425.10000000", "askQty": "0.8"
+            }))),
+            Some("ETHUSDT") => (StatusCode::OK, Json(json!({
+                "symbol": "ETHUSDT", "bidPrice": "2610.50", "bidQty": "10", "askPrice": "2610.40", "askQty": "5"
+            }))),
+            _ => (StatusCode::BAD_REQUEST, Json(json!({ "code": -1121, "msg": "Invalid symbol." }))),
+        }
+    }
+
+    async fn coinbase_ticker(Path(market): Path<String>) -> (StatusCode, Json<Value>) {
+        match market.as_str() {
+            "BTC-USD" => (StatusCode::OK, Json(json!({
+                "ask": "67426.00", "bid": "67424.00", "volume": "8123.5", "trade_id": 812345,
+                "price": "67425.00", "size": "0.01", "time": "2026-10-18T09:30:12.123456Z"
+            }))),
+            _ => (StatusCode::NOT_FOUND, Json(json!({ "message": "NotFound" }))),
+        }
+    }
+
+    async fn kraken_ticker(Query(params): Query<HashMap<String, String>>) -> Json<Value> {
+        match params.get("pair").map(String::as_str) {
+            Some("XBTUSD") => Json(json!({
+                "error": [],
+                "result": { "XXBTZUSD": {
+                    "a": ["67425.20000", "1", "1.000"],
+                    "b": ["67424.80000", "2", "2.000"],
+                    "c": ["67425.00000", "0.01"]
+                }}
+            })),
+            _ => Json(json!({ "error": ["EQuery:Unknown asset pair"], "result": {} })),
+        }
+    }
+
+    /// One local server standing in for all three exchanges
+    async fn spawn_exchange_server() -> String {
+        let app = Router::new()
+            .route("/api/v3/ticker/bookTicker", get(binance_ticker))
+            .route("/products/:market/ticker", get(coinbase_ticker))
+            .route("/0/public/Ticker", get(kraken_ticker));
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+        base_url
+    }
+
+    #[test]
+    fn test_default_market_mapping() {
+        assert_eq!(Exchange::Binance.market_symbol("BTC/USD").unwrap(), "BTCUSDT");
+        assert_eq!(Exchange::Binance.market_symbol("ETH/BTC").unwrap(), "ETHBTC");
+        assert_eq!(Exchange::Binance.market_symbol("USDC/USD").unwrap(), "USDCUSDT");
+        assert!(Exchange::Binance.market_symbol("USDT/USD").is_none());
+        assert_eq!(Exchange::Coinbase.market_symbol("USDT/USD").unwrap(), "USDT-USD");
+        assert_eq!(Exchange::Coinbase.market_symbol("SOL/USD").unwrap(), "SOL-USD");
+        assert_eq!(Exchange::Kraken.market_symbol("BTC/USD").unwrap(), "XBTUSD");
+        assert_eq!(Exchange::Kraken.market_symbol("ETH/USD").unwrap(), "ETHUSD");
+        assert!(Exchange::Kraken.market_symbol("BTCUSD").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_binance_book_ticker_mid() {
+        let base_url = spawn_exchange_server().await;
+        let client = CexClient::new(Exchange::Binance, Some(base_url), HashMap::new());
+
+        let price = client.get_price("BTC/USD").await.unwrap();
+        assert_eq!(price.source, "Binance");
+        assert_eq!(price.price, dec!(67425.05));
+        assert_eq!(price.confidence, dec!(0.05));
+    }
+
+    #[tokio::test]
+    async fn test_coinbase_ticker_uses_exchange_time() {
+        let base_url = spawn_exchange_server().await;
+        let client = CexClient::new(Exchange::Coinbase, Some(base_url), HashMap::new());
+
+        let price = client.get_price("BTC/USD").await.unwrap();
+        assert_eq!(price.source, "Coinbase");
+        assert_eq!(price.price, dec!(67425.0));
+        assert_eq!(price.confidence, dec!(1.0));
+        assert_eq!(price.timestamp, 1792315812);
+    }
+
+    #[tokio::test]
+    async fn test_kraken_ticker_reads_renamed_pair() {
+        let base_url = spawn_exchange_server().await;
+        let client = CexClient::new(Exchange::Kraken, Some(base_url), HashMap::new());
+
+        let price = client.get_price("BTC/USD").await.unwrap();
+        assert_eq!(price.source, "Kraken");
+        assert_eq!(price.price, dec!(67425.0));
+
+        let err = client.get_price("DOGE/USD").await.unwrap_err();
+        assert!(err.to_string().contains("Unknown asset pair"));
+    }
+
+    #[tokio::test]
+    async fn test_errors_and_crossed_books_are_rejected() {
+        let base_url = spawn_exchange_server().await;
+        let binance = CexClient::new(Exchange::Binance, Some(base_url.clone()), HashMap::new());
+
+        let err = binance.get_price("DOGE/USD").await.unwrap_err();
+        assert!(err.to_string().contains("400"));
+        // Mock ETHUSDT book has bid above ask
+        assert!(binance.get_price("ETH/USD").await.is_err());
+
+        let prices = binance
+            .get_multiple_prices(&["BTC/USD".to_string(), "ETH/USD".to_string(), "DOGE/USD".to_string()])
+            .await
+            .unwrap();
+        assert_eq!(prices.len(), 1);
+        assert_eq!(prices[0].symbol, "BTC/USD");
+
+        let coinbase = CexClient::new(Exchange::Coinbase, Some(base_url), HashMap::new());
+        assert!(coinbase.get_price("ETH/USD").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_market_overrides_from_config() {
+        let base_url = spawn_exchange_server().await;
+        let mut contents = std::fs::read_to_string("../config/dev.toml").unwrap();
+        contents.push_str(&format!(r#"
+[[exchanges]]
+name = "coinbase"
+base_url = "{}"
+
+[exchanges.symbols]
+"ETH/USD" = "BTC-USD"
+"#, base_url));
+
+        // Coinbase is listed twice now
+        let config = OracleFileConfig::parse(&contents).unwrap();
+        assert!(crate::feed_registry::FeedRegistry::from_config(&config).is_err());
+
+        let client = CexClient::from_config(config.exchanges.last().unwrap());
+        let price = client.get_price("ETH/USD").await.unwrap();
+        assert_eq!(price.symbol, "ETH/USD");
+        assert_eq!(price.price, dec!(67425.0));
+    }
+
+    #[test]
+    fn test_exchange_weights_apply_to_every_symbol() {
+        let registry = crate::tests::dev_registry();
+
+        assert_eq!(registry.source_weight("BTC/USD", "Binance"), 0.2);
+        assert_eq!(registry.source_weight("AVAX/USD", "Kraken"), 0.2);
+        assert_eq!(registry.source_weight("BTC/USD", "Pyth"), 0.6);
+    }
+}
@@ -0,0 +1,158 @@
+#[cfg(test)]
+mod chainlink_client_tests {
+    use crate::chainlink_client::{decode_decimals, decode_latest_round_data, ChainlinkClient, ChainlinkError, RoundData};
+    use crate::oracle_client::OracleClient;
+    use axum::extract::State;
+    use axum::routing::post;
+    use axum::{Json, Router};
+    use serde_json::{json, Value};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use std::time::{Duration, SystemTime, UNIX_EPOCH};
+    use tokio::net::TcpListener;
+    use rust_decimal_macros::dec;
+
+    const BTC_AGGREGATOR: &str = "0xf4030086522a5beea4988f8ca5b36dbc97bee88c";
+    const ETH_AGGREGATOR: &str = "0x5f4ec3df9cbd43714fe2740f5e3616155c5b8419";
+
+    fn now() -> u64 {
+        SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs()
+    }
+
+    fn word(value: i128) -> String {
+        let fill = if value < 0 { "f" } else { "0" };
+        format!("{}{:032x}", fill.repeat(32), value)
+    }
+
+    fn round_data(round_id: i128, answer: i128, updated_at: u64, answered_in_round: i128) -> String {
+        format!(
+            "0x{}{}{}{}{}",
+            word(round_id), word(answer), word(updated_at as i128), word(updated_at as i128), word(answered_in_round)
+        )
+    }
+
+    #[derive(Clone)]
+    struct Canned {
+        decimals_calls: Arc<AtomicUsize>,
+        updated_at: u64,
+    }
+
+    /// JSON-RPC stand-in: BTC answers normally, ETH carries over an old round
+    async fn eth_call(State(canned): State<Canned>, Json(request): Json<Value>) -> Json<Value> {
+        assert_eq!(request["method"], "eth_call");
+        assert_eq!(request["params"][1], "latest");
+        let to = request["params"][0]["to"].as_str().unwrap();
+        let data = request["params"][0]["data"].as_str().unwrap();
+
+        let result = match (to, data) {
+            (_, "0x313ce567") => {
+                canned.decimals_calls.fetch_add(1, Ordering::SeqCst);
+                format!("0x{}", word(8))
+            }
+            (BTC_AGGREGATOR, "0xfeaf968c") => round_data(110680464442257320247, 6_742_315_000_000, canned.updated_at, 110680464442257320247),
+            (ETH_AGGREGATOR, "0xfeaf968c") => round_data(110680464442257320300, 261_050_000_000, canned.updated_at, 110680464442257320299),
+            _ => return Json(json!({ "jsonrpc": "2.0", "id": request["id"], "error": { "code": 3, "message": "execution reverted" } })),
+        };
+        Json(json!({ "jsonrpc": "2.0", "id": request["id"], "result": result }))
+    }
+
+    async fn spawn_rpc_server(updated_at: u64) -> (String, Arc<AtomicUsize>) {
+        let decimals_calls = Arc::new(AtomicUsize::new(0));
+        let app = Router::new()
+            .route("/", post(eth_call))
+            .with_state(Canned { decimals_calls: decimals_calls.clone(), updated_at });
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let rpc_url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+        (rpc_url, decimals_calls)
+    }
+
+    fn dev_client(rpc_url: String) -> ChainlinkClient {
+        ChainlinkClient::new(rpc_url, crate::tests::dev_registry().into_shared(), Duration::from_secs(60))
+    }
+
+    #[test]
+    fn test_decode_latest_round_data() {
+        let round = decode_latest_round_data(&round_data(7, 6_742_315_000_000, 1760780412, 7)).unwrap();
+        assert_eq!(round, RoundData {
+            round_id: 7,
+            answer: 6_742_315_000_000,
+            started_at: 1760780412,
+            updated_at: 1760780412,
+            answered_in_round: 7,
+        });
+
+        // int256 sign extension
+        let negative = decode_latest_round_data(&round_data(7, -5, 1760780412, 7)).unwrap();
+        assert_eq!(negative.answer, -5);
+        assert_eq!(negative.validate(1760780412, 60), Err(ChainlinkError::NonPositiveAnswer(-5)));
+
+        assert_eq!(decode_decimals(&format!("0x{}", word(8))), Ok(8));
+        assert_eq!(decode_latest_round_data("0x1234"), Err(ChainlinkError::UnexpectedLength(2, 160)));
+        assert!(matches!(decode_latest_round_data("0xzz"), Err(ChainlinkError::InvalidHex(_))));
+    }
+
+    #[test]
+    fn test_round_staleness_checks() {
+        let round = RoundData { round_id: 10, answer: 100, started_at: 1000, updated_at: 1000, answered_in_round: 10 };
+        assert!(round.validate(1030, 60).is_ok());
+        assert_eq!(
+            round.validate(1100, 60),
+            Err(ChainlinkError::HeartbeatExceeded { age: 100, heartbeat: 60 })
+        );
+
+        let carried_over = RoundData { answered_in_round: 9, ..round.clone() };
+        assert_eq!(
+            carried_over.validate(1030, 60),
+            Err(ChainlinkError::StaleRound { round_id: 10, answered_in_round: 9 })
+        );
+
+        let unanswered = RoundData { updated_at: 0, ..round };
+        assert_eq!(unanswered.validate(1030, 60), Err(ChainlinkError::IncompleteRound(10)));
+    }
+
+    #[tokio::test]
+    async fn test_reads_price_through_eth_call() {
+        let (rpc_url, decimals_calls) = spawn_rpc_server(now() - 30).await;
+        let client = dev_client(rpc_url);
+
+        let price = client.get_price("BTC/USD").await.unwrap();
+        assert_eq!(price.source, "Chainlink");
+        assert_eq!(price.price, dec!(67423.15));
+        // dev config: 50 bps deviation threshold
+        assert_eq!(price.confidence, dec!(337.11575));
+
+        // decimals() is only called once per aggregator
+        client.get_price("BTC/USD").await.unwrap();
+        assert_eq!(decimals_calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_stale_rounds_and_unknown_symbols_are_rejected() {
+        let (rpc_url, _) = spawn_rpc_server(now() - 30).await;
+        let client = dev_client(rpc_url);
+
+        let err = client.get_price("ETH/USD").await.unwrap_err();
+        assert!(matches!(err.downcast_ref::<ChainlinkError>(), Some(ChainlinkError::StaleRound { .. })));
+        assert!(client.get_price("SOL/USD").await.is_err());
+
+        // Older than the configured 3600s heartbeat
+        let (rpc_url, _) = spawn_rpc_server(now() - 7200).await;
+        let err = dev_client(rpc_url).get_price("BTC/USD").await.unwrap_err();
+        assert!(matches!(err.downcast_ref::<ChainlinkError>(), Some(ChainlinkError::HeartbeatExceeded { heartbeat: 3600, .. })));
+    }
+
+    #[test]
+    fn test_chainlink_feeds_require_evm_section() {
+        let contents = std::fs::read_to_string("../config/dev.toml").unwrap();
+        let registry = crate::tests::dev_registry();
+        assert_eq!(registry.chainlink_feed("BTC/USD").unwrap().address, BTC_AGGREGATOR);
+        assert_eq!(registry.source_weight("ETH/USD", "Chainlink"), 0.3);
+
+        let without_evm = contents.replace("[evm]", "[unused]");
+        let config = crate::config::OracleFileConfig::parse(&without_evm).unwrap();
+        let err = crate::feed_registry::FeedRegistry::from_config(&config).unwrap_err();
+        assert!(err.to_string().contains("[evm]"));
+    }
+}
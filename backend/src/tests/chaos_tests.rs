@@ -5,6 +5,9 @@ mod chaos_tests {
     use rand::Rng;
     use std::sync::Arc;
     use tokio::time::{sleep, Duration};
+    use rust_decimal::Decimal;
+    use rust_decimal::prelude::FromPrimitive;
+    use rust_decimal_macros::dec;
 
     #[tokio::test]
     async fn test_random_oracle_failures() {
@@ -14,8 +17,8 @@ mod chaos_tests {
         // Set up initial price
         mock_oracle.set_price("BTC/USD", PriceData {
             symbol: "BTC/USD".to_string(),
-            price: 65000.0,
-            confidence: 50.0,
+            price: dec!(65000.0),
+            confidence: dec!(50.0),
             timestamp: 1700000000,
             source: "Mock".to_string(),
         });
@@ -53,8 +56,8 @@ mod chaos_tests {
         
         mock_oracle.set_price("BTC/USD", PriceData {
             symbol: "BTC/USD".to_string(),
-            price: 65000.0,
-            confidence: 50.0,
+            price: dec!(65000.0),
+            confidence: dec!(50.0),
             timestamp: 1700000000,
             source: "Mock".to_string(),
         });
@@ -95,8 +98,8 @@ mod chaos_tests {
         for symbol in &symbols {
             mock_oracle.set_price(symbol, PriceData {
                 symbol: symbol.to_string(),
-                price: 1000.0,
-                confidence: 10.0,
+                price: dec!(1000.0),
+                confidence: dec!(10.0),
                 timestamp: 1700000000,
                 source: "Mock".to_string(),
             });
@@ -160,8 +163,8 @@ mod chaos_tests {
 
             let price_data = PriceData {
                 symbol: "BTC/USD".to_string(),
-                price: final_price,
-                confidence,
+                price: Decimal::from_f64(final_price).unwrap(),
+                confidence: Decimal::from_f64(confidence).unwrap(),
                 timestamp: 1700000000 + i,
                 source: "Mock".to_string(),
             };
@@ -183,7 +186,7 @@ mod chaos_tests {
         let mut large_movements = 0;
         for window in price_history.windows(2) {
             let price_change = ((window[1].0 - window[0].0) / window[0].0).abs();
-            if price_change > 0.1 { // >10% movement
+            if price_change > dec!(0.1) { // >10% movement
                 large_movements += 1;
             }
         }
@@ -199,8 +202,8 @@ mod chaos_tests {
         
         mock_oracle.set_price("BTC/USD", PriceData {
             symbol: "BTC/USD".to_string(),
-            price: 65000.0,
-            confidence: 50.0,
+            price: dec!(65000.0),
+            confidence: dec!(50.0),
             timestamp: 1700000000,
             source: "Mock".to_string(),
         });
@@ -237,8 +240,8 @@ mod chaos_tests {
         
         mock_oracle.set_price("BTC/USD", PriceData {
             symbol: "BTC/USD".to_string(),
-            price: 65000.0,
-            confidence: 50.0,
+            price: dec!(65000.0),
+            confidence: dec!(50.0),
             timestamp: 1700000000,
             source: "Mock".to_string(),
         });
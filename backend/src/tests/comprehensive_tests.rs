@@ -3,6 +3,9 @@
 
 use super::*;
 use crate::oracle_client::{OracleClient, PythClient, SwitchboardClient};
+use crate::solana_rpc::SolanaRpcClient;
+use rust_decimal::Decimal;
+use std::sync::Arc;
 use std::time::Duration;
 
 #[tokio::test]
@@ -10,8 +13,14 @@ async fn test_complete_oracle_pipeline() {
     println!("🔄 Testing complete oracle pipeline...");
     
     // Test 1: Multiple oracle sources
-    let pyth_client = PythClient::new();
-    let switchboard_client = SwitchboardClient::new("https://api.mainnet-beta.solana.com".to_string());
+    let pyth_client = PythClient::new(
+        "https://hermes.pyth.network".to_string(),
+        crate::tests::dev_registry().into_shared(),
+    );
+    let switchboard_client = SwitchboardClient::new(
+        Arc::new(SolanaRpcClient::new("https://api.mainnet-beta.solana.com".to_string())),
+        crate::tests::dev_registry().into_shared(),
+    );
     
     assert_eq!(pyth_client.get_name(), "Pyth");
     assert_eq!(switchboard_client.get_name(), "Switchboard");
@@ -22,7 +31,7 @@ async fn test_complete_oracle_pipeline() {
     for symbol in &symbols {
         match pyth_client.get_price(symbol).await {
             Ok(price) => {
-                assert!(price.price > 0.0);
+                assert!(price.price > Decimal::ZERO);
                 assert_eq!(price.source, "Pyth");
                 println!("✅ Pyth {}: ${:.2}", symbol, price.price);
             }
@@ -31,7 +40,7 @@ async fn test_complete_oracle_pipeline() {
         
         match switchboard_client.get_price(symbol).await {
             Ok(price) => {
-                assert!(price.price > 0.0);
+                assert!(price.price > Decimal::ZERO);
                 assert_eq!(price.source, "Switchboard");
                 println!("✅ Switchboard {}: ${:.2}", symbol, price.price);
             }
@@ -0,0 +1,215 @@
+#[cfg(test)]
+mod config_tests {
+    use crate::config::OracleFileConfig;
+    use crate::feed_registry::{FeedRegistry, SymbolFeeds, normalize_pyth_feed_id, validate_solana_address};
+
+    const BTC_FEED_ID: &str = "e62df6c8b4a85fe1a67db44dc12de5db330f7ac66b72dc658afedf0f4a415b43";
+    const BTC_AGGREGATOR: &str = "8SXvChNYFhRq4EZuZvnhjrB3jJRQCv4k3P4W6hesH3Ee";
+
+    fn config_with_feeds(feeds: &str) -> String {
+        format!(r#"
+[solana]
+rpc_url = "http://localhost:8899"
+ws_url = "ws://localhost:8900"
+commitment = "confirmed"
+timeout_seconds = 30
+
+[oracle]
+max_price_deviation_bps = 500
+max_price_age_seconds = 60
+update_interval_ms = 1000
+confidence_threshold = 0.95
+pyth_weight = 0.6
+switchboard_weight = 0.4
+
+[[trading_pairs.pairs]]
+symbol = "BTC/USD"
+tick_size = "0.01"
+min_order_size = "0.0001"
+max_leverage = 20
+funding_interval_hours = 8
+
+{}
+"#, feeds)
+    }
+
+    fn pyth_feeds(feed_id: &str) -> SymbolFeeds {
+        SymbolFeeds { pyth_feed_id: Some(feed_id.to_string()), ..Default::default() }
+    }
+
+    fn build(feeds: &str) -> anyhow::Result<FeedRegistry> {
+        let config = OracleFileConfig::parse(&config_with_feeds(feeds))?;
+        FeedRegistry::from_config(&config)
+    }
+
+    #[test]
+    fn test_dev_config_builds_registry() {
+        let registry = crate::tests::dev_registry();
+
+        assert_eq!(registry.symbols(), ["BTC/USD", "ETH/USD", "SOL/USD", "AVAX/USD", "USDT/USD", "USDC/USD", "ETH/BTC", "SOL/ETH"]);
+        assert_eq!(registry.pyth_feed_ids().get("BTC/USD").unwrap(), BTC_FEED_ID);
+        assert_eq!(registry.switchboard_addresses().get("BTC/USD").unwrap(), BTC_AGGREGATOR);
+        assert!(!registry.switchboard_addresses().contains_key("AVAX/USD"));
+        assert_eq!(registry.source_weight("BTC/USD", "Pyth"), 0.6);
+        assert_eq!(registry.source_weight("BTC/USD", "Switchboard"), 0.4);
+    }
+
+    #[test]
+    fn test_feed_weights_default_to_oracle_section() {
+        let registry = build(&format!(r#"
+[[oracle_feeds.pyth]]
+symbol = "BTC/USD"
+feed_id = "0x{}"
+
+[[oracle_feeds.switchboard]]
+symbol = "BTC/USD"
+pubkey = "{}"
+"#, BTC_FEED_ID.to_uppercase(), BTC_AGGREGATOR)).unwrap();
+
+        assert_eq!(registry.pyth_feed_ids().get("BTC/USD").unwrap(), BTC_FEED_ID);
+        assert_eq!(registry.source_weight("BTC/USD", "Pyth"), 0.6);
+        assert_eq!(registry.source_weight("BTC/USD", "Switchboard"), 0.4);
+        assert_eq!(registry.source_weight("BTC/USD", "Other"), 1.0);
+    }
+
+    #[test]
+    fn test_pyth_pubkey_registers_onchain_price_account() {
+        let registry = build(&format!(r#"
+[[oracle_feeds.pyth]]
+symbol = "BTC/USD"
+feed_id = "{}"
+pubkey = "GVXRSBjFk6e6J3NbVPXohDJetcTjaeeuykUpbQF8UoMU"
+weight = 0.7
+"#, BTC_FEED_ID)).unwrap();
+
+        assert_eq!(
+            registry.pyth_price_account("BTC/USD").unwrap(),
+            "GVXRSBjFk6e6J3NbVPXohDJetcTjaeeuykUpbQF8UoMU"
+        );
+        assert_eq!(registry.source_weight("BTC/USD", "PythOnChain"), 0.7);
+        assert!(crate::tests::dev_registry().pyth_price_account("AVAX/USD").is_none());
+    }
+
+    #[test]
+    fn test_rejects_non_hex_pyth_feed_id() {
+        // The old hardcoded SOL/USD entry was a base58 account, not a Hermes ID
+        let result = build(r#"
+[[oracle_feeds.pyth]]
+symbol = "BTC/USD"
+feed_id = "7UVimffxr9ow1uXYxsr4LHAcV58mLzhmwaeKvJ1pjLiE"
+"#);
+        assert!(result.unwrap_err().to_string().contains("not a 32-byte hex feed ID"));
+    }
+
+    #[test]
+    fn test_rejects_malformed_switchboard_address() {
+        let result = build(r#"
+[[oracle_feeds.switchboard]]
+symbol = "BTC/USD"
+pubkey = "0OIl-not-base58"
+"#);
+        assert!(result.unwrap_err().to_string().contains("not valid base58"));
+    }
+
+    #[test]
+    fn test_rejects_feed_for_unknown_symbol() {
+        let result = build(&format!(r#"
+[[oracle_feeds.pyth]]
+symbol = "BTC/USD"
+feed_id = "{}"
+
+[[oracle_feeds.pyth]]
+symbol = "DOGE/USD"
+feed_id = "{}"
+"#, BTC_FEED_ID, BTC_FEED_ID));
+        assert!(result.unwrap_err().to_string().contains("unknown trading pair DOGE/USD"));
+    }
+
+    #[test]
+    fn test_rejects_duplicate_feed_and_unpriced_pair() {
+        let duplicate = build(&format!(r#"
+[[oracle_feeds.pyth]]
+symbol = "BTC/USD"
+feed_id = "{}"
+
+[[oracle_feeds.pyth]]
+symbol = "BTC/USD"
+feed_id = "{}"
+"#, BTC_FEED_ID, BTC_FEED_ID));
+        assert!(duplicate.unwrap_err().to_string().contains("Duplicate Pyth feed"));
+
+        let unpriced = build("");
+        assert!(unpriced.unwrap_err().to_string().contains("no oracle feeds configured"));
+    }
+
+    #[test]
+    fn test_id_validators() {
+        assert!(normalize_pyth_feed_id(&BTC_FEED_ID[..62]).is_err());
+        assert!(normalize_pyth_feed_id(&format!("{}zz", &BTC_FEED_ID[..62])).is_err());
+        assert!(validate_solana_address(BTC_AGGREGATOR).is_ok());
+        assert!(validate_solana_address("93DA3b71E5B3b93c47266eaBca3992b073Ce6b6B").is_err());
+    }
+
+    #[test]
+    fn test_add_and_remove_symbol_at_runtime() {
+        let mut registry = crate::tests::dev_registry();
+
+        registry.add_symbol(
+            "BNB/USD",
+            &pyth_feeds("0x2f95862b045670cd22bee3114c39763a4a08beeb663b145d283c31d7d1101c4f"),
+        ).unwrap();
+
+        assert!(registry.contains("BNB/USD"));
+        assert_eq!(
+            registry.pyth_feed_id("BNB/USD").unwrap(),
+            "2f95862b045670cd22bee3114c39763a4a08beeb663b145d283c31d7d1101c4f"
+        );
+        assert_eq!(registry.source_weight("BNB/USD", "Pyth"), 0.6);
+        assert_eq!(registry.symbols().last().unwrap(), "BNB/USD");
+
+        assert!(registry.remove_symbol("BNB/USD"));
+        assert!(!registry.contains("BNB/USD"));
+        assert!(registry.pyth_feed_id("BNB/USD").is_none());
+        assert!(!registry.remove_symbol("BNB/USD"));
+
+        // Re-adding a configured symbol doesn't bring back its old limits
+        assert_ne!(registry.price_bounds("BTC/USD"), registry.price_bounds("BNB/USD"));
+        assert_ne!(registry.refresh_schedule("BTC/USD"), registry.refresh_schedule("BNB/USD"));
+        assert!(registry.remove_symbol("BTC/USD"));
+        registry.add_symbol("BTC/USD", &pyth_feeds("0xe62df6c8b4a85fe1a67db44dc12de5db330f7ac66b72dc658afedf0f4a415b43")).unwrap();
+        assert_eq!(registry.price_bounds("BTC/USD"), registry.price_bounds("BNB/USD"));
+        assert_eq!(registry.refresh_schedule("BTC/USD"), registry.refresh_schedule("BNB/USD"));
+    }
+
+    #[test]
+    fn test_add_symbol_rejects_bad_input_without_side_effects() {
+        let mut registry = crate::tests::dev_registry();
+        let before = registry.symbols().to_vec();
+
+        assert!(registry.add_symbol("BTC/USD", &pyth_feeds(BTC_FEED_ID)).is_err());
+        assert!(registry.add_symbol("bnb-usd", &pyth_feeds(BTC_FEED_ID)).is_err());
+        assert!(registry.add_symbol("BNB/USD", &SymbolFeeds::default()).is_err());
+        // Valid Pyth ID but malformed Switchboard address: nothing is registered
+        let feeds = SymbolFeeds {
+            switchboard_address: Some("not-base58!".to_string()),
+            ..pyth_feeds(BTC_FEED_ID)
+        };
+        assert!(registry.add_symbol("BNB/USD", &feeds).is_err());
+
+        assert_eq!(registry.symbols(), before.as_slice());
+        assert!(registry.pyth_feed_id("BNB/USD").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_shared_registry_updates_are_visible_to_readers() {
+        let shared = crate::tests::dev_registry().into_shared();
+        let reader = shared.clone();
+
+        let feeds = SymbolFeeds { switchboard_address: Some(BTC_AGGREGATOR.to_string()), ..Default::default() };
+        shared.write().await.add_symbol("DOGE/USD", &feeds).unwrap();
+        assert_eq!(reader.read().await.switchboard_address("DOGE/USD").unwrap(), BTC_AGGREGATOR);
+
+        shared.write().await.remove_symbol("DOGE/USD");
+        assert!(!reader.read().await.contains("DOGE/USD"));
+    }
+}
@@ -0,0 +1,115 @@
+#[cfg(test)]
+mod error_tests {
+    use crate::aggregation::{DegradedMode, QuorumPolicy};
+    use crate::config::CacheConfig;
+    use crate::error::OracleError;
+    use crate::memory_store::MemoryStore;
+    use crate::oracle_client::{OracleClient, OracleManager, PriceData};
+    use crate::price_aggregator::PriceAggregator;
+    use crate::tests::mock_oracle_tests::mock_oracle_tests::MockOracleClient;
+    use anyhow::{anyhow, Context};
+    use axum::extract::{Query, State};
+    use axum::http::StatusCode;
+    use axum::response::IntoResponse;
+    use rust_decimal_macros::dec;
+    use serde_json::{json, Value};
+    use std::collections::HashMap;
+    use std::sync::Arc;
+
+    fn stale() -> OracleError {
+        OracleError::Stale { symbol: "BTC/USD".to_string(), age_seconds: 45 }
+    }
+
+    #[test]
+    fn test_codes_and_statuses() {
+        let cases = [
+            (OracleError::UnknownSymbol("DOGE/USD".to_string()), "unknown_symbol", StatusCode::NOT_FOUND),
+            (OracleError::InvalidRequest("bad feed id".to_string()), "invalid_request", StatusCode::BAD_REQUEST),
+            (OracleError::Unauthorized, "unauthorized", StatusCode::UNAUTHORIZED),
+            (OracleError::SourcesUnavailable { symbol: "BTC/USD".to_string(), reason: "none".to_string() }, "sources_unavailable", StatusCode::BAD_GATEWAY),
+            (stale(), "stale_price", StatusCode::SERVICE_UNAVAILABLE),
+            (OracleError::QuorumNotMet { symbol: "BTC/USD".to_string(), available: 1, required: 3 }, "quorum_not_met", StatusCode::SERVICE_UNAVAILABLE),
+            (OracleError::from(sqlx::Error::RowNotFound), "storage_error", StatusCode::INTERNAL_SERVER_ERROR),
+        ];
+        for (error, code, status) in cases {
+            assert_eq!((error.code(), error.status()), (code, status), "{}", error);
+        }
+    }
+
+    #[test]
+    fn test_recovers_typed_errors_through_anyhow() {
+        let wrapped = Err::<(), _>(anyhow::Error::from(stale()))
+            .context("Failed to price ETH/BTC from its legs")
+            .unwrap_err();
+        assert!(matches!(OracleError::from(wrapped), OracleError::Stale { age_seconds: 45, .. }));
+
+        assert!(matches!(OracleError::from(anyhow::Error::from(sqlx::Error::PoolTimedOut)), OracleError::Storage(_)));
+
+        let other = OracleError::from(anyhow!("decoder exploded"));
+        assert_eq!(other.code(), "internal_error");
+        assert_eq!(other.to_string(), "decoder exploded");
+    }
+
+    #[test]
+    fn test_quorum_shortfall_is_typed() {
+        let policy = QuorumPolicy { min_sources: 3, degraded_mode: DegradedMode::Reject, ..QuorumPolicy::default() };
+        let err = policy.apply("BTC/USD", Err(stale().into()), None, 0).unwrap_err();
+        assert_eq!(OracleError::from(err).code(), "stale_price");
+    }
+
+    #[tokio::test]
+    async fn test_response_body() {
+        let response = OracleError::UnknownSymbol("DOGE/USD".to_string()).into_response();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body: Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body, json!({ "error": { "code": "unknown_symbol", "message": "Unknown symbol DOGE/USD" } }));
+    }
+
+    #[tokio::test]
+    async fn test_multiple_prices_report_each_failure() {
+        let mock = MockOracleClient::new();
+        mock.set_price("ETH/USD", PriceData {
+            symbol: "ETH/USD".to_string(),
+            price: dec!(2610.5),
+            confidence: dec!(0.5),
+            timestamp: chrono::Utc::now().timestamp(),
+            source: "MockOracle".to_string(),
+        });
+        let store = Arc::new(MemoryStore::default());
+        let clients: Vec<Box<dyn OracleClient>> = vec![Box::new(mock)];
+        let oracle = OracleManager::new(store.clone(), crate::tests::dev_registry().into_shared(), clients, &CacheConfig::default());
+        let state = crate::AppState {
+            store,
+            config: crate::AppConfig {
+                database_url: "memory://".to_string(),
+                redis_url: String::new(),
+                server_port: 0,
+                pyth_rpc_url: String::new(),
+                switchboard_rpc_url: String::new(),
+                oracle_config_path: String::new(),
+                admin_api_token: None,
+            },
+            price_aggregator: Arc::new(PriceAggregator::new(oracle, Arc::new(MemoryStore::default()))),
+        };
+
+        let params = HashMap::from([("symbols".to_string(), "ETH/USD, DOGE/USD".to_string())]);
+        let response = crate::get_multiple_prices(Query(params), State(state)).await;
+        let body = serde_json::to_value(&response.0).unwrap();
+        assert_eq!(body["prices"].as_array().unwrap().len(), 1);
+        assert_eq!(body["prices"][0]["symbol"], "ETH/USD");
+        assert_eq!(body["errors"], json!({ "DOGE/USD": { "code": "unknown_symbol", "message": "Unknown symbol DOGE/USD" } }));
+    }
+
+    #[test]
+    fn test_internal_detail_is_not_exposed() {
+        let upstream = OracleError::from(anyhow!("Failed to parse Pyth response '<html>secret</html>'"));
+        assert_eq!(upstream.body().error.message, "Internal error");
+        assert_eq!(upstream.body().error.code, "internal_error");
+
+        let storage = OracleError::from(sqlx::Error::Protocol("relation \"price_feeds\" does not exist".to_string()));
+        assert_eq!(storage.body().error.message, "Storage error");
+        assert_eq!(stale().body().error.message, "BTC/USD price is stale: 45s old");
+    }
+}
@@ -5,6 +5,7 @@ pub mod mock_oracle_tests {
     use std::collections::HashMap;
     use std::sync::{Arc, Mutex};
     use anyhow::Result;
+    use rust_decimal_macros::dec;
 
     // Mock Oracle Client for testing
     pub struct MockOracleClient {
@@ -80,8 +81,8 @@ pub mod mock_oracle_tests {
         // Set up test price data
         let btc_price = PriceData {
             symbol: "BTC/USD".to_string(),
-            price: 65000.0,
-            confidence: 50.0,
+            price: dec!(65000.0),
+            confidence: dec!(50.0),
             timestamp: 1700000000,
             source: "Mock".to_string(),
         };
@@ -94,8 +95,8 @@ pub mod mock_oracle_tests {
         
         let fetched_price = result.unwrap();
         assert_eq!(fetched_price.symbol, "BTC/USD");
-        assert_eq!(fetched_price.price, 65000.0);
-        assert_eq!(fetched_price.confidence, 50.0);
+        assert_eq!(fetched_price.price, dec!(65000.0));
+        assert_eq!(fetched_price.confidence, dec!(50.0));
     }
 
     #[tokio::test] 
@@ -134,8 +135,8 @@ pub mod mock_oracle_tests {
         // Set up stale price (60 seconds old)
         let stale_price = PriceData {
             symbol: "BTC/USD".to_string(),
-            price: 65000.0,
-            confidence: 50.0,
+            price: dec!(65000.0),
+            confidence: dec!(50.0),
             timestamp: current_time - 60,
             source: "Mock".to_string(),
         };
@@ -157,8 +158,8 @@ pub mod mock_oracle_tests {
         // Set up price with high confidence interval (indicating unreliable data)
         let unreliable_price = PriceData {
             symbol: "BTC/USD".to_string(),
-            price: 65000.0,
-            confidence: 5000.0, // ±$5000 confidence - very high
+            price: dec!(65000.0),
+            confidence: dec!(5000.0), // ±$5000 confidence - very high
             timestamp: 1700000000,
             source: "Mock".to_string(),
         };
@@ -169,8 +170,8 @@ pub mod mock_oracle_tests {
         assert!(result.is_ok());
         
         let price_data = result.unwrap();
-        let confidence_percent = (price_data.confidence / price_data.price) * 100.0;
-        assert!(confidence_percent > 5.0, "High confidence interval should be detected");
+        let confidence_percent = (price_data.confidence / price_data.price) * dec!(100);
+        assert!(confidence_percent > dec!(5), "High confidence interval should be detected");
     }
 
     #[tokio::test]
@@ -178,17 +179,17 @@ pub mod mock_oracle_tests {
         let mock_oracle = MockOracleClient::new();
         
         let extreme_cases = vec![
-            ("ZERO/USD", 0.0),           // Zero price
-            ("NEGATIVE/USD", -100.0),    // Negative price
-            ("HUGE/USD", 1e12),          // Extremely large price
-            ("TINY/USD", 1e-8),          // Extremely small price
+            ("ZERO/USD", dec!(0.0)),                  // Zero price
+            ("NEGATIVE/USD", dec!(-100.0)),           // Negative price
+            ("HUGE/USD", dec!(1000000000000)),        // Extremely large price
+            ("TINY/USD", dec!(0.00000001)),           // Extremely small price
         ];
 
         for (symbol, price) in extreme_cases {
             let price_data = PriceData {
                 symbol: symbol.to_string(),
                 price,
-                confidence: 1.0,
+                confidence: dec!(1.0),
                 timestamp: 1700000000,
                 source: "Mock".to_string(),
             };
@@ -208,16 +209,16 @@ pub mod mock_oracle_tests {
         
         mock_oracle.set_price("BTC/USD", PriceData {
             symbol: "BTC/USD".to_string(),
-            price: 65000.0,
-            confidence: 50.0,
+            price: dec!(65000.0),
+            confidence: dec!(50.0),
             timestamp: 1700000000,
             source: "Mock".to_string(),
         });
         
         mock_oracle.set_price("ETH/USD", PriceData {
             symbol: "ETH/USD".to_string(),
-            price: 3500.0,
-            confidence: 35.0,
+            price: dec!(3500.0),
+            confidence: dec!(35.0),
             timestamp: 1700000000,
             source: "Mock".to_string(),
         });
@@ -10,3 +10,32 @@ pub mod integration_tests;
 pub mod mock_oracle_tests;
 pub mod chaos_tests;
 pub mod manipulation_detection_tests;
+pub mod config_tests;
+pub mod switchboard_decoding_tests;
+pub mod pyth_account_tests;
+pub mod pyth_stream_tests;
+pub mod pyth_batch_tests;
+pub mod switchboard_stream_tests;
+pub mod cex_client_tests;
+pub mod chainlink_client_tests;
+pub mod resilient_client_tests;
+pub mod source_stats_tests;
+pub mod aggregation_tests;
+pub mod quorum_tests;
+pub mod synthetic_tests;
+pub mod quote_tests;
+pub mod bounds_tests;
+pub mod error_tests;
+pub mod price_cache_tests;
+pub mod scheduler_tests;
+pub mod observation_tests;
+pub mod price_store_tests;
+
+use crate::config::OracleFileConfig;
+use crate::feed_registry::FeedRegistry;
+
+// Feed registry built from the checked-in dev config
+pub fn dev_registry() -> FeedRegistry {
+    let config = OracleFileConfig::load("../config/dev.toml").expect("dev config should load");
+    FeedRegistry::from_config(&config).expect("dev config should be valid")
+}
@@ -0,0 +1,55 @@
+#[cfg(test)]
+mod observation_tests {
+    use crate::aggregation::PriceMode;
+    use crate::oracle_client::{AggregatedPrice, ExcludedSource, PriceData, SourceObservation};
+    use rust_decimal::Decimal;
+    use rust_decimal_macros::dec;
+
+    fn source(symbol: &str, source: &str, price: Decimal) -> PriceData {
+        PriceData { symbol: symbol.to_string(), price, confidence: dec!(0.5), timestamp: 1760780400, source: source.to_string() }
+    }
+
+    fn eth_btc() -> AggregatedPrice {
+        AggregatedPrice {
+            symbol: "ETH/BTC".to_string(),
+            mark_price: dec!(0.05),
+            index_price: dec!(0.05),
+            confidence: dec!(0.0001),
+            sources: vec![source("ETH/USD", "pyth", dec!(3000)), source("BTC/USD", "binance", dec!(60000))],
+            timestamp: 1760780412,
+            excluded_sources: vec![ExcludedSource::new(source("BTC/USD", "kraken", dec!(66000)), "Outlier: 10% from median".to_string())],
+            mode: PriceMode::Normal,
+            depegged_quotes: vec![],
+        }
+    }
+
+    #[test]
+    fn test_observations_cover_used_and_excluded_sources() {
+        let observations = eth_btc().observations();
+        assert_eq!(observations.len(), 3);
+        // Legs keep their own symbol
+        assert_eq!(observations.iter().map(|o| o.symbol.as_str()).collect::<Vec<_>>(), ["ETH/USD", "BTC/USD", "BTC/USD"]);
+        assert_eq!(observations[2], SourceObservation {
+            symbol: "BTC/USD".to_string(),
+            source: "kraken".to_string(),
+            price: dec!(66000),
+            confidence: Some(dec!(0.5)),
+            timestamp: Some(1760780400),
+            excluded_reason: Some("Outlier: 10% from median".to_string()),
+        });
+    }
+
+    #[test]
+    fn test_stored_price_restores_its_sources() {
+        let price = eth_btc();
+        let stored = AggregatedPrice { sources: vec![], excluded_sources: vec![], ..price.clone() };
+        let restored = stored.with_observations(price.observations());
+        assert_eq!(restored.observations(), price.observations());
+        assert_eq!(restored.excluded_sources[0].reason, "Outlier: 10% from median");
+
+        // Rows without a confidence or timestamp fall back to the aggregate's timestamp
+        let bare = SourceObservation { confidence: None, timestamp: None, ..price.observations()[0].clone() };
+        let restored = AggregatedPrice { sources: vec![], excluded_sources: vec![], ..price }.with_observations(vec![bare]);
+        assert_eq!((restored.sources[0].confidence, restored.sources[0].timestamp), (Decimal::ZERO, 1760780412));
+    }
+}
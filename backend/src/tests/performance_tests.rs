@@ -1,6 +1,8 @@
 #[cfg(test)]
 mod performance_tests {
     use crate::oracle_client::{PythClient, SwitchboardClient, OracleClient};
+    use crate::solana_rpc::SolanaRpcClient;
+    use std::sync::Arc;
     use std::time::Instant;
     use tokio::time::Duration;
     
@@ -8,8 +10,14 @@ mod performance_tests {
     async fn test_price_fetch_latency() {
         println!("\n=== LATENCY MEASUREMENT TESTS ===");
         
-        let pyth_client = PythClient::new();
-        let switchboard_client = SwitchboardClient::new("https://api.mainnet-beta.solana.com".to_string());
+        let pyth_client = PythClient::new(
+            "https://hermes.pyth.network".to_string(),
+            crate::tests::dev_registry().into_shared(),
+        );
+        let switchboard_client = SwitchboardClient::new(
+            Arc::new(SolanaRpcClient::new("https://api.mainnet-beta.solana.com".to_string())),
+            crate::tests::dev_registry().into_shared(),
+        );
         
         let test_symbols = vec!["BTC/USD", "ETH/USD", "SOL/USD"];
         
@@ -64,7 +72,10 @@ mod performance_tests {
             let symbol = test_symbols[i % test_symbols.len()].to_string();
             
             let handle = tokio::spawn(async move {
-                let client = SwitchboardClient::new("https://api.mainnet-beta.solana.com".to_string());
+                let client = SwitchboardClient::new(
+                    Arc::new(SolanaRpcClient::new("https://api.mainnet-beta.solana.com".to_string())),
+                    crate::tests::dev_registry().into_shared(),
+                );
                 let req_start = Instant::now();
                 let result = client.get_price(&symbol).await;
                 (symbol, result, req_start.elapsed())
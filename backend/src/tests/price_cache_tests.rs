@@ -0,0 +1,91 @@
+#[cfg(test)]
+mod price_cache_tests {
+    use crate::config::OracleFileConfig;
+    use crate::error::OracleError;
+    use crate::price_cache::{CachePolicy, Freshness, SingleFlight};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    #[test]
+    fn test_freshness_windows() {
+        let policy = CachePolicy { ttl: Duration::from_millis(500), stale_while_revalidate: Duration::from_millis(1000) };
+        assert_eq!(policy.freshness(Duration::from_millis(499)), Freshness::Fresh);
+        assert_eq!(policy.freshness(Duration::from_millis(500)), Freshness::Stale);
+        assert_eq!(policy.freshness(Duration::from_millis(1499)), Freshness::Stale);
+        assert_eq!(policy.freshness(Duration::from_millis(1500)), Freshness::Expired);
+
+        // Disabled by default
+        assert_eq!(CachePolicy::default().freshness(Duration::from_millis(500)), Freshness::Expired);
+
+        let config = OracleFileConfig::load("../config/dev.toml").unwrap();
+        assert_eq!(CachePolicy::from(&config.oracle.cache).stale_while_revalidate, Duration::from_millis(500));
+    }
+
+    async fn counted_fetch(fetches: &AtomicUsize, value: u32) -> Result<u32, OracleError> {
+        fetches.fetch_add(1, Ordering::SeqCst);
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        Ok(value)
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_callers_share_one_fetch() {
+        let flights = Arc::new(SingleFlight::default());
+        let fetches = Arc::new(AtomicUsize::new(0));
+
+        let callers = (0..20).map(|i| {
+            let (flights, fetches) = (flights.clone(), fetches.clone());
+            tokio::spawn(async move { flights.run("BTC/USD", counted_fetch(&fetches, i)).await })
+        });
+        let results = futures::future::try_join_all(callers).await.unwrap();
+
+        assert_eq!(fetches.load(Ordering::SeqCst), 1);
+        assert_eq!(results.iter().filter(|(_, shared)| !shared).count(), 1);
+        let leader = results.iter().find(|(_, shared)| !shared).unwrap().0.as_ref().unwrap();
+        assert!(results.iter().all(|(result, _)| result.as_ref().unwrap() == leader));
+        assert!(!flights.is_running("BTC/USD"));
+
+        // Later calls and other keys fetch on their own
+        let (_, shared) = flights.run("BTC/USD", counted_fetch(&fetches, 0)).await;
+        let (_, other) = flights.run("ETH/USD", counted_fetch(&fetches, 0)).await;
+        assert!(!shared && !other);
+        assert_eq!(fetches.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_errors_are_shared() {
+        let flights = SingleFlight::<Result<u32, OracleError>>::default();
+        let failing = async {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            Err(OracleError::Stale { symbol: "BTC/USD".to_string(), age_seconds: 45 })
+        };
+        let ((first, _), (second, shared)) = tokio::join!(
+            flights.run("BTC/USD", failing),
+            flights.run("BTC/USD", async { Ok(1) }),
+        );
+        assert!(shared);
+        assert_eq!(first.unwrap_err().code(), "stale_price");
+        assert_eq!(second.unwrap_err().code(), "stale_price");
+    }
+
+    #[tokio::test]
+    async fn test_waiter_takes_over_a_cancelled_fetch() {
+        let flights = Arc::new(SingleFlight::<u32>::default());
+        let leader = {
+            let flights = flights.clone();
+            tokio::spawn(async move { flights.run("BTC/USD", std::future::pending()).await })
+        };
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        assert!(flights.is_running("BTC/USD"));
+
+        let waiter = {
+            let flights = flights.clone();
+            tokio::spawn(async move { flights.run("BTC/USD", async { 7 }).await })
+        };
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        leader.abort();
+
+        let (value, shared) = tokio::time::timeout(Duration::from_secs(1), waiter).await.unwrap().unwrap();
+        assert_eq!((value, shared), (7, false));
+    }
+}
@@ -0,0 +1,170 @@
+#[cfg(test)]
+mod price_store_tests {
+    use crate::aggregation::PriceMode;
+    use crate::config::CacheConfig;
+    use crate::feed_registry::SymbolFeeds;
+    use crate::memory_store::MemoryStore;
+    use crate::oracle_client::{AggregatedPrice, ExcludedSource, OracleClient, OracleManager, PriceData};
+    use crate::price_store::{self, unix_now, ManipulationEvent, PriceStore};
+    use anyhow::Result;
+    use async_trait::async_trait;
+    use rust_decimal::Decimal;
+    use rust_decimal_macros::dec;
+    use std::sync::Arc;
+
+    fn quote(symbol: &str, source: &str, price: Decimal) -> PriceData {
+        PriceData { symbol: symbol.to_string(), price, confidence: dec!(0.5), timestamp: unix_now() - 2, source: source.to_string() }
+    }
+
+    fn observed(symbol: &str, source: &str, price: Decimal) -> PriceData {
+        PriceData { timestamp: 1_700_000_000, ..quote(symbol, source, price) }
+    }
+
+    fn aggregate(symbol: &str, mark_price: Decimal) -> AggregatedPrice {
+        AggregatedPrice {
+            symbol: symbol.to_string(),
+            mark_price,
+            index_price: mark_price,
+            confidence: dec!(1.25),
+            sources: vec![observed(symbol, "pyth", mark_price), observed(symbol, "switchboard", mark_price + dec!(1))],
+            timestamp: 0,
+            excluded_sources: vec![ExcludedSource::new(observed(symbol, "binance", mark_price * dec!(2)), "outlier".to_string())],
+            mode: PriceMode::SingleSource,
+            depegged_quotes: vec![],
+        }
+    }
+
+    fn event(symbol: &str, score: f64, timestamp: i64) -> ManipulationEvent {
+        ManipulationEvent { symbol: symbol.to_string(), price: dec!(60000.125), confidence: dec!(3), manipulation_score: score, timestamp }
+    }
+
+    fn feeds(pyth_feed_id: &str) -> SymbolFeeds {
+        SymbolFeeds { pyth_feed_id: Some(pyth_feed_id.to_string()), pyth_price_account: None, switchboard_address: None }
+    }
+
+    // Behaviour every backend has to share
+    async fn check_store(store: &dyn PriceStore) {
+        let now = unix_now();
+        let empty = store.price_history("BTC/USD", 0, 10).await.unwrap();
+        assert!(empty.is_empty());
+        assert_eq!(store.average_price("BTC/USD", 0).await.unwrap(), None);
+
+        for mark in [dec!(100.1), dec!(200.2), dec!(300.3)] {
+            store.insert_price(&aggregate("BTC/USD", mark)).await.unwrap();
+        }
+        store.insert_price(&aggregate("ETH/USD", dec!(1))).await.unwrap();
+
+        let history = store.price_history("BTC/USD", now - 60, 10).await.unwrap();
+        assert_eq!(history.iter().map(|p| p.mark_price).collect::<Vec<_>>(), [dec!(300.3), dec!(200.2), dec!(100.1)]);
+        let latest = &history[0];
+        assert!(latest.timestamp >= now && latest.timestamp <= unix_now());
+        assert_eq!(latest.mode, PriceMode::SingleSource);
+        assert_eq!(latest.confidence, dec!(1.25));
+        assert_eq!(latest.observations(), aggregate("BTC/USD", dec!(300.3)).observations());
+
+        assert_eq!(store.price_history("BTC/USD", now - 60, 2).await.unwrap().len(), 2);
+        assert!(store.price_history("BTC/USD", unix_now() + 60, 10).await.unwrap().is_empty());
+        assert_eq!(store.count_prices("BTC/USD", now - 60).await.unwrap(), 3);
+        assert_eq!(store.average_price("BTC/USD", now - 60).await.unwrap(), Some(dec!(200.2)));
+
+        store.insert_manipulation_event(&event("BTC/USD", 0.75, now - 7200)).await.unwrap();
+        store.insert_manipulation_event(&event("BTC/USD", 0.9, now)).await.unwrap();
+        store.insert_manipulation_event(&event("BTC/USD", 0.8, now - 60)).await.unwrap();
+        store.insert_manipulation_event(&event("ETH/USD", 0.95, now)).await.unwrap();
+        let events = store.manipulation_events("BTC/USD", now - 3600).await.unwrap();
+        assert_eq!(events, [event("BTC/USD", 0.9, now), event("BTC/USD", 0.8, now - 60)]);
+
+        store.save_symbol("DOGE/USD", &feeds("0xdoge")).await.unwrap();
+        store.save_symbol("PEPE/USD", &feeds("0xpepe")).await.unwrap();
+        store.save_symbol("DOGE/USD", &feeds("0xdoge2")).await.unwrap();
+        assert_eq!(
+            store.saved_symbols().await.unwrap(),
+            [("DOGE/USD".to_string(), feeds("0xdoge2")), ("PEPE/USD".to_string(), feeds("0xpepe"))]
+        );
+        store.remove_symbol("DOGE/USD").await.unwrap();
+        store.remove_symbol("SHIB/USD").await.unwrap();
+        assert_eq!(store.saved_symbols().await.unwrap(), [("PEPE/USD".to_string(), feeds("0xpepe"))]);
+
+        store.check_health().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_memory_store() {
+        check_store(&MemoryStore::default()).await;
+    }
+
+    #[cfg(feature = "sqlite")]
+    #[tokio::test]
+    async fn test_sqlite_store() {
+        check_store(&crate::sqlite_store::SqliteStore::connect("sqlite::memory:").await.unwrap()).await;
+    }
+
+    #[tokio::test]
+    async fn test_memory_store_keeps_latest_rows() {
+        let store = MemoryStore::new(2);
+        for mark in [dec!(1), dec!(2), dec!(3)] {
+            store.insert_price(&aggregate("BTC/USD", mark)).await.unwrap();
+        }
+        let history = store.price_history("BTC/USD", 0, 10).await.unwrap();
+        assert_eq!(history.iter().map(|p| p.mark_price).collect::<Vec<_>>(), [dec!(3), dec!(2)]);
+    }
+
+    #[tokio::test]
+    async fn test_connect_picks_backend() {
+        let store = price_store::connect("memory://").await.unwrap();
+        assert!(format!("{:?}", store).starts_with("MemoryStore"));
+
+        #[cfg(not(feature = "sqlite"))]
+        {
+            let err = price_store::connect("sqlite://oracle.db").await.unwrap_err();
+            assert!(err.to_string().contains("rebuild with --features sqlite"), "{}", err);
+        }
+    }
+
+    struct FixedClient(&'static str, Decimal);
+
+    #[async_trait]
+    impl OracleClient for FixedClient {
+        async fn get_price(&self, symbol: &str) -> Result<PriceData> {
+            Ok(quote(symbol, self.0, self.1))
+        }
+
+        async fn get_multiple_prices(&self, symbols: &[String]) -> Result<Vec<PriceData>> {
+            Ok(symbols.iter().map(|symbol| quote(symbol, self.0, self.1)).collect())
+        }
+
+        fn get_name(&self) -> &str {
+            self.0
+        }
+    }
+
+    fn manager(store: Arc<dyn PriceStore>) -> OracleManager {
+        let clients: Vec<Box<dyn OracleClient>> = vec![
+            Box::new(FixedClient("pyth", dec!(3000))),
+            Box::new(FixedClient("switchboard", dec!(3001))),
+        ];
+        OracleManager::new(store, crate::tests::dev_registry().into_shared(), clients, &CacheConfig::default())
+    }
+
+    #[tokio::test]
+    async fn test_manager_runs_without_database() {
+        let store: Arc<dyn PriceStore> = Arc::new(MemoryStore::default());
+        let oracle = manager(store.clone());
+
+        let price = oracle.get_aggregated_price("ETH/USD").await.unwrap();
+        let stored = store.price_history("ETH/USD", 0, 10).await.unwrap();
+        assert_eq!(stored.len(), 1);
+        assert_eq!(stored[0].mark_price, price.mark_price);
+        assert_eq!(stored[0].observations(), price.observations());
+
+        // Symbols added at runtime come back in the next process
+        oracle.add_trading_symbol("DOGE/USD".to_string(), feeds(&format!("0x{}", "ab".repeat(32)))).await.unwrap();
+        assert_eq!(store.saved_symbols().await.unwrap().len(), 1);
+        let restarted = manager(store.clone());
+        assert_eq!(restarted.load_persisted_symbols().await.unwrap(), 1);
+        assert!(restarted.monitored_symbols().await.contains(&"DOGE/USD".to_string()));
+
+        oracle.remove_trading_symbol("DOGE/USD").await.unwrap();
+        assert!(store.saved_symbols().await.unwrap().is_empty());
+    }
+}
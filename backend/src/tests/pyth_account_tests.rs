@@ -0,0 +1,121 @@
+#[cfg(test)]
+mod pyth_account_tests {
+    use crate::oracle_client::{FallbackClient, OracleClient, PriceData, PythAccountClient};
+    use crate::pyth_account::{decode_price_account, PythAccountError};
+    use crate::tests::mock_oracle_tests::mock_oracle_tests::MockOracleClient;
+    use base64::prelude::*;
+    use rust_decimal::Decimal;
+    use rust_decimal_macros::dec;
+
+    // getAccountInfo response for a pyth-sdk price account (expo -8)
+    const SOL_FIXTURE: &str = include_str!("fixtures/pyth_sol_usd_account.json");
+    const SOL_PRICE_ACCOUNT: &str = "H6ARHf6YXhGYeQfUzQNGk6rDNnLBQKrenN712K4AQJEG";
+
+    fn fixture_bytes() -> Vec<u8> {
+        let response: serde_json::Value = serde_json::from_str(SOL_FIXTURE).unwrap();
+        BASE64_STANDARD.decode(response["result"]["value"]["data"][0].as_str().unwrap()).unwrap()
+    }
+
+    fn mock_price(price: Decimal, source: &str) -> PriceData {
+        PriceData {
+            symbol: "SOL/USD".to_string(),
+            price,
+            confidence: dec!(0.02),
+            timestamp: 1760780411,
+            source: source.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_decode_sol_fixture() {
+        let data = fixture_bytes();
+        let account = decode_price_account(&data).unwrap();
+
+        assert_eq!(data.len(), 3312);
+        assert_eq!(account.price, 18734250000);
+        assert_eq!(account.conf, 1875000);
+        assert_eq!(account.expo, -8);
+        assert_eq!(account.publish_time, 1760780411);
+        assert_eq!(account.pub_slot, 301245119);
+    }
+
+    #[test]
+    fn test_parse_account_info_returns_price_data() {
+        let response: serde_json::Value = serde_json::from_str(SOL_FIXTURE).unwrap();
+        let price = PythAccountClient::parse_account_info("SOL/USD", SOL_PRICE_ACCOUNT, &response).unwrap();
+
+        assert_eq!(price.source, "PythOnChain");
+        assert_eq!(price.price, dec!(187.3425));
+        assert_eq!(price.confidence, dec!(0.01875));
+        assert_eq!(price.timestamp, 1760780411);
+    }
+
+    #[test]
+    fn test_rejects_foreign_and_halted_accounts() {
+        let data = fixture_bytes();
+        assert_eq!(decode_price_account(&data[..100]), Err(PythAccountError::AccountTooSmall(100)));
+
+        let mut foreign = data.clone();
+        foreign[0] = 0;
+        assert!(matches!(decode_price_account(&foreign), Err(PythAccountError::InvalidMagic(_))));
+
+        // Product account (atype 2) at a configured price address
+        let mut product = data.clone();
+        product[8..12].copy_from_slice(&2u32.to_le_bytes());
+        assert_eq!(decode_price_account(&product), Err(PythAccountError::NotPriceAccount(2)));
+
+        // agg.status = Halted
+        let mut halted = data;
+        halted[224..228].copy_from_slice(&2u32.to_le_bytes());
+        assert_eq!(decode_price_account(&halted), Err(PythAccountError::NotTrading(2)));
+    }
+
+    #[test]
+    fn test_missing_account_is_typed_error() {
+        let response = serde_json::json!({
+            "jsonrpc": "2.0",
+            "result": { "context": { "slot": 1 }, "value": null },
+            "id": 1
+        });
+        let err = PythAccountClient::parse_account_info("SOL/USD", SOL_PRICE_ACCOUNT, &response).unwrap_err();
+
+        assert_eq!(
+            err.downcast_ref::<PythAccountError>(),
+            Some(&PythAccountError::AccountNotFound(SOL_PRICE_ACCOUNT.to_string()))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_fallback_used_only_when_primary_fails() {
+        let primary = MockOracleClient::new();
+        primary.set_price("SOL/USD", mock_price(dec!(187.30), "Pyth"));
+        primary.set_failure_mode(true);
+        let fallback = MockOracleClient::new();
+        fallback.set_price("SOL/USD", mock_price(dec!(187.34), "PythOnChain"));
+
+        let client = FallbackClient::new(Box::new(primary), Box::new(fallback));
+        let price = client.get_price("SOL/USD").await.unwrap();
+        assert_eq!(price.source, "PythOnChain");
+        assert_eq!(client.get_name(), "MockOracle");
+    }
+
+    #[tokio::test]
+    async fn test_fallback_fills_symbols_missing_from_batch() {
+        let primary = MockOracleClient::new();
+        primary.set_price("SOL/USD", mock_price(dec!(187.30), "Pyth"));
+        let fallback = MockOracleClient::new();
+        fallback.set_price("SOL/USD", mock_price(dec!(187.34), "PythOnChain"));
+        fallback.set_price("BTC/USD", PriceData { symbol: "BTC/USD".to_string(), ..mock_price(dec!(67423.0), "PythOnChain") });
+
+        let client = FallbackClient::new(Box::new(primary), Box::new(fallback));
+        let prices = client
+            .get_multiple_prices(&["SOL/USD".to_string(), "BTC/USD".to_string()])
+            .await
+            .unwrap();
+
+        assert_eq!(prices.len(), 2);
+        assert_eq!(prices[0].source, "Pyth");
+        assert_eq!(prices[1].symbol, "BTC/USD");
+        assert_eq!(prices[1].source, "PythOnChain");
+    }
+}
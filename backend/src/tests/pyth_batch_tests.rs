@@ -0,0 +1,108 @@
+#[cfg(test)]
+mod pyth_batch_tests {
+    use crate::oracle_client::{scaled_decimal, OracleClient, PythClient};
+    use axum::extract::RawQuery;
+    use axum::routing::get;
+    use axum::{Json, Router};
+    use serde_json::{json, Value};
+    use tokio::net::TcpListener;
+    use rust_decimal_macros::dec;
+
+    const BTC_FEED_ID: &str = "e62df6c8b4a85fe1a67db44dc12de5db330f7ac66b72dc658afedf0f4a415b43";
+    const ETH_FEED_ID: &str = "ff61491a931112ddf1bd8147cd1b641375f79f5825126d665480874634fd0ace";
+    const SOL_FEED_ID: &str = "ef0d8b6fda2ceba41da15d4095d1da392a0d2f8ed0c6c7bc0f4cfac8c280b56d";
+    const AVAX_FEED_ID: &str = "93da3352f9f1d105fdfe4971cfa80e9dd777bfc5d0f683ebb6e1294b92137bb7";
+
+    fn entry(id: &str, price: i64) -> Value {
+        json!({
+            "id": id,
+            "price": { "price": price.to_string(), "conf": "2500000", "expo": -8, "publish_time": 1760780412 },
+            "ema_price": { "price": price.to_string(), "conf": "2500000", "expo": -8, "publish_time": 1760780412 }
+        })
+    }
+
+    /// Hermes stand-in that reverses the requested order, drops AVAX, prices SOL
+    /// negatively and slips in a feed nobody asked for
+    async fn latest(RawQuery(query): RawQuery) -> Json<Value> {
+        let query = query.unwrap_or_default();
+        let mut parsed = Vec::new();
+        for (id, price) in [
+            (SOL_FEED_ID, -1),
+            (ETH_FEED_ID, 261_050_000_000),
+            (BTC_FEED_ID, 6_742_315_000_000),
+        ] {
+            if query.contains(id) {
+                parsed.push(entry(id, price));
+            }
+        }
+        parsed.push(entry("0000000000000000000000000000000000000000000000000000000000000001", 100));
+        // Hermes normally omits the prefix; accept it either way
+        if let Some(btc) = parsed.iter_mut().find(|e| e["id"] == BTC_FEED_ID) {
+            btc["id"] = json!(format!("0x{}", BTC_FEED_ID));
+        }
+
+        Json(json!({ "binary": { "encoding": "hex", "data": [] }, "parsed": parsed }))
+    }
+
+    async fn spawn_hermes() -> String {
+        let app = Router::new().route("/v2/updates/price/latest", get(latest));
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+        base_url
+    }
+
+    #[tokio::test]
+    async fn test_batch_entries_are_matched_by_feed_id() {
+        let client = PythClient::new(spawn_hermes().await, crate::tests::dev_registry().into_shared());
+        let symbols: Vec<String> = ["DOGE/USD", "BTC/USD", "ETH/USD", "SOL/USD", "AVAX/USD"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+
+        let batch = client.fetch_batch(&symbols).await.unwrap();
+
+        assert_eq!(batch.prices.len(), 2);
+        let btc = batch.prices.iter().find(|p| p.symbol == "BTC/USD").unwrap();
+        let eth = batch.prices.iter().find(|p| p.symbol == "ETH/USD").unwrap();
+        assert_eq!(btc.price, dec!(67423.15));
+        assert_eq!(eth.price, dec!(2610.5));
+
+        assert_eq!(batch.errors.len(), 3);
+        assert!(batch.errors["DOGE/USD"].contains("feed ID not found"));
+        assert!(batch.errors["SOL/USD"].contains("Invalid price"));
+        assert!(batch.errors["AVAX/USD"].contains(AVAX_FEED_ID));
+    }
+
+    #[tokio::test]
+    async fn test_single_price_uses_matching_entry() {
+        let client = PythClient::new(spawn_hermes().await, crate::tests::dev_registry().into_shared());
+
+        let eth = client.get_price("ETH/USD").await.unwrap();
+        assert_eq!(eth.symbol, "ETH/USD");
+        assert_eq!(eth.price, dec!(2610.5));
+
+        // Only the unrequested feed comes back
+        let err = client.get_price("AVAX/USD").await.unwrap_err();
+        assert!(err.to_string().contains("not returned"));
+    }
+
+    #[test]
+    fn test_integer_prices_convert_exactly() {
+        // 67423.15000001 has no exact f64 representation
+        let feed = json!({
+            "price": { "price": "6742315000001", "conf": "2500001", "expo": -8, "publish_time": 1760780412 }
+        });
+        let price = PythClient::parse_price_feed("BTC/USD", &feed).unwrap();
+        assert_eq!(price.price, dec!(67423.15000001));
+        assert_eq!(price.confidence, dec!(0.02500001));
+
+        // Serialized as a string so JSON clients don't round it either
+        let json = serde_json::to_value(&price).unwrap();
+        assert_eq!(json["price"], "67423.15000001");
+
+        assert_eq!(scaled_decimal(25, 3).unwrap(), dec!(25000));
+        assert!(scaled_decimal(1, -29).is_err());
+        assert!(scaled_decimal(i64::MAX.into(), 12).is_err());
+    }
+}
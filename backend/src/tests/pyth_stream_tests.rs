@@ -0,0 +1,166 @@
+#[cfg(test)]
+mod pyth_stream_tests {
+    use crate::oracle_client::OracleClient;
+    use crate::pyth_stream::{take_sse_events, PythStreamClient};
+    use std::sync::{Arc, Mutex};
+    use std::time::{Duration, SystemTime, UNIX_EPOCH};
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+    use rust_decimal::Decimal;
+    use rust_decimal_macros::dec;
+
+    const BTC_FEED_ID: &str = "e62df6c8b4a85fe1a67db44dc12de5db330f7ac66b72dc658afedf0f4a415b43";
+    const ETH_FEED_ID: &str = "ff61491a931112ddf1bd8147cd1b641375f79f5825126d665480874634fd0ace";
+    const SOL_FEED_ID: &str = "ef0d8b6fda2ceba41da15d4095d1da392a0d2f8ed0c6c7bc0f4cfac8c280b56d";
+
+    fn now() -> i64 {
+        SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs() as i64
+    }
+
+    // One Hermes stream event with a single parsed price update (expo -8)
+    fn hermes_event(feed_id: &str, price: i64, publish_time: i64) -> String {
+        let update = serde_json::json!({
+            "binary": { "encoding": "hex", "data": [] },
+            "parsed": [{
+                "id": feed_id,
+                "price": { "price": price.to_string(), "conf": "2500000", "expo": -8, "publish_time": publish_time },
+                "ema_price": { "price": price.to_string(), "conf": "2500000", "expo": -8, "publish_time": publish_time },
+                "metadata": { "slot": 1, "proof_available_time": publish_time, "prev_publish_time": publish_time - 1 }
+            }]
+        });
+        format!("data:{}\n\n", update)
+    }
+
+    /// Local stand-in for Hermes: connection N streams `connections[N]`, the last one stays open
+    async fn spawn_sse_server(connections: Vec<Vec<String>>) -> (String, Arc<Mutex<Vec<String>>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let requests = Arc::new(Mutex::new(Vec::new()));
+
+        let seen = requests.clone();
+        tokio::spawn(async move {
+            let last = connections.len() - 1;
+            for (i, events) in connections.into_iter().enumerate() {
+                let (mut socket, _) = listener.accept().await.unwrap();
+                let mut request = vec![0u8; 8192];
+                let read = socket.read(&mut request).await.unwrap();
+                let request_line = String::from_utf8_lossy(&request[..read]).lines().next().unwrap_or_default().to_string();
+                seen.lock().unwrap().push(request_line);
+
+                socket.write_all(b"HTTP/1.1 200 OK\r\ncontent-type: text/event-stream\r\nconnection: close\r\n\r\n").await.unwrap();
+                socket.write_all(b":keepalive\n\n").await.unwrap();
+                for event in events {
+                    socket.write_all(event.as_bytes()).await.unwrap();
+                    tokio::time::sleep(Duration::from_millis(20)).await;
+                }
+
+                if i == last {
+                    tokio::time::sleep(Duration::from_secs(30)).await;
+                }
+            }
+        });
+
+        (base_url, requests)
+    }
+
+    async fn wait_for_price(client: &PythStreamClient, symbol: &str, expected: Decimal) {
+        for _ in 0..100 {
+            if let Ok(price) = client.get_price(symbol).await {
+                if price.price == expected {
+                    return;
+                }
+            }
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        }
+        panic!("{} never reached {}", symbol, expected);
+    }
+
+    #[test]
+    fn test_sse_events_split_across_chunks() {
+        let mut buffer = b"data: {\"a\":\ndata: 1}\n\n:keepalive\n\ndata: {\"b\"".to_vec();
+
+        assert_eq!(take_sse_events(&mut buffer), vec!["{\"a\":\n1}".to_string()]);
+        assert_eq!(buffer, b"data: {\"b\"");
+
+        buffer.extend_from_slice(b":2}\n\n");
+        assert_eq!(take_sse_events(&mut buffer), vec!["{\"b\":2}".to_string()]);
+        assert!(buffer.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_streams_latest_price_per_symbol() {
+        let (base_url, requests) = spawn_sse_server(vec![vec![
+            hermes_event(BTC_FEED_ID, 6_742_315_000_000, now()),
+            hermes_event(ETH_FEED_ID, 261_050_000_000, now()),
+            hermes_event(BTC_FEED_ID, 6_742_500_000_000, now()),
+        ]]).await;
+
+        let client = PythStreamClient::start(base_url, crate::tests::dev_registry().into_shared(), Duration::from_secs(60));
+        wait_for_price(&client, "BTC/USD", dec!(67425.0)).await;
+        wait_for_price(&client, "ETH/USD", dec!(2610.5)).await;
+
+        let btc = client.get_price("BTC/USD").await.unwrap();
+        assert_eq!(btc.source, "Pyth");
+        assert_eq!(btc.confidence, dec!(0.025));
+
+        let request = requests.lock().unwrap()[0].clone();
+        assert!(request.starts_with("GET /v2/updates/price/stream?"));
+        assert!(request.contains(&format!("ids[]={}", BTC_FEED_ID)));
+        assert!(request.contains(&format!("ids[]={}", ETH_FEED_ID)));
+        assert!(request.contains("parsed=true"));
+    }
+
+    #[tokio::test]
+    async fn test_reconnects_after_stream_closes() {
+        let (base_url, requests) = spawn_sse_server(vec![
+            vec![hermes_event(BTC_FEED_ID, 6_700_000_000_000, now())],
+            vec![hermes_event(BTC_FEED_ID, 6_710_000_000_000, now())],
+        ]).await;
+
+        let client = PythStreamClient::start(base_url, crate::tests::dev_registry().into_shared(), Duration::from_secs(60));
+        wait_for_price(&client, "BTC/USD", dec!(67100.0)).await;
+
+        assert_eq!(requests.lock().unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_prices_are_dropped_on_disconnect() {
+        let (base_url, _) = spawn_sse_server(vec![
+            vec![hermes_event(BTC_FEED_ID, 6_700_000_000_000, now())],
+            vec![hermes_event(ETH_FEED_ID, 261_050_000_000, now())],
+        ]).await;
+
+        let client = PythStreamClient::start(base_url, crate::tests::dev_registry().into_shared(), Duration::from_secs(60));
+        wait_for_price(&client, "ETH/USD", dec!(2610.5)).await;
+
+        // BTC only came over the first connection
+        let err = client.get_price("BTC/USD").await.unwrap_err();
+        assert!(err.to_string().contains("No streamed Pyth price"), "{}", err);
+    }
+
+    #[tokio::test]
+    async fn test_stale_and_unknown_symbols_are_rejected() {
+        let (base_url, _) = spawn_sse_server(vec![vec![
+            hermes_event(BTC_FEED_ID, 6_742_315_000_000, now() - 600),
+            // Within max_price_age, but past the 30s max_source_age_seconds
+            hermes_event(SOL_FEED_ID, 15_000_000_000, now() - 45),
+            hermes_event(ETH_FEED_ID, 261_050_000_000, now()),
+        ]]).await;
+
+        let client = PythStreamClient::start(base_url, crate::tests::dev_registry().into_shared(), Duration::from_secs(60));
+        wait_for_price(&client, "ETH/USD", dec!(2610.5)).await;
+
+        let err = client.get_price("BTC/USD").await.unwrap_err();
+        assert!(err.to_string().contains("old"));
+        let err = client.get_price("SOL/USD").await.unwrap_err();
+        assert!(err.to_string().contains("45s old"), "{}", err);
+        assert!(client.get_price("DOGE/USD").await.is_err());
+
+        let prices = client
+            .get_multiple_prices(&["BTC/USD".to_string(), "ETH/USD".to_string()])
+            .await
+            .unwrap();
+        assert_eq!(prices.len(), 1);
+        assert_eq!(prices[0].symbol, "ETH/USD");
+    }
+}
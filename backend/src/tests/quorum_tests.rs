@@ -0,0 +1,120 @@
+#[cfg(test)]
+mod quorum_tests {
+    use crate::aggregation::{DegradedMode, PriceMode, QuorumPolicy};
+    use crate::config::OracleFileConfig;
+    use crate::feed_registry::FeedRegistry;
+    use crate::oracle_client::{AggregatedPrice, PriceData};
+    use anyhow::anyhow;
+    use rust_decimal_macros::dec;
+
+    const NOW: i64 = 1760780412;
+
+    fn aggregate(sources: &[&str], timestamp: i64) -> AggregatedPrice {
+        AggregatedPrice {
+            symbol: "BTC/USD".to_string(),
+            mark_price: dec!(67423.15),
+            index_price: dec!(67423.15),
+            confidence: dec!(20.0),
+            sources: sources.iter()
+                .map(|source| PriceData {
+                    symbol: "BTC/USD".to_string(),
+                    price: dec!(67423.15),
+                    confidence: dec!(20.0),
+                    timestamp,
+                    source: source.to_string(),
+                })
+                .collect(),
+            timestamp,
+            excluded_sources: vec![],
+            mode: PriceMode::Normal,
+            depegged_quotes: vec![],
+        }
+    }
+
+    fn policy(degraded_mode: DegradedMode) -> QuorumPolicy {
+        QuorumPolicy { min_sources: 2, degraded_mode, ..QuorumPolicy::default() }
+    }
+
+    #[test]
+    fn test_quorum_met_passes_through() {
+        for mode in [DegradedMode::Reject, DegradedMode::LastGood, DegradedMode::SingleSource] {
+            let price = policy(mode).apply("BTC/USD", Ok(aggregate(&["Pyth", "Switchboard"], NOW)), None, NOW).unwrap();
+            assert_eq!(price.mode, PriceMode::Normal);
+            assert_eq!(price.confidence, dec!(20.0));
+        }
+    }
+
+    #[test]
+    fn test_reject_below_quorum() {
+        let err = policy(DegradedMode::Reject)
+            .apply("BTC/USD", Ok(aggregate(&["Pyth"], NOW)), Some(&aggregate(&["Pyth", "Switchboard"], NOW)), NOW)
+            .unwrap_err();
+        assert!(err.to_string().contains("Only 1 of 2 required sources"));
+    }
+
+    #[test]
+    fn test_single_source_widens_confidence() {
+        let price = policy(DegradedMode::SingleSource)
+            .apply("BTC/USD", Ok(aggregate(&["Pyth"], NOW)), None, NOW)
+            .unwrap();
+        assert_eq!(price.mode, PriceMode::SingleSource);
+        assert_eq!(price.confidence, dec!(40.0));
+        assert_eq!(price.mark_price, dec!(67423.15));
+
+        // Nothing to serve when every source failed
+        let err = policy(DegradedMode::SingleSource)
+            .apply("BTC/USD", Err(anyhow!("No price data available from any oracle source")), None, NOW)
+            .unwrap_err();
+        assert!(err.to_string().contains("No price data"));
+    }
+
+    #[test]
+    fn test_last_good_is_served_flagged_until_it_expires() {
+        let last_good = aggregate(&["Pyth", "Switchboard"], NOW - 120);
+        let policy = policy(DegradedMode::LastGood);
+
+        let price = policy.apply("BTC/USD", Err(anyhow!("Sources disagree")), Some(&last_good), NOW).unwrap();
+        assert_eq!(price.mode, PriceMode::LastGood);
+        assert_eq!(price.timestamp, NOW - 120);
+        assert_eq!(price.sources.len(), 2);
+
+        let price = policy.apply("BTC/USD", Ok(aggregate(&["Pyth"], NOW)), Some(&last_good), NOW).unwrap();
+        assert_eq!(price.mode, PriceMode::LastGood);
+
+        // Past last_good_max_age_seconds (300)
+        let err = policy.apply("BTC/USD", Ok(aggregate(&["Pyth"], NOW)), Some(&last_good), NOW + 200).unwrap_err();
+        assert!(format!("{:#}", err).contains("No last good BTC/USD price within 300s"));
+        assert!(policy.apply("BTC/USD", Err(anyhow!("down")), None, NOW).is_err());
+    }
+
+    #[test]
+    fn test_quorum_policy_per_symbol() {
+        let registry = crate::tests::dev_registry();
+
+        let btc = registry.quorum_policy("BTC/USD");
+        assert_eq!((btc.min_sources, btc.degraded_mode), (3, DegradedMode::LastGood));
+        assert_eq!(btc.confidence_multiplier, 2.0);
+
+        let eth = registry.quorum_policy("ETH/USD");
+        assert_eq!(eth, QuorumPolicy::default());
+
+        let contents = std::fs::read_to_string("../config/dev.toml").unwrap();
+        let zero = contents.replace("min_sources = 3", "min_sources = 0");
+        let err = FeedRegistry::from_config(&OracleFileConfig::parse(&zero).unwrap()).unwrap_err();
+        assert!(err.to_string().contains("min_sources for BTC/USD"));
+
+        let narrowing = contents.replace("confidence_multiplier = 2.0", "confidence_multiplier = 0.5");
+        assert!(FeedRegistry::from_config(&OracleFileConfig::parse(&narrowing).unwrap()).is_err());
+        assert!(OracleFileConfig::parse(&contents.replace("\"last_good\"", "\"fallback\"")).is_err());
+    }
+
+    #[test]
+    fn test_stored_mode_names() {
+        for mode in [PriceMode::Normal, PriceMode::LastGood, PriceMode::SingleSource] {
+            // Stored names match what the API returns
+            assert_eq!(serde_json::to_value(mode).unwrap(), mode.as_str());
+            assert_eq!(PriceMode::parse(mode.as_str()).unwrap(), mode);
+        }
+        assert!(PriceMode::parse("degraded").is_err());
+    }
+}
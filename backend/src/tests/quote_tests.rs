@@ -0,0 +1,160 @@
+#[cfg(test)]
+mod quote_tests {
+    use crate::cex_client::{CexClient, Exchange};
+    use crate::config::{CacheConfig, OracleFileConfig};
+    use crate::error::OracleError;
+    use crate::feed_registry::FeedRegistry;
+    use crate::memory_store::MemoryStore;
+    use crate::oracle_client::{OracleClient, OracleManager, PriceData};
+    use crate::quote::{DepegAction, QuoteAsset, QuoteConversion, QuoteRate};
+    use anyhow::anyhow;
+    use rust_decimal::Decimal;
+    use rust_decimal_macros::dec;
+    use std::collections::HashMap;
+    use std::sync::Arc;
+
+    fn price(source: &str, price: Decimal, confidence: Decimal) -> PriceData {
+        PriceData {
+            symbol: "BTC/USD".to_string(),
+            price,
+            confidence,
+            timestamp: 1760780412,
+            source: source.to_string(),
+        }
+    }
+
+    fn rate(asset: QuoteAsset, rate: Decimal) -> QuoteRate {
+        QuoteRate { asset, rate, confidence: dec!(0.0001) }
+    }
+
+    fn conversion(depeg_action: DepegAction) -> QuoteConversion {
+        QuoteConversion {
+            feeds: HashMap::from([(QuoteAsset::Usdt, "USDT/USD".to_string())]),
+            max_depeg: dec!(0.01),
+            depeg_action,
+        }
+    }
+
+    #[test]
+    fn test_converts_stablecoin_quotes_to_usd() {
+        let rates = HashMap::from([(QuoteAsset::Usdt, Ok(rate(QuoteAsset::Usdt, dec!(0.999))))]);
+        let normalized = conversion(DepegAction::Exclude).normalize(
+            "BTC/USD",
+            vec![
+                (price("Pyth", dec!(67420), dec!(20)), None),
+                (price("Binance", dec!(67500), dec!(0.05)), Some(QuoteAsset::Usdt)),
+                (price("Coinbase", dec!(67425), dec!(1)), Some(QuoteAsset::Usd)),
+            ],
+            &rates,
+        );
+
+        assert!(normalized.excluded.is_empty());
+        assert!(normalized.depegged.is_empty());
+        let prices: Vec<Decimal> = normalized.prices.iter().map(|p| p.price).collect();
+        assert_eq!(prices, [dec!(67420), dec!(67432.5), dec!(67425)]);
+        // 0.05 * 0.999 + 67500 * 0.0001
+        assert_eq!(normalized.prices[1].confidence, dec!(6.79995));
+        assert_eq!(normalized.prices[1].source, "Binance");
+    }
+
+    #[test]
+    fn test_depeg_excludes_or_flags() {
+        let rates = HashMap::from([(QuoteAsset::Usdt, Ok(rate(QuoteAsset::Usdt, dec!(0.97))))]);
+        let prices = || vec![
+            (price("Pyth", dec!(67420), dec!(20)), None),
+            (price("Binance", dec!(69500), dec!(0.05)), Some(QuoteAsset::Usdt)),
+        ];
+
+        let excluded = conversion(DepegAction::Exclude).normalize("BTC/USD", prices(), &rates);
+        assert_eq!(excluded.prices.len(), 1);
+        assert_eq!(excluded.excluded[0].source, "Binance");
+        assert_eq!(excluded.excluded[0].reason, "Quoted in USDT, 3.00% off its USD peg at 0.97");
+        assert!(excluded.depegged.is_empty());
+
+        let flagged = conversion(DepegAction::Flag).normalize("BTC/USD", prices(), &rates);
+        assert!(flagged.excluded.is_empty());
+        assert_eq!(flagged.prices[1].price, dec!(67415));
+        assert_eq!(flagged.depegged, [rate(QuoteAsset::Usdt, dec!(0.97))]);
+    }
+
+    #[test]
+    fn test_excludes_sources_without_a_rate() {
+        let rates = HashMap::from([(QuoteAsset::Usdt, Err(anyhow!("No price data available from any oracle source")))]);
+        let normalized = conversion(DepegAction::Exclude).normalize(
+            "BTC/USD",
+            vec![
+                (price("Binance", dec!(67500), dec!(0.05)), Some(QuoteAsset::Usdt)),
+                (price("Kraken", dec!(67480), dec!(0.05)), Some(QuoteAsset::Usdc)),
+            ],
+            &rates,
+        );
+        assert!(normalized.prices.is_empty());
+        let reasons: Vec<&str> = normalized.excluded.iter().map(|e| e.reason.as_str()).collect();
+        assert_eq!(reasons, [
+            "Quoted in USDT, no USDT/USD rate: No price data available from any oracle source",
+            "Quoted in USDC, no conversion to USD",
+        ]);
+
+        // Only USD targets convert
+        let normalized = conversion(DepegAction::Exclude).normalize(
+            "ETH/BTC",
+            vec![(price("Binance", dec!(0.04), dec!(0.00001)), Some(QuoteAsset::Usdt))],
+            &rates,
+        );
+        assert_eq!(normalized.excluded[0].reason, "Quoted in USDT, no conversion to ETH/BTC");
+    }
+
+    #[test]
+    fn test_exchange_quotes() {
+        let binance = CexClient::new(Exchange::Binance, None, HashMap::new());
+        assert_eq!(binance.quote_asset("BTC/USD"), Some(QuoteAsset::Usdt));
+        assert_eq!(CexClient::new(Exchange::Coinbase, None, HashMap::new()).quote_asset("BTC/USD"), None);
+
+        let contents = std::fs::read_to_string("../config/dev.toml").unwrap();
+        let config = OracleFileConfig::parse(&contents.replace(
+            "name = \"kraken\"",
+            "name = \"kraken\"\nquotes = { \"BTC/USD\" = \"USDC\" }",
+        )).unwrap();
+        let kraken = config.exchanges.iter().find(|e| e.name == Exchange::Kraken).unwrap();
+        assert_eq!(CexClient::from_config(kraken).quote_asset("BTC/USD"), Some(QuoteAsset::Usdc));
+        assert_eq!(CexClient::from_config(kraken).quote_asset("ETH/USD"), None);
+    }
+
+    #[test]
+    fn test_quote_conversion_from_config() {
+        let registry = crate::tests::dev_registry();
+        let conversion = registry.quote_conversion();
+        assert_eq!(conversion.feeds[&QuoteAsset::Usdc], "USDC/USD");
+        assert_eq!(conversion.max_depeg, dec!(0.01));
+        assert_eq!(conversion.depeg_action, DepegAction::Exclude);
+
+        let contents = std::fs::read_to_string("../config/dev.toml").unwrap();
+        let rejects = |from: &str, to: &str, message: &str| {
+            let config = OracleFileConfig::parse(&contents.replace(from, to)).unwrap();
+            let err = FeedRegistry::from_config(&config).unwrap_err();
+            assert!(err.to_string().contains(message), "{}", err);
+        };
+        rejects("USDT = \"USDT/USD\"", "USDT = \"DAI/USD\"", "DAI/USD must be a trading pair with its own feeds");
+        rejects("USDT = \"USDT/USD\"", "USDT = \"ETH/BTC\"", "must price a stablecoin in USD");
+        rejects("USDT = \"USDT/USD\"", "USD = \"USDT/USD\"", "must price a stablecoin in USD");
+        rejects("max_depeg_bps = 100", "max_depeg_bps = 0", "max_depeg_bps must be in (0, 10000)");
+    }
+
+    #[tokio::test]
+    async fn test_quote_feeds_cannot_be_removed() {
+        let registry = crate::tests::dev_registry();
+        assert_eq!(registry.quote_dependents("USDT/USD"), ["USDT"]);
+        assert!(registry.quote_dependents("BTC/USD").is_empty());
+
+        let oracle = OracleManager::new(Arc::new(MemoryStore::default()), registry.into_shared(), vec![], &CacheConfig::default());
+        let in_use = |err: anyhow::Error| match OracleError::from(err) {
+            error @ OracleError::SymbolInUse { .. } => error.to_string(),
+            error => panic!("{:?}", error),
+        };
+        let err = in_use(oracle.remove_trading_symbol("USDT/USD").await.unwrap_err());
+        assert_eq!(err, "Symbol USDT/USD is still used by USDT quote conversion");
+        let err = in_use(oracle.remove_trading_symbol("ETH/USD").await.unwrap_err());
+        assert_eq!(err, "Symbol ETH/USD is still used by ETH/BTC, SOL/ETH");
+        assert!(oracle.monitored_symbols().await.contains(&"USDT/USD".to_string()));
+    }
+}
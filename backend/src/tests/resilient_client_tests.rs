@@ -0,0 +1,237 @@
+#[cfg(test)]
+mod resilient_client_tests {
+    use crate::config::{OracleFileConfig, ResilienceConfig};
+    use crate::feed_registry::FeedRegistry;
+    use crate::oracle_client::{OracleClient, PriceData};
+    use crate::resilient_client::{BreakerState, CircuitBreaker, ResilientClient};
+    use crate::tests::mock_oracle_tests::mock_oracle_tests::MockOracleClient;
+    use anyhow::{anyhow, Result};
+    use async_trait::async_trait;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use std::time::Duration;
+    use rust_decimal_macros::dec;
+
+    /// Mock that fails a set number of calls before delegating, and counts every call.
+    /// With `only` set it has a feed for that symbol alone.
+    struct FlakyClient {
+        mock: Arc<MockOracleClient>,
+        failures_left: AtomicUsize,
+        calls: Arc<AtomicUsize>,
+        only: Option<&'static str>,
+    }
+
+    #[async_trait]
+    impl OracleClient for FlakyClient {
+        async fn get_price(&self, symbol: &str) -> Result<PriceData> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            let failing = self.failures_left
+                .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |n| n.checked_sub(1))
+                .is_ok();
+            if failing {
+                return Err(anyhow!("Transient failure"));
+            }
+            self.mock.get_price(symbol).await
+        }
+
+        async fn get_multiple_prices(&self, symbols: &[String]) -> Result<Vec<PriceData>> {
+            self.mock.get_multiple_prices(symbols).await
+        }
+
+        fn get_name(&self) -> &str {
+            self.mock.get_name()
+        }
+
+        async fn supports(&self, symbol: &str) -> bool {
+            self.only.map_or(true, |only| only == symbol)
+        }
+    }
+
+    fn test_config() -> ResilienceConfig {
+        ResilienceConfig {
+            max_attempts: 3,
+            initial_backoff_ms: 1,
+            max_backoff_ms: 5,
+            jitter: 0.0,
+            call_deadline_ms: 1000,
+            breaker_window: 10,
+            breaker_min_calls: 3,
+            breaker_error_rate: 0.5,
+            breaker_open_ms: 100,
+        }
+    }
+
+    fn btc_mock() -> MockOracleClient {
+        let mock = MockOracleClient::new();
+        mock.set_price("BTC/USD", PriceData {
+            symbol: "BTC/USD".to_string(),
+            price: dec!(67423.15),
+            confidence: dec!(25.0),
+            timestamp: chrono::Utc::now().timestamp(),
+            source: "MockOracle".to_string(),
+        });
+        mock
+    }
+
+    fn flaky(mock: Arc<MockOracleClient>, failures: usize, config: ResilienceConfig) -> (ResilientClient, Arc<AtomicUsize>) {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let client = FlakyClient { mock, failures_left: AtomicUsize::new(failures), calls: calls.clone(), only: None };
+        (ResilientClient::new(Box::new(client), config), calls)
+    }
+
+    #[tokio::test]
+    async fn test_retries_through_transient_failures() {
+        let (client, calls) = flaky(Arc::new(btc_mock()), 2, test_config());
+
+        let price = client.get_price("BTC/USD").await.unwrap();
+        assert_eq!(price.price, dec!(67423.15));
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+
+        let snapshot = client.circuit_breaker().unwrap();
+        assert_eq!(snapshot.state, BreakerState::Closed);
+        assert_eq!(snapshot.recent_calls, 1);
+        assert_eq!(snapshot.error_rate, 0.0);
+    }
+
+    #[tokio::test]
+    async fn test_gives_up_after_max_attempts() {
+        let (client, calls) = flaky(Arc::new(btc_mock()), 5, test_config());
+
+        let err = client.get_price("BTC/USD").await.unwrap_err();
+        assert!(err.to_string().contains("Transient failure"));
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+        assert_eq!(client.circuit_breaker().unwrap().error_rate, 1.0);
+    }
+
+    #[tokio::test]
+    async fn test_deadline_covers_slow_sources() {
+        let mut mock = btc_mock();
+        mock.set_latency(500);
+        let config = ResilienceConfig { call_deadline_ms: 50, ..test_config() };
+        let client = ResilientClient::new(Box::new(mock), config);
+
+        let started = std::time::Instant::now();
+        let err = client.get_price("BTC/USD").await.unwrap_err();
+        assert!(err.to_string().contains("deadline"));
+        assert!(started.elapsed() < Duration::from_millis(400));
+    }
+
+    #[tokio::test]
+    async fn test_breaker_opens_and_recovers_through_half_open_probe() {
+        let mock = Arc::new(btc_mock());
+        mock.set_failure_mode(true);
+        let config = ResilienceConfig { max_attempts: 1, ..test_config() };
+        let (client, calls) = flaky(mock.clone(), 0, config);
+
+        for _ in 0..3 {
+            assert!(client.get_price("BTC/USD").await.is_err());
+        }
+        assert_eq!(client.circuit_breaker().unwrap().state, BreakerState::Open);
+
+        // Rejected without reaching the source
+        let err = client.get_price("BTC/USD").await.unwrap_err();
+        assert!(err.to_string().contains("Circuit breaker open"));
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+
+        // A failed probe re-opens the breaker
+        tokio::time::sleep(Duration::from_millis(120)).await;
+        assert!(client.get_price("BTC/USD").await.is_err());
+        assert_eq!(calls.load(Ordering::SeqCst), 4);
+        assert_eq!(client.circuit_breaker().unwrap().state, BreakerState::Open);
+
+        // A successful probe closes it again
+        mock.set_failure_mode(false);
+        tokio::time::sleep(Duration::from_millis(120)).await;
+        assert!(client.get_price("BTC/USD").await.is_ok());
+        let snapshot = client.circuit_breaker().unwrap();
+        assert_eq!(snapshot.state, BreakerState::Closed);
+        assert_eq!(snapshot.recent_calls, 0);
+    }
+
+    #[tokio::test]
+    async fn test_unsupported_symbols_skip_retries_and_breaker() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let inner = FlakyClient { mock: Arc::new(btc_mock()), failures_left: AtomicUsize::new(0), calls: calls.clone(), only: Some("BTC/USD") };
+        let config = ResilienceConfig { initial_backoff_ms: 100, max_backoff_ms: 100, ..test_config() };
+        let client = ResilientClient::new(Box::new(inner), config);
+
+        let started = std::time::Instant::now();
+        for _ in 0..5 {
+            assert!(client.get_price("BTC/USD").await.is_ok());
+            let err = client.get_price("AVAX/USD").await.unwrap_err();
+            assert!(err.to_string().contains("has no feed for AVAX/USD"), "{}", err);
+        }
+        assert!(started.elapsed() < Duration::from_millis(100));
+        assert_eq!(calls.load(Ordering::SeqCst), 5);
+        assert!(!client.supports("AVAX/USD").await);
+
+        let snapshot = client.circuit_breaker().unwrap();
+        assert_eq!(snapshot.state, BreakerState::Closed);
+        assert_eq!((snapshot.recent_calls, snapshot.error_rate), (5, 0.0));
+    }
+
+    #[test]
+    fn test_half_open_allows_a_single_probe() {
+        let breaker = CircuitBreaker::new(ResilienceConfig { breaker_open_ms: 0, ..test_config() });
+
+        assert_eq!(breaker.record(true), None);
+        assert_eq!(breaker.record(false), None);
+        assert_eq!(breaker.record(false), Some(BreakerState::Open));
+
+        let probe = breaker.allow().unwrap();
+        assert_eq!(breaker.snapshot().state, BreakerState::HalfOpen);
+        assert!(breaker.allow().is_none());
+        assert_eq!(probe.record(true), Some(BreakerState::Closed));
+        assert!(breaker.allow().is_some());
+    }
+
+    #[tokio::test]
+    async fn test_dropped_probe_releases_half_open_breaker() {
+        let mut mock = btc_mock();
+        mock.set_latency(200);
+        let config = ResilienceConfig { max_attempts: 1, breaker_open_ms: 50, ..test_config() };
+        let (client, calls) = flaky(Arc::new(mock), 3, config);
+
+        for _ in 0..3 {
+            assert!(client.get_price("BTC/USD").await.is_err());
+        }
+        assert_eq!(client.circuit_breaker().unwrap().state, BreakerState::Open);
+
+        // The caller gives up while the probe is still waiting on the source
+        tokio::time::sleep(Duration::from_millis(60)).await;
+        assert!(tokio::time::timeout(Duration::from_millis(20), client.get_price("BTC/USD")).await.is_err());
+        assert_eq!(calls.load(Ordering::SeqCst), 4);
+        assert_eq!(client.circuit_breaker().unwrap().state, BreakerState::Open);
+
+        // Counted as a failed probe, so the next one goes through after the cooldown
+        tokio::time::sleep(Duration::from_millis(60)).await;
+        assert!(client.get_price("BTC/USD").await.is_ok());
+        assert_eq!(calls.load(Ordering::SeqCst), 5);
+        assert_eq!(client.circuit_breaker().unwrap().state, BreakerState::Closed);
+    }
+
+    #[test]
+    fn test_breaker_needs_min_calls_before_opening() {
+        let breaker = CircuitBreaker::new(test_config());
+
+        assert_eq!(breaker.record(false), None);
+        assert_eq!(breaker.record(false), None);
+        assert_eq!(breaker.snapshot().state, BreakerState::Closed);
+        assert_eq!(breaker.snapshot().error_rate, 1.0);
+    }
+
+    #[test]
+    fn test_rejects_bad_resilience_settings() {
+        let contents = std::fs::read_to_string("../config/dev.toml").unwrap();
+        let rejects = |from: &str, to: &str, message: &str| {
+            let config = OracleFileConfig::parse(&contents.replace(from, to)).unwrap();
+            let err = FeedRegistry::from_config(&config).unwrap_err();
+            assert!(err.to_string().contains(message), "{}", err);
+        };
+        rejects("jitter = 0.5", "jitter = -0.1", "oracle.resilience: jitter must be in [0, 1], got -0.1");
+        rejects("jitter = 0.5", "jitter = nan", "oracle.resilience: jitter must be in [0, 1], got NaN");
+        rejects("breaker_error_rate = 0.5", "breaker_error_rate = 0.0", "oracle.resilience: breaker_error_rate must be in (0, 1], got 0");
+        rejects("breaker_error_rate = 0.5", "breaker_error_rate = 1.5", "breaker_error_rate must be in (0, 1], got 1.5");
+        rejects("breaker_window = 20", "breaker_window = 4", "breaker_window 4 is smaller than breaker_min_calls 5");
+    }
+}
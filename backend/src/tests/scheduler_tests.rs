@@ -0,0 +1,261 @@
+#[cfg(test)]
+mod scheduler_tests {
+    use crate::config::OracleFileConfig;
+    use crate::feed_registry::FeedRegistry;
+    use crate::aggregation::PriceMode;
+    use crate::oracle_client::{AggregatedPrice, PriceData};
+    use crate::price_aggregator::ManipulationDetector;
+    use crate::scheduler::{MarketActivity, RefreshSchedule, SymbolScheduler};
+    use rust_decimal::Decimal;
+    use rust_decimal_macros::dec;
+    use anyhow::anyhow;
+    use std::collections::HashMap;
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+
+    fn every(ms: u64) -> RefreshSchedule {
+        RefreshSchedule::fixed(Duration::from_millis(ms))
+    }
+
+    fn between(min_ms: u64, max_ms: u64) -> RefreshSchedule {
+        RefreshSchedule {
+            min_interval: Duration::from_millis(min_ms),
+            max_interval: Duration::from_millis(max_ms),
+            ..every(max_ms)
+        }
+    }
+
+    // Counts refreshes per symbol; "SLOW/USD" takes a second per refresh, "BAD/USD" always
+    // fails and "HOT/USD" reports a volatile market
+    fn counting_refresh(counts: Arc<Mutex<HashMap<String, usize>>>) -> impl Fn(String) -> futures::future::BoxFuture<'static, anyhow::Result<MarketActivity>> + Clone + Send + 'static {
+        move |symbol: String| {
+            let counts = counts.clone();
+            Box::pin(async move {
+                if symbol == "SLOW/USD" {
+                    tokio::time::sleep(Duration::from_secs(1)).await;
+                }
+                *counts.lock().unwrap().entry(symbol.clone()).or_default() += 1;
+                if symbol == "BAD/USD" {
+                    return Err(anyhow!("upstream down"));
+                }
+                if symbol == "HOT/USD" {
+                    return Ok(MarketActivity { volatility: 0.01, dispersion: 0.0 });
+                }
+                Ok(MarketActivity::default())
+            })
+        }
+    }
+
+    #[test]
+    fn test_jittered_delay_stays_in_range() {
+        let schedule = every(100).with_jitter(0.2);
+        for _ in 0..1000 {
+            let delay = schedule.next_delay(Duration::from_millis(100));
+            assert!(delay >= Duration::from_millis(80) && delay <= Duration::from_millis(120), "{:?}", delay);
+        }
+        assert_eq!(every(100).next_delay(Duration::from_millis(40)), Duration::from_millis(40));
+    }
+
+    #[test]
+    fn test_interval_follows_activity() {
+        let schedule = between(100, 1000);
+        let interval = |volatility, dispersion| schedule.interval_for(&MarketActivity { volatility, dispersion }).as_millis();
+        assert_eq!(interval(0.0, 0.0), 1000);
+        // Halfway to the 50bps volatility threshold
+        assert_eq!(interval(0.0025, 0.0), 550);
+        // The busier signal wins
+        assert_eq!(interval(0.001, 0.0025), 100);
+        assert_eq!(interval(0.5, 0.0), 100);
+        // Unusable measurements are ignored
+        assert_eq!(interval(f64::NAN, 0.0025), 100);
+        assert_eq!(every(250).interval_for(&MarketActivity { volatility: 0.5, dispersion: 0.0 }).as_millis(), 250);
+    }
+
+    fn source(symbol: &str, price: Decimal) -> PriceData {
+        PriceData { symbol: symbol.to_string(), price, confidence: dec!(1), timestamp: 0, source: "test".to_string() }
+    }
+
+    #[test]
+    fn test_source_dispersion() {
+        let mut price = AggregatedPrice {
+            symbol: "ETH/BTC".to_string(),
+            mark_price: dec!(0.05),
+            index_price: dec!(0.05),
+            confidence: dec!(0),
+            sources: vec![source("ETH/USD", dec!(3000)), source("ETH/USD", dec!(3003)), source("BTC/USD", dec!(60000))],
+            timestamp: 0,
+            excluded_sources: vec![],
+            mode: PriceMode::Normal,
+            depegged_quotes: vec![],
+        };
+        // Legs are measured separately, not against each other
+        assert!((price.source_dispersion() - 0.0009995).abs() < 1e-6, "{}", price.source_dispersion());
+
+        price.sources.truncate(1);
+        assert_eq!(price.source_dispersion(), 0.0);
+    }
+
+    #[tokio::test]
+    async fn test_realized_volatility() {
+        let detector = ManipulationDetector::new();
+        detector.analyze_price("BTC/USD", 60000.0, 1000).await;
+        assert_eq!(detector.realized_volatility("BTC/USD").await, None);
+        assert_eq!(detector.realized_volatility("ETH/USD").await, None);
+
+        // Flat prices over a minute
+        for ts in 1001..=1060 {
+            detector.analyze_price("BTC/USD", 60000.0, ts).await;
+        }
+        assert_eq!(detector.realized_volatility("BTC/USD").await, Some(0.0));
+
+        // One 1% move: about 1% per minute over the last minute
+        detector.analyze_price("BTC/USD", 60600.0, 1060).await;
+        let volatility = detector.realized_volatility("BTC/USD").await.unwrap();
+        assert!((volatility - 0.01).abs() < 0.0005, "{}", volatility);
+        // Cached prices are scored without joining the history
+        detector.score_price("BTC/USD", 60600.0).await;
+        assert_eq!(detector.realized_volatility("BTC/USD").await, Some(volatility));
+
+        // Moves older than a minute no longer count
+        for ts in 1065..=1125 {
+            detector.analyze_price("BTC/USD", 60600.0, ts).await;
+        }
+        assert_eq!(detector.realized_volatility("BTC/USD").await, Some(0.0));
+    }
+
+    #[tokio::test]
+    async fn test_scheduler_adapts_interval() {
+        let counts = Arc::new(Mutex::new(HashMap::new()));
+        let scheduler = SymbolScheduler::default();
+        scheduler.reconcile(
+            &[("HOT/USD".to_string(), between(20, 200)), ("CALM/USD".to_string(), between(20, 200)), ("BAD/USD".to_string(), between(20, 200))],
+            counting_refresh(counts.clone()),
+        );
+        tokio::time::sleep(Duration::from_millis(300)).await;
+
+        let counts = counts.lock().unwrap().clone();
+        assert!(counts["HOT/USD"] >= 8, "{:?}", counts);
+        assert!(counts["CALM/USD"] <= 3, "{:?}", counts);
+
+        let status = scheduler.status();
+        let interval = |symbol: &str| status.iter().find(|s| s.symbol == symbol).unwrap().interval_ms;
+        assert_eq!((interval("HOT/USD"), interval("CALM/USD")), (20, 200));
+        // Failing symbols keep their starting interval
+        assert_eq!(interval("BAD/USD"), 200);
+        let hot = status.iter().find(|s| s.symbol == "HOT/USD").unwrap();
+        assert_eq!(hot.activity.volatility, 0.01);
+        assert_eq!((hot.min_interval_ms, hot.max_interval_ms), (20, 200));
+    }
+
+    #[tokio::test]
+    async fn test_slow_symbol_does_not_hold_up_others() {
+        let counts = Arc::new(Mutex::new(HashMap::new()));
+        let scheduler = SymbolScheduler::default();
+        scheduler.reconcile(
+            &[("BTC/USD".to_string(), every(20)), ("AVAX/USD".to_string(), every(100)), ("SLOW/USD".to_string(), every(20))],
+            counting_refresh(counts.clone()),
+        );
+        tokio::time::sleep(Duration::from_millis(300)).await;
+
+        let counts = counts.lock().unwrap().clone();
+        assert!(counts["BTC/USD"] >= 8, "{:?}", counts);
+        assert!(counts["AVAX/USD"] >= 2 && counts["AVAX/USD"] < counts["BTC/USD"], "{:?}", counts);
+        assert!(!counts.contains_key("SLOW/USD"));
+
+        let status = scheduler.status();
+        assert_eq!(status.iter().map(|s| s.symbol.as_str()).collect::<Vec<_>>(), ["AVAX/USD", "BTC/USD", "SLOW/USD"]);
+        assert_eq!(status[1].interval_ms, 20);
+        assert!(status[1].last_update_ms.is_some() && status[1].update_lag_ms < 100);
+        // Never refreshed: lag counts from when it was scheduled
+        assert!(status[2].last_update_ms.is_none() && status[2].update_lag_ms >= 250);
+    }
+
+    #[tokio::test]
+    async fn test_symbols_added_and_removed_at_runtime() {
+        let counts = Arc::new(Mutex::new(HashMap::new()));
+        let scheduler = SymbolScheduler::default();
+        let refresh = counting_refresh(counts.clone());
+        scheduler.reconcile(&[("BTC/USD".to_string(), every(20))], refresh.clone());
+        tokio::time::sleep(Duration::from_millis(50)).await;
+
+        scheduler.reconcile(&[("ETH/USD".to_string(), every(20)), ("BAD/USD".to_string(), every(20))], refresh.clone());
+        let removed_at = counts.lock().unwrap()["BTC/USD"];
+        tokio::time::sleep(Duration::from_millis(100)).await;
+
+        let counts = counts.lock().unwrap().clone();
+        assert_eq!(counts["BTC/USD"], removed_at);
+        assert!(counts["ETH/USD"] >= 3, "{:?}", counts);
+
+        let status = scheduler.status();
+        assert_eq!(status.len(), 2);
+        let bad = status.iter().find(|s| s.symbol == "BAD/USD").unwrap();
+        assert!(bad.consecutive_failures >= 3 && bad.last_update_ms.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_changed_schedule_applies_to_running_task() {
+        let counts = Arc::new(Mutex::new(HashMap::new()));
+        let scheduler = SymbolScheduler::default();
+        let refresh = counting_refresh(counts.clone());
+        scheduler.reconcile(&[("HOT/USD".to_string(), between(50, 100)), ("CALM/USD".to_string(), every(50))], refresh.clone());
+        tokio::time::sleep(Duration::from_millis(20)).await;
+
+        scheduler.reconcile(&[("HOT/USD".to_string(), between(10, 100)), ("CALM/USD".to_string(), every(200))], refresh.clone());
+        let status = scheduler.status();
+        let ms = |symbol: &str| {
+            let status = status.iter().find(|s| s.symbol == symbol).unwrap();
+            (status.interval_ms, status.min_interval_ms, status.max_interval_ms)
+        };
+        // Busy symbols move straight to the new minimum
+        assert_eq!(ms("HOT/USD"), (10, 10, 100));
+        assert_eq!(ms("CALM/USD"), (200, 200, 200));
+
+        let before = counts.lock().unwrap().clone();
+        tokio::time::sleep(Duration::from_millis(300)).await;
+        let after = counts.lock().unwrap().clone();
+        assert!(after["HOT/USD"] - before["HOT/USD"] >= 10, "{:?} {:?}", before, after);
+        // The wait already under way finishes, then the new interval applies
+        assert!(after["CALM/USD"] - before["CALM/USD"] <= 2, "{:?} {:?}", before, after);
+    }
+
+    #[test]
+    fn test_schedules_from_config() {
+        let registry = crate::tests::dev_registry();
+        let ms = |schedule: RefreshSchedule| {
+            (schedule.interval.as_millis(), schedule.min_interval.as_millis(), schedule.max_interval.as_millis())
+        };
+        assert_eq!(ms(registry.refresh_schedule("BTC/USD")), (100, 50, 1000));
+        assert_eq!(ms(registry.refresh_schedule("AVAX/USD")), (1000, 100, 2000));
+        let defaults = registry.refresh_schedule("ETH/USD");
+        assert_eq!(ms(defaults), (250, 100, 1000));
+        assert_eq!((defaults.jitter, defaults.volatility_threshold, defaults.dispersion_threshold), (0.1, 0.005, 0.0025));
+        // Symbols added at runtime get the default schedule
+        assert_eq!(registry.refresh_schedule("DOGE/USD"), defaults);
+
+        // Without a range the interval stays fixed
+        let contents = std::fs::read_to_string("../config/dev.toml").unwrap();
+        let fixed = contents.replace("min_interval_ms = 100\nmax_interval_ms = 1000\n", "");
+        let registry = FeedRegistry::from_config(&OracleFileConfig::parse(&fixed).unwrap()).unwrap();
+        assert_eq!(ms(registry.refresh_schedule("ETH/USD")), (250, 250, 250));
+
+        // Minimums never go below the cache TTL
+        let cached = contents.replace("ttl_ms = 50\n", "ttl_ms = 200\n");
+        let registry = FeedRegistry::from_config(&OracleFileConfig::parse(&cached).unwrap()).unwrap();
+        assert_eq!(ms(registry.refresh_schedule("BTC/USD")), (200, 200, 1000));
+        assert_eq!(ms(registry.refresh_schedule("AVAX/USD")), (1000, 200, 2000));
+        assert_eq!(ms(registry.refresh_schedule("ETH/USD")), (250, 200, 1000));
+        assert_eq!(ms(registry.refresh_schedule("DOGE/USD")), (250, 200, 1000));
+
+        let rejects = |from: &str, to: &str, message: &str| {
+            let config = OracleFileConfig::parse(&contents.replace(from, to)).unwrap();
+            let err = FeedRegistry::from_config(&config).unwrap_err();
+            assert!(err.to_string().contains(message), "{}", err);
+        };
+        rejects("jitter = 0.1", "jitter = 1.0", "oracle.schedule.jitter must be in [0, 1), got 1");
+        rejects("min_interval_ms = 100", "min_interval_ms = 5", "oracle.schedule: minimum interval must be at least 10ms, got 5ms");
+        rejects("max_interval_ms = 1000", "max_interval_ms = 200", "oracle.schedule: interval 250ms must be within 100ms..=200ms");
+        rejects("volatility_bps = 50", "volatility_bps = 0", "oracle.schedule: volatility_bps and dispersion_bps must be positive");
+        rejects("min_refresh_interval_ms = 50", "min_refresh_interval_ms = 0", "refresh intervals for BTC/USD: minimum interval must be at least 10ms, got 0ms");
+        rejects("max_refresh_interval_ms = 2000", "max_refresh_interval_ms = 500", "refresh intervals for AVAX/USD: interval 1000ms must be within 100ms..=500ms");
+    }
+}
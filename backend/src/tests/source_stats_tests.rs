@@ -0,0 +1,90 @@
+#[cfg(test)]
+mod source_stats_tests {
+    use crate::oracle_client::SourceStatus;
+    use crate::resilient_client::{BreakerSnapshot, BreakerState};
+    use crate::source_stats::{CacheStats, SourceStats};
+    use std::time::{Duration, Instant};
+
+    fn ms(millis: u64) -> Duration {
+        Duration::from_millis(millis)
+    }
+
+    #[test]
+    fn test_counts_and_latency_percentiles() {
+        let stats = SourceStats::new(Duration::from_secs(60));
+        let start = Instant::now();
+        for latency in 1..=100 {
+            stats.record_at(start, 1760780412, latency % 10 != 0, ms(latency * 10));
+        }
+
+        let snapshot = stats.snapshot_at(start);
+        assert_eq!(snapshot.window_seconds, 60);
+        assert_eq!(snapshot.success_count, 90);
+        assert_eq!(snapshot.failure_count, 10);
+        assert!((snapshot.error_rate - 0.1).abs() < 1e-9);
+        assert_eq!(snapshot.last_success, Some(1760780412));
+
+        let latency = snapshot.latency.unwrap();
+        assert_eq!(latency.p50_ms, 500);
+        assert_eq!(latency.p95_ms, 950);
+        assert_eq!(latency.p99_ms, 990);
+        assert_eq!(latency.max_ms, 1000);
+
+        // Buckets: <=10, <=25, <=50, <=100, <=250, <=500, <=1000, <=2500, overflow
+        let counts: Vec<u64> = latency.histogram.iter().map(|b| b.count).collect();
+        assert_eq!(counts, vec![1, 1, 3, 5, 15, 25, 50, 0, 0]);
+        assert_eq!(latency.histogram.last().unwrap().le_ms, None);
+    }
+
+    #[test]
+    fn test_samples_leave_the_window() {
+        let stats = SourceStats::new(Duration::from_secs(60));
+        let start = Instant::now();
+        stats.record_at(start, 1000, true, ms(20));
+        stats.record_at(start + Duration::from_secs(30), 1030, false, ms(5000));
+
+        let snapshot = stats.snapshot_at(start + Duration::from_secs(75));
+        assert_eq!(snapshot.success_count, 0);
+        assert_eq!(snapshot.failure_count, 1);
+        assert_eq!(snapshot.error_rate, 1.0);
+        assert_eq!(snapshot.latency.unwrap().histogram.last().unwrap().count, 1);
+        // Last success is remembered past the window
+        assert_eq!(snapshot.last_success, Some(1000));
+
+        let idle = stats.snapshot_at(start + Duration::from_secs(120));
+        assert_eq!(idle.failure_count, 0);
+        assert_eq!(idle.error_rate, 0.0);
+        assert!(idle.latency.is_none());
+    }
+
+    #[test]
+    fn test_source_health_from_counters() {
+        let stats = SourceStats::new(Duration::from_secs(60));
+        assert!(!SourceStatus::new("Pyth", None, stats.snapshot()).is_healthy);
+
+        stats.record(true, ms(40));
+        assert!(SourceStatus::new("Pyth", None, stats.snapshot()).is_healthy);
+
+        let open = BreakerSnapshot { state: BreakerState::Open, error_rate: 1.0, recent_calls: 5 };
+        assert!(!SourceStatus::new("Pyth", Some(open), stats.snapshot()).is_healthy);
+    }
+
+    #[test]
+    fn test_cache_hit_rate() {
+        let cache = CacheStats::default();
+        assert_eq!(cache.snapshot().hit_rate, 0.0);
+
+        cache.record_miss();
+        for _ in 0..3 {
+            cache.record_hit();
+        }
+        let snapshot = cache.snapshot();
+        assert_eq!((snapshot.hits, snapshot.misses), (3, 1));
+        assert_eq!(snapshot.hit_rate, 0.75);
+
+        cache.record_stale();
+        cache.record_coalesced();
+        let snapshot = cache.snapshot();
+        assert_eq!((snapshot.stale, snapshot.coalesced, snapshot.hit_rate), (1, 1, 0.75));
+    }
+}
@@ -0,0 +1,98 @@
+#[cfg(test)]
+mod switchboard_decoding_tests {
+    use crate::oracle_client::SwitchboardClient;
+    use crate::switchboard_account::{decode_aggregator_account, SwitchboardError, AGGREGATOR_ACCOUNT_SIZE};
+    use base64::prelude::*;
+    use rust_decimal_macros::dec;
+
+    // getAccountInfo responses in the Switchboard V2 aggregator layout
+    const BTC_FIXTURE: &str = include_str!("fixtures/switchboard_btc_usd.json");
+    const SOL_FIXTURE: &str = include_str!("fixtures/switchboard_sol_usd.json");
+    const BTC_AGGREGATOR: &str = "8SXvChNYFhRq4EZuZvnhjrB3jJRQCv4k3P4W6hesH3Ee";
+
+    fn fixture_bytes(fixture: &str) -> Vec<u8> {
+        let response: serde_json::Value = serde_json::from_str(fixture).unwrap();
+        BASE64_STANDARD.decode(response["result"]["value"]["data"][0].as_str().unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_decode_btc_fixture() {
+        let round = decode_aggregator_account(&fixture_bytes(BTC_FIXTURE)).unwrap();
+
+        assert_eq!(round.num_success, 3);
+        assert_eq!(round.round_open_slot, 301245117);
+        assert_eq!(round.round_open_timestamp, 1760780412);
+        assert_eq!(round.result, dec!(67423.15));
+        assert_eq!(round.std_deviation, dec!(12.5));
+    }
+
+    #[test]
+    fn test_parse_account_info_returns_price_data() {
+        let response: serde_json::Value = serde_json::from_str(SOL_FIXTURE).unwrap();
+        let price = SwitchboardClient::parse_account_info("SOL/USD", "GvDMxPzN1sCj7L26YDK2HnMRXEQmQ2aemov8YBtPS7vR", &response).unwrap();
+
+        assert_eq!(price.symbol, "SOL/USD");
+        assert_eq!(price.source, "Switchboard");
+        assert_eq!(price.price, dec!(187.3425));
+        assert_eq!(price.confidence, dec!(0.0215));
+        assert_eq!(price.timestamp, 1760780409);
+    }
+
+    #[test]
+    fn test_missing_account_is_typed_error() {
+        let response = serde_json::json!({
+            "jsonrpc": "2.0",
+            "result": { "context": { "slot": 1 }, "value": null },
+            "id": 1
+        });
+        let err = SwitchboardClient::parse_account_info("BTC/USD", BTC_AGGREGATOR, &response).unwrap_err();
+
+        assert_eq!(
+            err.downcast_ref::<SwitchboardError>(),
+            Some(&SwitchboardError::AccountNotFound(BTC_AGGREGATOR.to_string()))
+        );
+    }
+
+    #[test]
+    fn test_non_base64_encoding_is_rejected() {
+        let mut response: serde_json::Value = serde_json::from_str(BTC_FIXTURE).unwrap();
+        response["result"]["value"]["data"][1] = serde_json::json!("base58");
+        let err = SwitchboardClient::parse_account_info("BTC/USD", BTC_AGGREGATOR, &response).unwrap_err();
+
+        assert!(matches!(err.downcast_ref::<SwitchboardError>(), Some(SwitchboardError::InvalidEncoding(_))));
+    }
+
+    #[test]
+    fn test_truncated_and_foreign_accounts_are_rejected() {
+        let data = fixture_bytes(BTC_FIXTURE);
+        assert_eq!(
+            decode_aggregator_account(&data[..200]),
+            Err(SwitchboardError::AccountTooSmall(200))
+        );
+
+        let mut foreign = data.clone();
+        foreign[0] ^= 0xff;
+        assert_eq!(decode_aggregator_account(&foreign), Err(SwitchboardError::InvalidDiscriminator));
+        assert_eq!(data.len(), AGGREGATOR_ACCOUNT_SIZE);
+    }
+
+    #[test]
+    fn test_unconfirmed_and_non_positive_rounds_are_rejected() {
+        let data = fixture_bytes(BTC_FIXTURE);
+
+        // latest_confirmed_round.num_success
+        let mut unconfirmed = data.clone();
+        unconfirmed[341..345].copy_from_slice(&0u32.to_le_bytes());
+        assert_eq!(decode_aggregator_account(&unconfirmed), Err(SwitchboardError::NoConfirmedRound));
+
+        // latest_confirmed_round.result mantissa
+        let mut negative = data.clone();
+        negative[366..382].copy_from_slice(&(-5i128).to_le_bytes());
+        assert!(matches!(decode_aggregator_account(&negative), Err(SwitchboardError::NonPositiveResult(_))));
+
+        // latest_confirmed_round.result scale
+        let mut bad_scale = data;
+        bad_scale[382..386].copy_from_slice(&40u32.to_le_bytes());
+        assert_eq!(decode_aggregator_account(&bad_scale), Err(SwitchboardError::InvalidScale(40)));
+    }
+}
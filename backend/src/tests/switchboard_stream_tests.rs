@@ -0,0 +1,168 @@
+#[cfg(test)]
+mod switchboard_stream_tests {
+    use crate::config::OracleFileConfig;
+    use crate::feed_registry::FeedRegistry;
+    use crate::oracle_client::{FallbackClient, OracleClient, PriceData};
+    use crate::switchboard_stream::SwitchboardStreamClient;
+    use crate::tests::mock_oracle_tests::mock_oracle_tests::MockOracleClient;
+    use base64::prelude::*;
+    use futures::{SinkExt, StreamExt};
+    use std::sync::{Arc, Mutex};
+    use std::time::{Duration, SystemTime, UNIX_EPOCH};
+    use tokio::net::TcpListener;
+    use tokio_tungstenite::{accept_async, tungstenite::Message};
+    use rust_decimal_macros::dec;
+
+    const BTC_FIXTURE: &str = include_str!("fixtures/switchboard_btc_usd.json");
+    const BTC_AGGREGATOR: &str = "8SXvChNYFhRq4EZuZvnhjrB3jJRQCv4k3P4W6hesH3Ee";
+
+    // accountNotification carrying the BTC/USD fixture with a round opened `round_age` seconds ago
+    fn btc_notification(subscription: u64, round_age: i64) -> String {
+        let fixture: serde_json::Value = serde_json::from_str(BTC_FIXTURE).unwrap();
+        let mut data = BASE64_STANDARD.decode(fixture["result"]["value"]["data"][0].as_str().unwrap()).unwrap();
+        let opened = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs() as i64 - round_age;
+        data[358..366].copy_from_slice(&opened.to_le_bytes()); // latest_confirmed_round.round_open_timestamp
+
+        let mut value = fixture["result"]["value"].clone();
+        value["data"][0] = serde_json::json!(BASE64_STANDARD.encode(data));
+        serde_json::json!({
+            "jsonrpc": "2.0",
+            "method": "accountNotification",
+            "params": {
+                "result": { "context": { "slot": 301245200 }, "value": value },
+                "subscription": subscription
+            }
+        }).to_string()
+    }
+
+    /// Local stand-in for a Solana RPC websocket. Confirms every subscription and
+    /// pushes the BTC fixture `round_age` seconds old; the first connection is dropped when
+    /// `drop_first` is set.
+    async fn spawn_ws_server(drop_first: bool, round_age: i64) -> (String, Arc<Mutex<Vec<serde_json::Value>>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let ws_url = format!("ws://{}", listener.local_addr().unwrap());
+        let requests = Arc::new(Mutex::new(Vec::new()));
+
+        let seen = requests.clone();
+        tokio::spawn(async move {
+            let mut connection = 0;
+            loop {
+                let (stream, _) = listener.accept().await.unwrap();
+                let mut socket = accept_async(stream).await.unwrap();
+                connection += 1;
+
+                let mut btc_subscription = None;
+                while let Some(Ok(Message::Text(text))) = socket.next().await {
+                    let request: serde_json::Value = serde_json::from_str(&text).unwrap();
+                    let subscription = 1000 + request["id"].as_u64().unwrap();
+                    if request["params"][0] == BTC_AGGREGATOR {
+                        btc_subscription = Some(subscription);
+                    }
+                    seen.lock().unwrap().push(request.clone());
+
+                    let confirmation = serde_json::json!({ "jsonrpc": "2.0", "result": subscription, "id": request["id"] });
+                    socket.send(Message::Text(confirmation.to_string())).await.unwrap();
+
+                    // Dev config has three aggregators
+                    if seen.lock().unwrap().len() % 3 == 0 {
+                        break;
+                    }
+                }
+
+                socket.send(Message::Text(btc_notification(btc_subscription.unwrap(), round_age))).await.unwrap();
+                if drop_first && connection == 1 {
+                    tokio::time::sleep(Duration::from_millis(300)).await;
+                    drop(socket);
+                    // Stay down long enough for callers to observe the outage
+                    tokio::time::sleep(Duration::from_millis(1500)).await;
+                    continue;
+                }
+                tokio::time::sleep(Duration::from_secs(30)).await;
+            }
+        });
+
+        (ws_url, requests)
+    }
+
+    async fn wait_until<F: std::future::Future<Output = bool>>(mut check: impl FnMut() -> F) {
+        for _ in 0..100 {
+            if check().await {
+                return;
+            }
+            tokio::time::sleep(Duration::from_millis(25)).await;
+        }
+        panic!("condition never became true");
+    }
+
+    fn start_client(ws_url: String) -> SwitchboardStreamClient {
+        start_with_registry(ws_url, crate::tests::dev_registry())
+    }
+
+    fn start_with_registry(ws_url: String, registry: FeedRegistry) -> SwitchboardStreamClient {
+        SwitchboardStreamClient::start(ws_url, "confirmed".to_string(), registry.into_shared(), Duration::from_secs(60))
+    }
+
+    #[tokio::test]
+    async fn test_subscribes_to_each_aggregator_and_decodes_updates() {
+        let (ws_url, requests) = spawn_ws_server(false, 0).await;
+        let client = start_client(ws_url);
+
+        wait_until(|| async { client.get_price("BTC/USD").await.is_ok() }).await;
+        let price = client.get_price("BTC/USD").await.unwrap();
+        assert_eq!(price.source, "Switchboard");
+        assert_eq!(price.price, dec!(67423.15));
+        assert_eq!(price.confidence, dec!(12.5));
+
+        let requests = requests.lock().unwrap().clone();
+        assert_eq!(requests.len(), 3);
+        assert!(requests.iter().all(|r| r["method"] == "accountSubscribe"));
+        assert!(requests.iter().all(|r| r["params"][1]["encoding"] == "base64" && r["params"][1]["commitment"] == "confirmed"));
+        assert!(requests.iter().any(|r| r["params"][0] == BTC_AGGREGATOR));
+
+        // Subscribed but never notified
+        assert!(client.get_price("ETH/USD").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_falls_back_to_polling_while_socket_is_down() {
+        let (ws_url, requests) = spawn_ws_server(true, 0).await;
+        let polling = MockOracleClient::new();
+        polling.set_price("BTC/USD", PriceData {
+            symbol: "BTC/USD".to_string(),
+            price: dec!(67000.0),
+            confidence: dec!(20.0),
+            timestamp: 1760780412,
+            source: "Switchboard".to_string(),
+        });
+        let client = FallbackClient::new(Box::new(start_client(ws_url)), Box::new(polling));
+
+        wait_until(|| async { client.get_price("BTC/USD").await.is_ok_and(|p| p.price > dec!(67400)) }).await;
+        // Socket dropped: served by the polling client
+        wait_until(|| async { client.get_price("BTC/USD").await.is_ok_and(|p| p.price == dec!(67000)) }).await;
+        // Reconnected and resubscribed
+        wait_until(|| async { client.get_price("BTC/USD").await.is_ok_and(|p| p.price > dec!(67400)) }).await;
+
+        assert_eq!(requests.lock().unwrap().len(), 6);
+    }
+
+    #[tokio::test]
+    async fn test_rounds_past_the_symbols_source_age_are_rejected() {
+        // Within the 30s default and the client's 60s, but past BTC's own limit
+        let contents = std::fs::read_to_string("../config/dev.toml").unwrap().replace(
+            r#"bounds = { min_price = "1000", max_price = "10000000" }"#,
+            r#"bounds = { min_price = "1000", max_price = "10000000", max_source_age_seconds = 5 }"#,
+        );
+        let registry = FeedRegistry::from_config(&OracleFileConfig::parse(&contents).unwrap()).unwrap();
+        assert_eq!(registry.price_bounds("BTC/USD").max_source_age_seconds, 5);
+
+        let (ws_url, _) = spawn_ws_server(false, 10).await;
+        let strict = start_with_registry(ws_url, registry);
+        wait_until(|| async { strict.get_price("BTC/USD").await.is_err_and(|e| e.to_string().contains("old")) }).await;
+        let err = strict.get_price("BTC/USD").await.unwrap_err();
+        assert!(err.to_string().contains("Streamed Switchboard price for BTC/USD is 1"), "{}", err);
+
+        let (ws_url, _) = spawn_ws_server(false, 10).await;
+        let lenient = start_client(ws_url);
+        wait_until(|| async { lenient.get_price("BTC/USD").await.is_ok() }).await;
+    }
+}
@@ -0,0 +1,101 @@
+#[cfg(test)]
+mod synthetic_tests {
+    use crate::aggregation::PriceMode;
+    use crate::config::OracleFileConfig;
+    use crate::feed_registry::FeedRegistry;
+    use crate::oracle_client::{AggregatedPrice, PriceData};
+    use crate::synthetic::SyntheticPair;
+    use rust_decimal::Decimal;
+    use rust_decimal_macros::dec;
+
+    fn leg(symbol: &str, price: Decimal, confidence: Decimal, timestamp: i64) -> AggregatedPrice {
+        AggregatedPrice {
+            symbol: symbol.to_string(),
+            mark_price: price,
+            index_price: price,
+            confidence,
+            sources: vec![PriceData {
+                symbol: symbol.to_string(),
+                price,
+                confidence,
+                timestamp,
+                source: "Pyth".to_string(),
+            }],
+            timestamp,
+            excluded_sources: vec![],
+            mode: PriceMode::Normal,
+            depegged_quotes: vec![],
+        }
+    }
+
+    fn pair(multiply: &[&str], divide: &[&str]) -> SyntheticPair {
+        SyntheticPair {
+            multiply: multiply.iter().map(|s| s.to_string()).collect(),
+            divide: divide.iter().map(|s| s.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn test_quotient_propagates_relative_confidence() {
+        let eth = leg("ETH/USD", dec!(2600), dec!(2.6), 1760780412); // 0.1%
+        let btc = leg("BTC/USD", dec!(65000), dec!(32.5), 1760780405); // 0.05%
+
+        let price = pair(&["ETH/USD"], &["BTC/USD"]).combine("ETH/BTC", &[eth], &[btc]).unwrap();
+        assert_eq!(price.symbol, "ETH/BTC");
+        assert_eq!(price.mark_price, dec!(0.04));
+        assert_eq!(price.index_price, dec!(0.04));
+        assert_eq!(price.confidence, dec!(0.00006)); // 0.15% of the price
+        assert_eq!(price.timestamp, 1760780405); // Older leg
+        assert_eq!(price.mode, PriceMode::Normal);
+
+        let legs: Vec<&str> = price.sources.iter().map(|s| s.symbol.as_str()).collect();
+        assert_eq!(legs, ["ETH/USD", "BTC/USD"]);
+    }
+
+    #[test]
+    fn test_product_and_degraded_legs() {
+        let sol = leg("SOL/USD", dec!(150), dec!(0.15), 1760780412);
+        let usd_jpy = AggregatedPrice {
+            mode: PriceMode::SingleSource,
+            ..leg("USD/JPY", dec!(150.5), dec!(0.301), 1760780410)
+        };
+
+        let price = pair(&["SOL/USD", "USD/JPY"], &[]).combine("SOL/JPY", &[sol.clone(), usd_jpy], &[]).unwrap();
+        assert_eq!(price.mark_price, dec!(22575));
+        assert_eq!(price.confidence, dec!(67.725)); // 0.1% + 0.2%
+        assert_eq!(price.mode, PriceMode::SingleSource);
+
+        let stale_btc = AggregatedPrice { mode: PriceMode::LastGood, ..leg("BTC/USD", dec!(65000), dec!(30), 1760780300) };
+        let price = pair(&["SOL/USD"], &["BTC/USD"]).combine("SOL/BTC", std::slice::from_ref(&sol), &[stale_btc]).unwrap();
+        assert_eq!(price.mode, PriceMode::LastGood);
+
+        let zero = leg("BTC/USD", Decimal::ZERO, dec!(1), 1760780412);
+        assert!(pair(&["SOL/USD"], &["BTC/USD"]).combine("SOL/BTC", std::slice::from_ref(&sol), &[zero]).is_err());
+        assert!(pair(&["SOL/USD"], &["BTC/USD"]).combine("SOL/BTC", &[sol], &[]).is_err());
+    }
+
+    #[test]
+    fn test_synthetic_pairs_from_config() {
+        let registry = crate::tests::dev_registry();
+        assert_eq!(registry.synthetic("ETH/BTC"), Some(&pair(&["ETH/USD"], &["BTC/USD"])));
+        assert_eq!(registry.synthetic("ETH/USD"), None);
+        assert_eq!(registry.synthetic_dependents("ETH/USD"), ["ETH/BTC", "SOL/ETH"]);
+        assert!(registry.synthetic_dependents("AVAX/USD").is_empty());
+
+        let contents = std::fs::read_to_string("../config/dev.toml").unwrap();
+        let rejects = |from: &str, to: &str, message: &str| {
+            let config = OracleFileConfig::parse(&contents.replace(from, to)).unwrap();
+            let err = FeedRegistry::from_config(&config).unwrap_err();
+            assert!(err.to_string().contains(message), "{}", err);
+        };
+        rejects("divide = [\"BTC/USD\"]", "divide = [\"DOGE/USD\"]", "unknown trading pair DOGE/USD");
+        rejects("divide = [\"ETH/USD\"]", "divide = [\"ETH/BTC\"]", "uses synthetic pair ETH/BTC");
+        rejects("multiply = [\"ETH/USD\"]", "multiply = []", "at least one multiply leg");
+        rejects(
+            "synthetic = { multiply = [\"SOL/USD\"]",
+            "min_sources = 1\nsynthetic = { multiply = [\"SOL/USD\"]",
+            "takes its aggregation and quorum from its legs",
+        );
+        rejects("symbol = \"AVAX/USD\"\nfeed_id", "symbol = \"ETH/BTC\"\nfeed_id", "Pyth feed configured for synthetic pair ETH/BTC");
+    }
+}
@@ -0,0 +1,14 @@
+-- One row per aggregated price the oracle published
+CREATE TABLE price_feeds (
+    id BIGSERIAL PRIMARY KEY,
+    symbol TEXT NOT NULL,
+    mark_price NUMERIC NOT NULL,
+    index_price NUMERIC NOT NULL,
+    confidence NUMERIC NOT NULL,
+    source_count INTEGER NOT NULL,
+    -- How the price was produced: normal, last_good or single_source
+    mode TEXT NOT NULL DEFAULT 'normal',
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX price_feeds_symbol_created_at_idx ON price_feeds (symbol, created_at DESC);
@@ -0,0 +1,15 @@
+-- The individual source prices behind each aggregate, including the excluded ones
+CREATE TABLE source_observations (
+    id BIGSERIAL PRIMARY KEY,
+    price_feed_id BIGINT NOT NULL REFERENCES price_feeds (id) ON DELETE CASCADE,
+    -- Differs from the aggregate's symbol for the legs of synthetic pairs
+    symbol TEXT NOT NULL,
+    source TEXT NOT NULL,
+    price NUMERIC NOT NULL,
+    confidence NUMERIC,
+    observed_at TIMESTAMPTZ,
+    -- Why the source was left out of the aggregate; NULL if it was used
+    excluded_reason TEXT
+);
+
+CREATE INDEX source_observations_price_feed_id_idx ON source_observations (price_feed_id);
@@ -0,0 +1,11 @@
+-- Prices whose manipulation score crossed the alert threshold
+CREATE TABLE manipulation_events (
+    id BIGSERIAL PRIMARY KEY,
+    symbol TEXT NOT NULL,
+    price NUMERIC NOT NULL,
+    confidence NUMERIC NOT NULL,
+    manipulation_score DOUBLE PRECISION NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX manipulation_events_symbol_created_at_idx ON manipulation_events (symbol, created_at DESC);
@@ -0,0 +1,8 @@
+-- Symbols added at runtime through the admin API, restored on startup
+CREATE TABLE trading_symbols (
+    symbol TEXT PRIMARY KEY,
+    pyth_feed_id TEXT,
+    pyth_price_account TEXT,
+    switchboard_address TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
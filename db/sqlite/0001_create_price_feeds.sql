@@ -0,0 +1,13 @@
+-- SQLite mirror of db/0001; decimals are stored as text and times as unix seconds
+CREATE TABLE price_feeds (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    symbol TEXT NOT NULL,
+    mark_price TEXT NOT NULL,
+    index_price TEXT NOT NULL,
+    confidence TEXT NOT NULL,
+    source_count INTEGER NOT NULL,
+    mode TEXT NOT NULL DEFAULT 'normal',
+    created_at INTEGER NOT NULL
+);
+
+CREATE INDEX price_feeds_symbol_created_at_idx ON price_feeds (symbol, created_at DESC);
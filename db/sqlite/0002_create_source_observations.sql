@@ -0,0 +1,13 @@
+-- SQLite mirror of db/0002
+CREATE TABLE source_observations (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    price_feed_id INTEGER NOT NULL REFERENCES price_feeds (id) ON DELETE CASCADE,
+    symbol TEXT NOT NULL,
+    source TEXT NOT NULL,
+    price TEXT NOT NULL,
+    confidence TEXT,
+    observed_at INTEGER,
+    excluded_reason TEXT
+);
+
+CREATE INDEX source_observations_price_feed_id_idx ON source_observations (price_feed_id);
@@ -0,0 +1,11 @@
+-- SQLite mirror of db/0003
+CREATE TABLE manipulation_events (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    symbol TEXT NOT NULL,
+    price TEXT NOT NULL,
+    confidence TEXT NOT NULL,
+    manipulation_score REAL NOT NULL,
+    created_at INTEGER NOT NULL
+);
+
+CREATE INDEX manipulation_events_symbol_created_at_idx ON manipulation_events (symbol, created_at DESC);
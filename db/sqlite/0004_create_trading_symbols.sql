@@ -0,0 +1,8 @@
+-- SQLite mirror of db/0004
+CREATE TABLE trading_symbols (
+    symbol TEXT PRIMARY KEY,
+    pyth_feed_id TEXT,
+    pyth_price_account TEXT,
+    switchboard_address TEXT,
+    created_at INTEGER NOT NULL
+);
@@ -86,10 +86,10 @@ else
     echo -e "❌ ${RED}Backend Service${NC} - Missing backend main.rs"
 fi
 
-if [ -f "./db/schema.sql" ]; then
-    echo -e "✅ ${GREEN}Database Schema${NC} - PostgreSQL schema ready"
+if ls ./db/*.sql > /dev/null 2>&1; then
+    echo -e "✅ ${GREEN}Database Schema${NC} - PostgreSQL migrations ready"
 else
-    echo -e "❌ ${RED}Database Schema${NC} - Missing schema.sql"
+    echo -e "❌ ${RED}Database Schema${NC} - Missing migrations in db/"
 fi
 
 if [ -f "./docker-compose.yml" ] && [ -f "./docker-compose.dev.yml" ]; then